@@ -0,0 +1,648 @@
+//! Check digits and check characters of the supported symbologies.
+//!
+//! Each scheme has a `compute_` function, which returns the check digit or characters of data
+//! without them, and a `validate_` function, which checks data ending with them. Data can be
+//! checked without creating a barcode:
+//!
+//! ```rust
+//! use barcoders::checksum;
+//!
+//! assert_eq!(checksum::compute_gtin("400638133393").unwrap(), '1');
+//! assert!(checksum::validate_gtin("4006381333931").is_ok());
+//! assert!(checksum::validate_code39("CODE39W").is_ok());
+//! ```
+//!
+//! Invalid characters and lengths are reported as they are by the encoders. A check digit that
+//! does not match is reported as `Error::Checksum`, with the computed and the given check digits.
+//! Check characters are reported by their values in the scheme, so that digits are reported as
+//! digit values (with 'X' as 10) and other characters by their positions in the character set of
+//! the scheme, such as 32 for the 'W' of Code39.
+
+use crate::error::{Error, Result};
+use crate::sym::code128::Code128;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+
+// The characters of the modulo-43 scheme of Code39, in order of their values.
+const CODE39_CHARS: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ-. $/+%";
+
+// The characters of the modulo-47 scheme of Code93, in order of their values. The full-ASCII
+// shift characters are represented with (, ), [, ].
+const CODE93_CHARS: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ-. $/+%()[]";
+
+// The characters of the modulo-11 scheme of Code11, in order of their values.
+const CODE11_CHARS: &str = "0123456789-";
+
+/// Computes the modulo-10 check digit used by GS1 numbers, UPC and 2-of-5 barcodes.
+/// The digits are weighted alternately by 3 and 1, starting from the rightmost digit.
+pub fn compute_modulo_10<T: AsRef<str>>(data: T) -> Result<char> {
+    let digits = digits(data.as_ref(), 1..=usize::MAX)?;
+
+    Ok(digit_char(gs1_digit(&digits)))
+}
+
+/// Validates data ending with a modulo-10 check digit.
+pub fn validate_modulo_10<T: AsRef<str>>(data: T) -> Result<()> {
+    validate_digits(data.as_ref(), 2..=usize::MAX, gs1_digit)
+}
+
+/// Computes the check digit of a GTIN-8, GTIN-12, GTIN-13 or GTIN-14 from its first 7, 11, 12 or
+/// 13 digits.
+pub fn compute_gtin<T: AsRef<str>>(data: T) -> Result<char> {
+    let digits = gtin_digits(data.as_ref(), &[7, 11, 12, 13])?;
+
+    Ok(digit_char(gs1_digit(&digits)))
+}
+
+/// Validates a GTIN-8, GTIN-12, GTIN-13 or GTIN-14, including its check digit.
+pub fn validate_gtin<T: AsRef<str>>(data: T) -> Result<()> {
+    let digits = gtin_digits(data.as_ref(), &[8, 12, 13, 14])?;
+    let (&provided, digits) = digits.split_last().expect("Data is not empty");
+
+    check(gs1_digit(digits), provided)
+}
+
+/// Computes the check digit of an SSCC (Serial Shipping Container Code) from its first 17 digits.
+pub fn compute_sscc<T: AsRef<str>>(data: T) -> Result<char> {
+    digits(data.as_ref(), 17..=17)?;
+    compute_modulo_10(data)
+}
+
+/// Validates an 18 digit SSCC (Serial Shipping Container Code), including its check digit.
+pub fn validate_sscc<T: AsRef<str>>(data: T) -> Result<()> {
+    digits(data.as_ref(), 18..=18)?;
+    validate_modulo_10(data)
+}
+
+/// Computes the check digit of a GLN (Global Location Number) from its first 12 digits.
+pub fn compute_gln<T: AsRef<str>>(data: T) -> Result<char> {
+    digits(data.as_ref(), 12..=12)?;
+    compute_modulo_10(data)
+}
+
+/// Validates a 13 digit GLN (Global Location Number), including its check digit.
+pub fn validate_gln<T: AsRef<str>>(data: T) -> Result<()> {
+    digits(data.as_ref(), 13..=13)?;
+    validate_modulo_10(data)
+}
+
+/// Computes the modulo-11 check character of an ISBN-10 or ISSN from its first 9 or 7 digits.
+/// The digits are weighted in descending order down to 2, and a check digit of 10 is 'X'.
+pub fn compute_modulo_11<T: AsRef<str>>(data: T) -> Result<char> {
+    digits(data.as_ref(), 1..=usize::MAX).map(|digits| digit_char(modulo_11_digit(&digits)))
+}
+
+/// Validates data ending with a modulo-11 check character, such as an ISBN-10 or ISSN.
+pub fn validate_modulo_11<T: AsRef<str>>(data: T) -> Result<()> {
+    let data = data.as_ref();
+    let len = data.chars().count();
+
+    // Only the check character may be an X.
+    let (i, c) = match data.char_indices().last() {
+        Some(last) if len > 1 => last,
+        _ => return Err(Error::length(2..=usize::MAX, len)),
+    };
+    let digits = digits(&data[..i], 1..=usize::MAX)?;
+    let provided = match c {
+        'X' | 'x' => 10,
+        c => c.to_digit(10).ok_or(Error::character(len - 1, c))? as u8,
+    };
+
+    check(modulo_11_digit(&digits), provided)
+}
+
+/// Computes the check digit of an EAN-5 supplemental barcode. The check digit is not part of the
+/// data, but is encoded in the pattern of the barcode.
+pub fn compute_ean5<T: AsRef<str>>(data: T) -> Result<char> {
+    digits(data.as_ref(), 5..=5).map(|digits| digit_char(ean5_digit(&digits)))
+}
+
+/// Validates the check digit of an EAN-5 supplemental barcode against its data.
+pub fn validate_ean5<T: AsRef<str>>(data: T, check_digit: char) -> Result<()> {
+    let digits = digits(data.as_ref(), 5..=5)?;
+    let provided = check_digit
+        .to_digit(10)
+        .ok_or(Error::character(digits.len(), check_digit))?;
+
+    check(ean5_digit(&digits), provided as u8)
+}
+
+/// Computes the check digit of the AIC number of a Code 32 barcode from its first 8 digits.
+pub fn compute_code32<T: AsRef<str>>(data: T) -> Result<char> {
+    digits(data.as_ref(), 8..=8).map(|digits| digit_char(code32_digit(&digits)))
+}
+
+/// Validates a nine digit AIC number of a Code 32 barcode, including its check digit.
+pub fn validate_code32<T: AsRef<str>>(data: T) -> Result<()> {
+    validate_digits(data.as_ref(), 9..=9, code32_digit)
+}
+
+/// Computes the check digit of a PZN-8 or PZN-7 from its first 7 or 6 digits. Numbers with a
+/// check digit of 10 are not issued, and return a character error at their last digit.
+pub fn compute_pzn<T: AsRef<str>>(data: T) -> Result<char> {
+    let digits = digits(data.as_ref(), 6..=7)?;
+
+    issued_pzn_digit(&digits).map(digit_char)
+}
+
+/// Validates a PZN-8 or PZN-7, including its check digit.
+pub fn validate_pzn<T: AsRef<str>>(data: T) -> Result<()> {
+    let digits = digits(data.as_ref(), 7..=8)?;
+    let (&provided, digits) = digits.split_last().expect("Data is not empty");
+
+    check(issued_pzn_digit(digits)?, provided)
+}
+
+/// Computes the check digit of a Deutsche Post Leitcode or Identcode from its first 13 or 11
+/// digits. The digits are weighted alternately by 4 and 9.
+pub fn compute_deutsche_post<T: AsRef<str>>(data: T) -> Result<char> {
+    digits(data.as_ref(), 1..=usize::MAX).map(|digits| digit_char(deutsche_post_digit(&digits)))
+}
+
+/// Validates a Deutsche Post Leitcode or Identcode, including its check digit.
+pub fn validate_deutsche_post<T: AsRef<str>>(data: T) -> Result<()> {
+    validate_digits(data.as_ref(), 2..=usize::MAX, deutsche_post_digit)
+}
+
+/// Computes the modulo-43 check character of Code39 data. This is also the check character of
+/// HIBC data.
+pub fn compute_code39<T: AsRef<str>>(data: T) -> Result<char> {
+    chars(data.as_ref(), CODE39_CHARS, 1..=usize::MAX).map(|chars| code39_char(&chars))
+}
+
+/// Validates Code39 data ending with its modulo-43 check character.
+pub fn validate_code39<T: AsRef<str>>(data: T) -> Result<()> {
+    let chars = chars(data.as_ref(), CODE39_CHARS, 2..=usize::MAX)?;
+    let (&provided, data) = chars.split_last().expect("Data is not empty");
+
+    check_char(CODE39_CHARS, code39_char(data), provided)
+}
+
+/// Computes the C and K check characters of Code93 data, using weighted modulo-47 algorithms.
+pub fn compute_code93<T: AsRef<str>>(data: T) -> Result<(char, char)> {
+    chars(data.as_ref(), CODE93_CHARS, 1..=usize::MAX).map(|chars| code93_chars(&chars))
+}
+
+/// Validates Code93 data ending with its C and K check characters.
+pub fn validate_code93<T: AsRef<str>>(data: T) -> Result<()> {
+    let chars = chars(data.as_ref(), CODE93_CHARS, 3..=usize::MAX)?;
+    let (data, provided) = chars.split_at(chars.len() - 2);
+    let (c, k) = code93_chars(data);
+
+    check_char(CODE93_CHARS, c, provided[0])?;
+    check_char(CODE93_CHARS, k, provided[1])
+}
+
+/// Computes the C check character of Code11 data, and the K check character for data longer
+/// than 10 characters, using weighted modulo-11 algorithms.
+pub fn compute_code11<T: AsRef<str>>(data: T) -> Result<(char, Option<char>)> {
+    chars(data.as_ref(), CODE11_CHARS, 1..=usize::MAX).map(|chars| code11_chars(&chars))
+}
+
+/// Validates Code11 data ending with its C check character, and its K check character for data
+/// longer than 10 characters.
+pub fn validate_code11<T: AsRef<str>>(data: T) -> Result<()> {
+    let chars = chars(data.as_ref(), CODE11_CHARS, 2..=usize::MAX)?;
+
+    // Data of up to 10 characters has a single check character, and longer data has two.
+    let data = match chars.len() {
+        len @ 2..=11 => &chars[..len - 1],
+        12 => return Err(Error::length(13..=usize::MAX, 12)),
+        len => &chars[..len - 2],
+    };
+    let (c, k) = code11_chars(data);
+
+    check_char(CODE11_CHARS, c, chars[data.len()])?;
+    match k {
+        Some(k) => check_char(CODE11_CHARS, k, chars[data.len() + 1]),
+        None => Ok(()),
+    }
+}
+
+/// Computes the modulo-103 check value of Code128 data, given in the syntax of
+/// [`Code128`](crate::sym::code128). The check value is a symbol value (0-102), rather than a
+/// character of the data.
+pub fn compute_code128<T: AsRef<str>>(data: T) -> Result<u8> {
+    Code128::new(data).map(|code128| code128.check_value())
+}
+
+/// Validates the check value of Code128 data against its data.
+pub fn validate_code128<T: AsRef<str>>(data: T, check_value: u8) -> Result<()> {
+    check(compute_code128(data)?, check_value)
+}
+
+// Returns the digits of the data, which must have one of the given lengths.
+fn digits(data: &str, lengths: RangeInclusive<usize>) -> Result<Vec<u8>> {
+    if let Some(e) = Error::find_character(data, 0, |c| c.is_ascii_digit()) {
+        return Err(e);
+    }
+
+    let digits: Vec<u8> = data.bytes().map(|b| b - b'0').collect();
+
+    if !lengths.contains(&digits.len()) {
+        return Err(Error::length(lengths, digits.len()));
+    }
+
+    Ok(digits)
+}
+
+// Returns the characters of the data, which must all be in the given set and have one of the
+// given lengths.
+fn chars(data: &str, set: &str, lengths: RangeInclusive<usize>) -> Result<Vec<char>> {
+    if let Some(e) = Error::find_character(data, 0, |c| set.contains(c)) {
+        return Err(e);
+    }
+
+    let chars: Vec<char> = data.chars().collect();
+
+    if !lengths.contains(&chars.len()) {
+        return Err(Error::length(lengths, chars.len()));
+    }
+
+    Ok(chars)
+}
+
+// Returns the digits of a GTIN, with or without its check digit, which must have one of the
+// lengths of a GTIN-8, GTIN-12, GTIN-13 or GTIN-14.
+fn gtin_digits(data: &str, lengths: &'static [usize]) -> Result<Vec<u8>> {
+    let digits = digits(data, 0..=usize::MAX)?;
+
+    match lengths.contains(&digits.len()) {
+        true => Ok(digits),
+        false => Err(Error::lengths(lengths, digits.len())),
+    }
+}
+
+// Validates digits ending with a check digit computed from those before it.
+fn validate_digits<F: Fn(&[u8]) -> u8>(
+    data: &str,
+    lengths: RangeInclusive<usize>,
+    check_digit: F,
+) -> Result<()> {
+    let digits = digits(data, lengths)?;
+    let (&provided, digits) = digits.split_last().expect("Data is not empty");
+
+    check(check_digit(digits), provided)
+}
+
+fn check(expected: u8, provided: u8) -> Result<()> {
+    match expected == provided {
+        true => Ok(()),
+        false => Err(Error::checksum(expected, provided)),
+    }
+}
+
+// Checks a check character by its value, the position of the character in the given set.
+fn check_char(set: &str, expected: char, provided: char) -> Result<()> {
+    let value = |c| {
+        set.chars()
+            .position(|s| s == c)
+            .expect("Character is in the set") as u8
+    };
+
+    check(value(expected), value(provided))
+}
+
+// Returns the character of a check digit, with 10 as 'X'.
+fn digit_char(d: u8) -> char {
+    match d {
+        10 => 'X',
+        d => (b'0' + d) as char,
+    }
+}
+
+/// Calculates the checksum digit using a modulo-10 weighting algorithm.
+/// EAN-13 (and some others?) barcodes use EVEN-first weighting to maintain backwards
+/// compatibility.
+pub(crate) fn modulo_10_digit(digits: &[u8], even_start: bool) -> u8 {
+    let (weight_even, weight_odd) = if even_start { (1, 3) } else { (3, 1) };
+    let sum: usize = digits
+        .iter()
+        .enumerate()
+        .map(|(i, &d)| d as usize * if i % 2 == 0 { weight_even } else { weight_odd })
+        .sum();
+
+    ((10 - sum % 10) % 10) as u8
+}
+
+/// Calculates the modulo-10 check digit of a GS1 number, with the digits weighted alternately by
+/// 3 and 1 starting from the rightmost digit.
+pub(crate) fn gs1_digit(digits: &[u8]) -> u8 {
+    modulo_10_digit(digits, digits.len().is_multiple_of(2))
+}
+
+/// Calculates the check digit of an ISBN-10 or ISSN, with weights descending to 2, modulo-11.
+pub(crate) fn modulo_11_digit(digits: &[u8]) -> u8 {
+    let weights = (2..=digits.len() + 1).rev();
+    let sum: usize = digits
+        .iter()
+        .zip(weights)
+        .map(|(&d, w)| d as usize * w)
+        .sum();
+
+    ((11 - sum % 11) % 11) as u8
+}
+
+/// Calculates the check digit of an EAN-5 supplemental, weighting the digits alternately by 3
+/// and 9.
+pub(crate) fn ean5_digit(digits: &[u8]) -> u8 {
+    let sum: usize = digits
+        .iter()
+        .enumerate()
+        .map(|(i, &d)| d as usize * if i % 2 == 0 { 3 } else { 9 })
+        .sum();
+
+    (sum % 10) as u8
+}
+
+/// Calculates the check digit of an AIC number, doubling the digits in even positions and
+/// summing the digits of the results.
+pub(crate) fn code32_digit(digits: &[u8]) -> u8 {
+    let sum: u8 = digits
+        .iter()
+        .enumerate()
+        .map(|(i, &d)| match i % 2 {
+            0 => d,
+            _ => d * 2 / 10 + d * 2 % 10,
+        })
+        .sum();
+
+    sum % 10
+}
+
+/// Calculates the check digit of a PZN using a modulo-11 weighting algorithm, with weights
+/// ascending from 1 for PZN-8 and from 2 for PZN-7. A result of 10 is not a valid check digit.
+pub(crate) fn pzn_digit(digits: &[u8]) -> u8 {
+    let first = 8 - digits.len();
+    let sum: usize = digits
+        .iter()
+        .enumerate()
+        .map(|(i, &d)| (i + first) * d as usize)
+        .sum();
+
+    (sum % 11) as u8
+}
+
+// Returns the check digit of a PZN, or a character error at the last digit of a number with a
+// check digit of 10, which is never issued.
+fn issued_pzn_digit(digits: &[u8]) -> Result<u8> {
+    match pzn_digit(digits) {
+        10 => {
+            let last = digits.len() - 1;
+            Err(Error::character(last, (b'0' + digits[last]) as char))
+        }
+        d => Ok(d),
+    }
+}
+
+/// Calculates the check digit of a Deutsche Post barcode using a modulo-10 algorithm, with the
+/// digits weighted alternately by 4 and 9.
+pub(crate) fn deutsche_post_digit(digits: &[u8]) -> u8 {
+    let sum: usize = digits
+        .iter()
+        .enumerate()
+        .map(|(i, &d)| d as usize * if i % 2 == 0 { 4 } else { 9 })
+        .sum();
+
+    ((10 - sum % 10) % 10) as u8
+}
+
+/// Calculates the modulo-43 check character of the given Code39 characters.
+pub(crate) fn code39_char(data: &[char]) -> char {
+    let sum: usize = data.iter().map(|&c| value(CODE39_CHARS, c)).sum();
+
+    char_of(CODE39_CHARS, sum)
+}
+
+/// Calculates the C and K check characters of the given Code93 characters using a weighted
+/// modulo-47 algorithm.
+pub(crate) fn code93_chars(data: &[char]) -> (char, char) {
+    let c = weighted_char(CODE93_CHARS, data, 20);
+    let k = weighted_char(CODE93_CHARS, &[data, &[c]].concat(), 15);
+
+    (c, k)
+}
+
+/// Calculates the C check character of the given Code11 characters using a weighted modulo-11
+/// algorithm, and the K check character if there are more than 10 characters.
+///
+/// Some sources suggest that the K checksum should use modulo-9, but most generators use
+/// modulo-11 for both.
+pub(crate) fn code11_chars(data: &[char]) -> (char, Option<char>) {
+    let c = weighted_char(CODE11_CHARS, data, 10);
+    let k = (data.len() > 10).then(|| weighted_char(CODE11_CHARS, &[data, &[c]].concat(), 9));
+
+    (c, k)
+}
+
+/// Calculates the check value of a Code128 barcode from the values of its symbols, starting with
+/// the start symbol, using a weighted modulo-103 algorithm.
+pub(crate) fn modulo_103_value<I: IntoIterator<Item = usize>>(values: I) -> u8 {
+    let sum: usize = values
+        .into_iter()
+        .enumerate()
+        .map(|(i, v)| v * i.max(1))
+        .sum();
+
+    (sum % 103) as u8
+}
+
+// Returns the check character of the characters, weighted from the rightmost character by 1 up
+// to the maximum weight and then repeating.
+fn weighted_char(set: &str, data: &[char], max_weight: usize) -> char {
+    let sum: usize = data
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &c)| value(set, c) * (i % max_weight + 1))
+        .sum();
+
+    char_of(set, sum)
+}
+
+fn value(set: &str, c: char) -> usize {
+    set.chars().position(|t| t == c).expect("Character in set")
+}
+
+fn char_of(set: &str, sum: usize) -> char {
+    set.as_bytes()[sum % set.len()] as char
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checksum::*;
+    use crate::error::Lengths;
+
+    #[test]
+    fn gtin_checksum() {
+        assert_eq!(compute_gtin("400638133393").unwrap(), '1');
+        assert_eq!(compute_gtin("5512345").unwrap(), '7');
+        assert_eq!(compute_gtin("03600029145").unwrap(), '2');
+        assert_eq!(compute_gtin("1540014128876").unwrap(), '3');
+        assert!(validate_gtin("4006381333931").is_ok());
+        assert!(validate_gtin("55123457").is_ok());
+        assert!(validate_gtin("036000291452").is_ok());
+        assert!(validate_gtin("15400141288763").is_ok());
+    }
+
+    #[test]
+    fn invalid_gtin() {
+        let gtin1 = validate_gtin("4006381333932");
+        let gtin2 = validate_gtin("4006381333");
+        let gtin3 = validate_gtin("40063813339A1");
+        let gtin4 = compute_gtin("40063813339312");
+
+        assert!(matches!(
+            gtin1,
+            Err(Error::Checksum {
+                expected: 1,
+                provided: 2
+            })
+        ));
+        assert!(matches!(
+            gtin2,
+            Err(Error::Length { allowed, actual: 10 }) if allowed == Lengths::Set(&[8, 12, 13, 14])
+        ));
+        assert!(matches!(
+            gtin3,
+            Err(Error::Character {
+                character: 'A',
+                index: 11
+            })
+        ));
+        assert_eq!(gtin4.err().unwrap(), Error::lengths(&[7, 11, 12, 13], 14));
+    }
+
+    #[test]
+    fn sscc_and_gln_checksum() {
+        assert_eq!(compute_sscc("10614141000000001").unwrap(), '9');
+        assert_eq!(compute_gln("061414100001").unwrap(), '2');
+        assert!(validate_sscc("106141410000000019").is_ok());
+        assert!(validate_gln("0614141000012").is_ok());
+        assert_eq!(
+            validate_sscc("10614141000000001").err().unwrap(),
+            Error::length(18..=18, 17)
+        );
+        assert_eq!(
+            validate_gln("0614141000013").err().unwrap(),
+            Error::checksum(2, 3)
+        );
+    }
+
+    #[test]
+    fn modulo_11_checksum() {
+        assert_eq!(compute_modulo_11("030640615").unwrap(), '2');
+        assert_eq!(compute_modulo_11("080442957").unwrap(), 'X');
+        assert!(validate_modulo_11("0306406152").is_ok());
+        assert!(validate_modulo_11("080442957X").is_ok());
+        assert!(validate_modulo_11("1050124X").is_ok());
+        assert_eq!(
+            validate_modulo_11("0306406153").err().unwrap(),
+            Error::checksum(2, 3)
+        );
+        assert_eq!(
+            validate_modulo_11("03064X6152").err().unwrap(),
+            Error::character(5, 'X')
+        );
+    }
+
+    #[test]
+    fn digit_checksums() {
+        assert_eq!(compute_ean5("51299").unwrap(), '8');
+        assert!(validate_ean5("51299", '8').is_ok());
+        assert!(matches!(
+            validate_ean5("51299", '4'),
+            Err(Error::Checksum {
+                expected: 8,
+                provided: 4
+            })
+        ));
+        assert!(matches!(
+            validate_ean5("51299", 'A'),
+            Err(Error::Character {
+                character: 'A',
+                index: 5
+            })
+        ));
+        assert_eq!(compute_code32("01234567").unwrap(), '6');
+        assert!(validate_code32("012345676").is_ok());
+        assert_eq!(compute_pzn("1234562").unwrap(), '6');
+        assert!(validate_pzn("12345626").is_ok());
+        assert_eq!(
+            compute_pzn("1234561").err().unwrap(),
+            Error::character(6, '1')
+        );
+        assert_eq!(
+            validate_pzn("12345610").err().unwrap(),
+            Error::character(6, '1')
+        );
+        assert_eq!(compute_deutsche_post("5631801702001").unwrap(), '4');
+        assert!(validate_deutsche_post("56318017020014").is_ok());
+        assert!(validate_deutsche_post("56318017020015").is_err());
+    }
+
+    #[test]
+    fn code39_checksum() {
+        assert_eq!(compute_code39("CODE39").unwrap(), 'W');
+        assert!(validate_code39("CODE39W").is_ok());
+        assert!(matches!(
+            validate_code39("CODE39X"),
+            Err(Error::Checksum {
+                expected: 32,
+                provided: 33
+            })
+        ));
+        assert_eq!(
+            compute_code39("code39").err().unwrap(),
+            Error::character(0, 'c')
+        );
+    }
+
+    #[test]
+    fn code93_checksum() {
+        assert_eq!(compute_code93("TEST93").unwrap(), ('+', '6'));
+        assert!(validate_code93("TEST93+6").is_ok());
+        assert_eq!(
+            validate_code93("TEST93+7").err().unwrap(),
+            Error::checksum(6, 7)
+        );
+    }
+
+    #[test]
+    fn code11_checksum() {
+        assert_eq!(compute_code11("123-45").unwrap(), ('5', None));
+        assert_eq!(compute_code11("123-4552").unwrap(), ('1', None));
+        assert_eq!(compute_code11("12345678901").unwrap(), ('4', Some('-')));
+        assert!(validate_code11("123-455").is_ok());
+        assert!(validate_code11("123456789014-").is_ok());
+        assert_eq!(
+            validate_code11("123456789014").err().unwrap(),
+            Error::length(13..=usize::MAX, 12)
+        );
+        assert!(matches!(
+            validate_code11("123456789015-"),
+            Err(Error::Checksum {
+                expected: 4,
+                provided: 5
+            })
+        ));
+        assert!(matches!(
+            validate_code11("123456789014-").and(validate_code11("12345678901-4")),
+            Err(Error::Checksum {
+                expected: 4,
+                provided: 10
+            })
+        ));
+    }
+
+    #[test]
+    fn code128_checksum() {
+        assert_eq!(compute_code128("ÀHELLO").unwrap(), 39);
+        assert!(validate_code128("ÀHELLO", 39).is_ok());
+        assert_eq!(
+            validate_code128("ÀHELLO", 40).err().unwrap(),
+            Error::checksum(39, 40)
+        );
+    }
+}
@@ -1,35 +1,388 @@
 //! Custom error types.
 
 use core::fmt;
+use core::ops::RangeInclusive;
 #[cfg(feature = "std")]
 use std::error::Error as StdError;
+#[cfg(feature = "std")]
+use std::sync::Arc;
 
 /// The possible errors that can occur during barcode encoding and generation.
-#[derive(Copy, Clone, Debug, PartialEq)]
+///
+/// Each error carries the details needed to point at the problem in the data, such as the index
+/// of an invalid character. Use [`Error::kind`] to match on the kind of error alone.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
 pub enum Error {
+    /// An invalid character found during encoding.
+    Character {
+        /// The invalid character.
+        character: char,
+        /// The index of the invalid character, counted in characters of the data.
+        index: usize,
+    },
+    /// An invalid data length during encoding.
+    ///
+    /// Lengths are counted in characters of the data. When the data does not fit the capacity
+    /// of a symbol, they are counted in the units of that capacity instead, such as codewords.
+    Length {
+        /// The allowed lengths.
+        allowed: Lengths,
+        /// The actual length.
+        actual: usize,
+    },
+    /// An option, such as a symbol version or a wide-to-narrow ratio, or an encoded number
+    /// outside its allowed range.
+    Option {
+        /// The name of the option.
+        name: &'static str,
+        /// The allowed values.
+        allowed: RangeInclusive<f64>,
+        /// The actual value.
+        actual: f64,
+    },
+    /// An error during barcode generation.
+    Generate(GenerateError),
+    /// Invalid checksum.
+    Checksum {
+        /// The value of the check digit or character computed from the data.
+        expected: u8,
+        /// The value of the check digit or character given in the data.
+        provided: u8,
+    },
+}
+
+/// The allowed lengths of an [`Error::Length`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Lengths {
+    /// Any length within the range.
+    Range(RangeInclusive<usize>),
+    /// One of the listed lengths, such as the 8, 12, 13 or 14 digits of a GTIN.
+    Set(&'static [usize]),
+}
+
+impl Lengths {
+    /// Returns whether the length is allowed.
+    pub fn contains(&self, len: usize) -> bool {
+        match self {
+            Lengths::Range(range) => range.contains(&len),
+            Lengths::Set(set) => set.contains(&len),
+        }
+    }
+}
+
+/// The kinds of [`Error`], without their details.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
     /// An invalid character found during encoding.
     Character,
     /// An invalid data length during encoding.
     Length,
+    /// An option or encoded number outside its allowed range.
+    Option,
     /// An error during barcode generation.
     Generate,
     /// Invalid checksum.
     Checksum,
 }
 
+/// The underlying cause of an error during barcode generation.
+///
+/// The causes are shared, so that errors can be cloned. Errors are equal when their causes are
+/// of the same kind.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum GenerateError {
+    /// The generator cannot produce encoded bytes in the requested format.
+    Format,
+    /// An I/O error while writing the barcode.
+    #[cfg(feature = "std")]
+    Io(Arc<std::io::Error>),
+    /// An error from the image library while writing the barcode.
+    #[cfg(all(feature = "image", feature = "std"))]
+    Image(Arc<image::ImageError>),
+}
+
+impl PartialEq for GenerateError {
+    fn eq(&self, other: &GenerateError) -> bool {
+        match (self, other) {
+            (GenerateError::Format, GenerateError::Format) => true,
+            #[cfg(feature = "std")]
+            (GenerateError::Io(a), GenerateError::Io(b)) => a.kind() == b.kind(),
+            #[cfg(all(feature = "image", feature = "std"))]
+            (GenerateError::Image(_), GenerateError::Image(_)) => true,
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+}
+
 /// Alias-type for Result<T, barcoders::error::Error>.
 pub type Result<T> = ::core::result::Result<T, Error>;
 
+impl Error {
+    /// Returns the kind of the error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::Character { .. } => ErrorKind::Character,
+            Error::Length { .. } => ErrorKind::Length,
+            Error::Option { .. } => ErrorKind::Option,
+            Error::Generate(_) => ErrorKind::Generate,
+            Error::Checksum { .. } => ErrorKind::Checksum,
+        }
+    }
+
+    // Creates an invalid character error for the character at the given index.
+    pub(crate) fn character(index: usize, character: char) -> Error {
+        Error::Character { character, index }
+    }
+
+    // Creates an invalid character error for the first character of the data that fails the
+    // predicate, offset by the given number of characters. Returns None if all are valid.
+    pub(crate) fn find_character<F: Fn(char) -> bool>(
+        data: &str,
+        offset: usize,
+        valid: F,
+    ) -> Option<Error> {
+        data.chars()
+            .enumerate()
+            .find(|&(_, c)| !valid(c))
+            .map(|(i, c)| Error::character(offset + i, c))
+    }
+
+    // Moves the index of an invalid character error by the given number of characters, for data
+    // that was found within a larger string.
+    pub(crate) fn offset(self, by: isize) -> Error {
+        match self {
+            Error::Character { character, index } => Error::Character {
+                character,
+                index: index.saturating_add_signed(by),
+            },
+            e => e,
+        }
+    }
+
+    // Creates an invalid length error.
+    pub(crate) fn length(allowed: RangeInclusive<usize>, actual: usize) -> Error {
+        Error::Length {
+            allowed: Lengths::Range(allowed),
+            actual,
+        }
+    }
+
+    // Creates an invalid length error for data that must have one of the given lengths.
+    pub(crate) fn lengths(allowed: &'static [usize], actual: usize) -> Error {
+        Error::Length {
+            allowed: Lengths::Set(allowed),
+            actual,
+        }
+    }
+
+    // Creates an invalid option error.
+    pub(crate) fn option(
+        name: &'static str,
+        allowed: RangeInclusive<usize>,
+        actual: usize,
+    ) -> Error {
+        Error::Option {
+            name,
+            allowed: *allowed.start() as f64..=*allowed.end() as f64,
+            actual: actual as f64,
+        }
+    }
+
+    // Creates an invalid option error for an option with fractional values.
+    pub(crate) fn option_f64(
+        name: &'static str,
+        allowed: RangeInclusive<f64>,
+        actual: f64,
+    ) -> Error {
+        Error::Option {
+            name,
+            allowed,
+            actual,
+        }
+    }
+
+    // Creates an invalid checksum error.
+    pub(crate) fn checksum(expected: u8, provided: u8) -> Error {
+        Error::Checksum { expected, provided }
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Error::Character => write!(f, "Barcode data is invalid"),
-            Error::Length => write!(f, "Barcode data length is invalid"),
-            Error::Generate => write!(f, "Could not generate barcode data"),
-            Error::Checksum => write!(f, "Invalid checksum"),
+            Error::Character { character, index } => write!(
+                f,
+                "Barcode data is invalid: character {:?} at index {}",
+                character, index
+            ),
+            Error::Length {
+                allowed: Lengths::Range(allowed),
+                actual,
+            } if *allowed.end() == usize::MAX => write!(
+                f,
+                "Barcode data length is invalid: {} is less than {}",
+                actual,
+                allowed.start()
+            ),
+            Error::Length {
+                allowed: Lengths::Range(allowed),
+                actual,
+            } => write!(
+                f,
+                "Barcode data length is invalid: {} is not within {}..={}",
+                actual,
+                allowed.start(),
+                allowed.end()
+            ),
+            Error::Length {
+                allowed: Lengths::Set(allowed),
+                actual,
+            } => write!(
+                f,
+                "Barcode data length is invalid: {} is not one of {:?}",
+                actual, allowed
+            ),
+            Error::Option {
+                name,
+                allowed,
+                actual,
+            } => write!(
+                f,
+                "Barcode option is invalid: {} {} is not within {}..={}",
+                name,
+                actual,
+                allowed.start(),
+                allowed.end()
+            ),
+            Error::Generate(e) => write!(f, "Could not generate barcode data: {}", e),
+            Error::Checksum { expected, provided } => write!(
+                f,
+                "Invalid checksum: expected {}, provided {}",
+                expected, provided
+            ),
+        }
+    }
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenerateError::Format => write!(f, "unsupported format"),
+            #[cfg(feature = "std")]
+            GenerateError::Io(e) => write!(f, "{}", e),
+            #[cfg(all(feature = "image", feature = "std"))]
+            GenerateError::Image(e) => write!(f, "{}", e),
         }
     }
 }
 
+impl From<GenerateError> for Error {
+    fn from(e: GenerateError) -> Error {
+        Error::Generate(e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Error {
+        Error::Generate(GenerateError::Io(Arc::new(e)))
+    }
+}
+
+#[cfg(all(feature = "image", feature = "std"))]
+impl From<image::ImageError> for Error {
+    fn from(e: image::ImageError) -> Error {
+        Error::Generate(GenerateError::Image(Arc::new(e)))
+    }
+}
+
 #[cfg(feature = "std")]
-impl StdError for Error {}
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::Generate(GenerateError::Io(e)) => Some(e.as_ref()),
+            #[cfg(all(feature = "image", feature = "std"))]
+            Error::Generate(GenerateError::Image(e)) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::string::ToString;
+
+    #[test]
+    fn error_kind() {
+        assert_eq!(Error::character(3, 'a').kind(), ErrorKind::Character);
+        assert_eq!(Error::length(1..=5, 6).kind(), ErrorKind::Length);
+        assert_eq!(Error::lengths(&[2, 5], 3).kind(), ErrorKind::Length);
+        assert_eq!(Error::option("mode", 4..=6, 7).kind(), ErrorKind::Option);
+        assert_eq!(Error::checksum(1, 2).kind(), ErrorKind::Checksum);
+        assert_eq!(
+            Error::from(GenerateError::Format).kind(),
+            ErrorKind::Generate
+        );
+    }
+
+    #[test]
+    fn error_eq() {
+        let error = Error::character(3, 'a');
+
+        assert_eq!(error.clone(), error);
+        assert_ne!(error, Error::character(4, 'a'));
+        assert_eq!(
+            Error::from(GenerateError::Format),
+            Error::from(GenerateError::Format).clone()
+        );
+        assert_ne!(Error::from(GenerateError::Format), Error::checksum(1, 2));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn error_eq_io() {
+        use std::io::{Error as IoError, ErrorKind as IoErrorKind};
+
+        let error = Error::from(IoError::new(IoErrorKind::NotFound, "missing"));
+
+        assert_eq!(error.clone(), error);
+        assert_eq!(error, Error::from(IoError::from(IoErrorKind::NotFound)));
+        assert_ne!(
+            error,
+            Error::from(IoError::from(IoErrorKind::PermissionDenied))
+        );
+    }
+
+    #[test]
+    fn error_display() {
+        assert_eq!(
+            Error::character(13, '☺').to_string(),
+            "Barcode data is invalid: character '☺' at index 13"
+        );
+        assert_eq!(
+            Error::length(1..=5, 6).to_string(),
+            "Barcode data length is invalid: 6 is not within 1..=5"
+        );
+        assert_eq!(
+            Error::lengths(&[2, 5], 3).to_string(),
+            "Barcode data length is invalid: 3 is not one of [2, 5]"
+        );
+        assert_eq!(
+            Error::length(2..=usize::MAX, 1).to_string(),
+            "Barcode data length is invalid: 1 is less than 2"
+        );
+        assert_eq!(
+            Error::option("mode", 4..=6, 7).to_string(),
+            "Barcode option is invalid: mode 7 is not within 4..=6"
+        );
+        assert_eq!(
+            Error::checksum(0, 1).to_string(),
+            "Invalid checksum: expected 0, provided 1"
+        );
+    }
+}
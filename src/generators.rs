@@ -27,3 +27,6 @@ pub mod image;
 
 #[cfg(feature = "svg")]
 pub mod svg;
+
+#[cfg(any(feature = "svg", all(feature = "image", feature = "std")))]
+mod hexagon;
@@ -6,11 +6,13 @@
 //! or running the test suite.
 
 use crate::error::Result;
+use crate::sym::Matrix;
 #[cfg(not(feature = "std"))]
-use alloc::string::String;
-use core::iter::repeat;
+use alloc::{string::String, vec::Vec};
+use core::iter::repeat_n;
 
 /// The ASCII barcode generator type.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, Debug)]
 pub struct ASCII {
     /// The height of the barcode (```self.height``` characters high for ASCII).
@@ -41,7 +43,7 @@ impl ASCII {
     fn generate_row(&self, barcode: &[u8]) -> String {
         barcode
             .iter()
-            .flat_map(|&d| repeat(CHARS[d as usize]).take(self.xdim))
+            .flat_map(|&d| repeat_n(CHARS[d as usize], self.xdim))
             .collect()
     }
 
@@ -60,6 +62,17 @@ impl ASCII {
 
         Ok(output)
     }
+
+    /// Generates the given 2D barcode, one line per row of modules (repeated for taller rows).
+    /// The height is ignored. Returns a `Result<String, Error>` indicating success.
+    pub fn generate_matrix(&self, matrix: &Matrix) -> Result<String> {
+        let rows: Vec<String> = matrix
+            .rows()
+            .flat_map(|row| vec![self.generate_row(row); matrix.row_height()])
+            .collect();
+
+        Ok(rows.join("\n"))
+    }
 }
 
 #[cfg(test)]
@@ -73,6 +86,7 @@ mod tests {
     use crate::sym::ean13::*;
     use crate::sym::ean8::*;
     use crate::sym::ean_supp::*;
+    use crate::sym::qr::*;
     use crate::sym::tf::*;
 
     #[test]
@@ -416,4 +430,39 @@ mod tests {
             .trim()
         );
     }
+
+    #[test]
+    fn qr_as_ascii() {
+        let qr = QRCode::new("HELLO WORLD").unwrap();
+        let ascii = ASCII::new();
+        let generated = ascii.generate_matrix(&qr.encode()).unwrap();
+
+        assert_eq!(
+            generated,
+            "
+#######   # # #######
+#     # ###   #     #
+# ### #   # # # ### #
+# ### #   # # # ### #
+# ### # # ### # ### #
+#     #  ###  #     #
+####### # # # #######
+                     
+# # # #  #  #   #  # 
+ ####   #  #    #   #
+   ####### #  # ##   
+#### # ##  ### # ### 
+ #  #### # #  ### # #
+        # #   #   # #
+#######     #  # ##  
+#     #  ##   ## #   
+# ### # ##  # #######
+# ### #   ## # #   # 
+# ### # #### ### #  #
+#     #    ###   # ##
+####### ## # ###    #
+"
+            .trim_matches('\n')
+        );
+    }
 }
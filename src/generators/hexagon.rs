@@ -0,0 +1,66 @@
+//! Geometry shared by the generators for drawing hexagonal matrices.
+//!
+//! Modules are hexagons with a point at the top, sized so that neighbouring modules in a row
+//! touch. Rows are spaced so that the offset rows interlock with those above and below.
+
+use crate::sym::Matrix;
+
+const SQRT_3: f64 = 1.732_050_807_568_877_2;
+
+/// Radii of the bullseye circles in module widths, from the outside in. The circles are
+/// alternately dark and light, leaving three dark rings around a light centre.
+pub const BULLSEYE: [f64; 6] = [4.42, 3.64, 2.85, 2.07, 1.28, 0.5];
+
+/// Returns the width and height of the drawn matrix.
+pub fn size(matrix: &Matrix, xdim: f64) -> (f64, f64) {
+    let rows = matrix.height().max(1) as f64;
+
+    (
+        (matrix.width() as f64 + 0.5) * xdim,
+        ((rows - 1.0) * SQRT_3 / 2.0 + 2.0 / SQRT_3) * xdim,
+    )
+}
+
+/// Returns the centre of the module at the given column and row.
+pub fn centre(matrix: &Matrix, x: usize, y: usize, xdim: f64) -> (f64, f64) {
+    let offset = if matrix.is_offset(y) { 1.0 } else { 0.5 };
+
+    (
+        (x as f64 + offset) * xdim,
+        (y as f64 * SQRT_3 / 2.0 + 1.0 / SQRT_3) * xdim,
+    )
+}
+
+/// Returns the centre of the bullseye, which is the centre of the module to the left of the
+/// middle of the central row.
+pub fn bullseye(matrix: &Matrix, xdim: f64) -> (f64, f64) {
+    centre(
+        matrix,
+        (matrix.width() / 2).saturating_sub(1),
+        matrix.height() / 2,
+        xdim,
+    )
+}
+
+/// Returns the corners of the module centred on the given point, clockwise from the top.
+#[cfg(feature = "svg")]
+pub fn corners((x, y): (f64, f64), xdim: f64) -> [(f64, f64); 6] {
+    let (half, tip) = (xdim / 2.0, xdim / SQRT_3);
+
+    [
+        (x, y - tip),
+        (x + half, y - tip / 2.0),
+        (x + half, y + tip / 2.0),
+        (x, y + tip),
+        (x - half, y + tip / 2.0),
+        (x - half, y - tip / 2.0),
+    ]
+}
+
+/// Returns true if the point lies within the module centred on the given point.
+#[cfg(all(feature = "image", feature = "std"))]
+pub fn contains((x, y): (f64, f64), (px, py): (f64, f64), xdim: f64) -> bool {
+    let (dx, dy) = ((px - x).abs(), (py - y).abs());
+
+    dx <= xdim / 2.0 && dy <= (xdim - dx) / SQRT_3
+}
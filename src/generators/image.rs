@@ -22,7 +22,9 @@
 //! See the README for more examples.
 
 use std::io::Cursor;
-use crate::error::{Error, Result};
+use crate::error::{Error, GenerateError, Result};
+use crate::generators::hexagon;
+use crate::sym::{Elements, Matrix};
 use image::{
     DynamicImage::{self, ImageRgba8},
     ImageBuffer, ImageFormat, Rgba,
@@ -32,6 +34,7 @@ macro_rules! image_variants {
     ( $( #[$attr:meta] $v:ident ),* ) => {
         /// The image generator type.
         #[derive(Copy, Clone, Debug)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         pub enum Image {
         $(
             #[$attr]
@@ -80,6 +83,7 @@ macro_rules! expand_image_variants {
 }
 
 /// Represents a RGBA color for the barcode foreground and background.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, Debug)]
 pub struct Color {
     /// Reg, Green, Blue, Alpha value.
@@ -108,6 +112,7 @@ impl Color {
 }
 
 /// Possible rotation values for images.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, Debug)]
 pub enum Rotation {
     /// No rotation. This is the default.
@@ -155,20 +160,35 @@ impl Image {
     /// Generates the given barcode. Returns a `Result<Vec<u8>, Error>` of the encoded bytes or
     /// an error message.
     pub fn generate<T: AsRef<[u8]>>(&self, barcode: T) -> Result<Vec<u8>> {
+        self.write_image(self.place_pixels(&barcode))
+    }
+
+    /// Generates the given barcode from the widths of its bars and spaces, with each module
+    /// ```xdim``` pixels wide. Each element is snapped to a whole number of pixels, so wide
+    /// elements drawn at a 2.5:1 ratio are five pixels wide when ```xdim``` is 2. Returns a
+    /// `Result<Vec<u8>, Error>` of the encoded bytes or an error message.
+    pub fn generate_elements(&self, elements: &Elements) -> Result<Vec<u8>> {
+        self.write_image(self.place_element_pixels(elements))
+    }
+
+    /// Generates the given 2D barcode with modules ```xdim``` pixels wide and the row height of
+    /// the matrix tall. The height is ignored. Returns a `Result<Vec<u8>, Error>` of the encoded bytes or an error message.
+    pub fn generate_matrix(&self, matrix: &Matrix) -> Result<Vec<u8>> {
+        self.write_image(self.place_matrix_pixels(matrix))
+    }
+
+    fn write_image(&self, img: DynamicImage) -> Result<Vec<u8>> {
         let format = match *self {
             Image::GIF { .. } => ImageFormat::Gif,
             Image::PNG { .. } => ImageFormat::Png,
             Image::WEBP { .. } => ImageFormat::WebP,
-            _ => return Err(Error::Generate),
+            _ => return Err(Error::Generate(GenerateError::Format)),
         };
 
         let mut bytes: Vec<u8> = vec![];
-        let img = self.place_pixels(&barcode);
 
-        match img.write_to(&mut Cursor::new(&mut bytes), format) {
-            Ok(_) => Ok(bytes),
-            _ => Err(Error::Generate),
-        }
+        img.write_to(&mut Cursor::new(&mut bytes), format)?;
+        Ok(bytes)
     }
 
     /// Generates the given barcode to an image::ImageBuffer. Returns a `Result<ImageBuffer<Rgba<u8>, Vec<u8>>, Error>`
@@ -182,6 +202,26 @@ impl Image {
         Ok(img.to_rgba8())
     }
 
+    /// Generates the given barcode from the widths of its bars and spaces to an
+    /// image::ImageBuffer. Returns a `Result<ImageBuffer<Rgba<u8>, Vec<u8>>, Error>` of the
+    /// encoded bytes or an error message.
+    pub fn generate_elements_buffer(
+        self,
+        elements: &Elements,
+    ) -> Result<ImageBuffer<Rgba<u8>, Vec<u8>>> {
+        let img = self.place_element_pixels(elements);
+
+        Ok(img.to_rgba8())
+    }
+
+    /// Generates the given 2D barcode to an image::ImageBuffer. Returns a
+    /// `Result<ImageBuffer<Rgba<u8>, Vec<u8>>, Error>` of the encoded bytes or an error message.
+    pub fn generate_matrix_buffer(self, matrix: &Matrix) -> Result<ImageBuffer<Rgba<u8>, Vec<u8>>> {
+        let img = self.place_matrix_pixels(matrix);
+
+        Ok(img.to_rgba8())
+    }
+
     fn place_pixels<T: AsRef<[u8]>>(&self, barcode: T) -> DynamicImage {
         let barcode = barcode.as_ref();
         let (xdim, height, rotation, bg, fg) = expand_image_variants!(
@@ -202,15 +242,128 @@ impl Image {
             }
         }
 
-        let img = ImageRgba8(buffer);
+        rotate(ImageRgba8(buffer), rotation)
+    }
+
+    fn place_element_pixels(&self, elements: &Elements) -> DynamicImage {
+        let (xdim, height, rotation, bg, fg) = expand_image_variants!(
+            *self,
+            {height: h, xdim: x, rotation: r, background: b, foreground: f} => (x, h, r, b.to_rgba(), f.to_rgba()),
+            GIF, PNG, WEBP, ImageBuffer
+        );
+        // Every element is at least one pixel wide.
+        let widths: Vec<u32> = elements
+            .iter()
+            .map(|e| ((e.width() * xdim as f64).round() as u32).max(1))
+            .collect();
+        let width = widths.iter().sum();
+        let mut buffer = ImageBuffer::from_pixel(width, height, bg);
+        let mut x = 0;
+
+        for (element, w) in elements.iter().zip(widths) {
+            if element.is_bar() {
+                for y in 0..height {
+                    for p in 0..w {
+                        buffer.put_pixel(x + p, y, fg);
+                    }
+                }
+            }
+
+            x += w;
+        }
+
+        rotate(ImageRgba8(buffer), rotation)
+    }
+
+    fn place_matrix_pixels(&self, matrix: &Matrix) -> DynamicImage {
+        let (xdim, rotation, bg, fg) = expand_image_variants!(
+            *self,
+            {xdim: x, rotation: r, background: b, foreground: f, ..} => (x, r, b.to_rgba(), f.to_rgba()),
+            GIF, PNG, WEBP, ImageBuffer
+        );
+        if matrix.is_hexagonal() {
+            return rotate(ImageRgba8(place_hexagons(matrix, xdim, bg, fg)), rotation);
+        }
+
+        let ydim = matrix.row_height() as u32 * xdim;
+        let width = matrix.width() as u32 * xdim;
+        let height = matrix.height() as u32 * ydim;
+        let mut buffer = ImageBuffer::new(width, height);
+
+        for (y, row) in matrix.rows().enumerate() {
+            for (x, &m) in row.iter().enumerate() {
+                let c = if m == 0 { bg } else { fg };
+
+                for py in 0..ydim {
+                    for px in 0..xdim {
+                        buffer.put_pixel(x as u32 * xdim + px, y as u32 * ydim + py, c);
+                    }
+                }
+            }
+        }
+
+        rotate(ImageRgba8(buffer), rotation)
+    }
+}
+
+// Draws each dark module as a hexagon, testing the centre of every pixel it might cover, then
+// draws the bullseye over the light modules in the middle.
+fn place_hexagons(
+    matrix: &Matrix,
+    xdim: u32,
+    bg: Rgba<u8>,
+    fg: Rgba<u8>,
+) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
+    let x = xdim as f64;
+    let (width, height) = hexagon::size(matrix, x);
+    let mut buffer = ImageBuffer::from_pixel(width.ceil() as u32, height.ceil() as u32, bg);
+    let pixels = |(cx, cy): (f64, f64), r: f64| {
+        let xs = (cx - r).max(0.0) as u32..((cx + r).ceil() as u32).min(buffer.width());
+        let ys = (cy - r).max(0.0) as u32..((cy + r).ceil() as u32).min(buffer.height());
+
+        ys.flat_map(move |py| xs.clone().map(move |px| (px, py)))
+    };
+    let mut dark = vec![];
+
+    for (y, row) in matrix.rows().enumerate() {
+        for (col, _) in row.iter().enumerate().filter(|&(_, &m)| m == 1) {
+            let centre = hexagon::centre(matrix, col, y, x);
+
+            dark.extend(pixels(centre, x).filter(|&(px, py)| {
+                hexagon::contains(centre, (px as f64 + 0.5, py as f64 + 0.5), x)
+            }));
+        }
+    }
+
+    let (cx, cy) = hexagon::bullseye(matrix, x);
+
+    for (px, py) in pixels((cx, cy), hexagon::BULLSEYE[0] * x) {
+        let (dx, dy) = (px as f64 + 0.5 - cx, py as f64 + 0.5 - cy);
+        let distance = (dx * dx + dy * dy).sqrt();
+        let circles = hexagon::BULLSEYE
+            .iter()
+            .filter(|&&r| distance <= r * x)
+            .count();
 
-        match rotation {
-            Rotation::Ninety => img.rotate90(),
-            Rotation::OneEighty => img.rotate180(),
-            Rotation::TwoSeventy => img.rotate270(),
-            _ => img,
+        if circles % 2 == 1 {
+            dark.push((px, py));
         }
     }
+
+    for (px, py) in dark {
+        buffer.put_pixel(px, py, fg);
+    }
+
+    buffer
+}
+
+fn rotate(img: DynamicImage, rotation: Rotation) -> DynamicImage {
+    match rotation {
+        Rotation::Ninety => img.rotate90(),
+        Rotation::OneEighty => img.rotate180(),
+        Rotation::TwoSeventy => img.rotate270(),
+        _ => img,
+    }
 }
 
 #[cfg(test)]
@@ -226,6 +379,9 @@ mod tests {
     use crate::sym::ean13::*;
     use crate::sym::ean8::*;
     use crate::sym::ean_supp::*;
+    use crate::sym::maxicode::*;
+    use crate::sym::pdf417::*;
+    use crate::sym::qr::*;
     use crate::sym::tf::*;
     use std::fs::File;
     use std::io::prelude::*;
@@ -236,13 +392,13 @@ mod tests {
     const WRITE_TO_FILE: bool = true;
 
     fn open_file(name: &'static str) -> File {
-        File::create(&Path::new(&format!("{}/{}", TEST_DATA_BASE, name)[..])).unwrap()
+        File::create(Path::new(&format!("{}/{}", TEST_DATA_BASE, name)[..])).unwrap()
     }
 
     fn write_file(bytes: &[u8], file: &'static str) {
         let path = open_file(file);
         let mut writer = BufWriter::new(path);
-        writer.write(bytes).unwrap();
+        writer.write_all(bytes).unwrap();
     }
 
     #[test]
@@ -1030,6 +1186,30 @@ mod tests {
         assert_eq!(generated.width(), 116);
     }
 
+    #[test]
+    fn itf_elements_as_image_buffer() {
+        let itf = TF::interleaved("1234567").unwrap();
+        let img = Image::ImageBuffer {
+            height: 80,
+            xdim: 2,
+            rotation: Rotation::Zero,
+            foreground: Color {
+                rgba: [0, 0, 0, 255],
+            },
+            background: Color {
+                rgba: [255, 255, 255, 255],
+            },
+        };
+        let generated = img
+            .generate_elements_buffer(&itf.elements().with_ratio(2.5).unwrap())
+            .unwrap();
+
+        // The first wide bar follows the start character and is five pixels wide.
+        assert_eq!(generated.width(), 145);
+        assert_eq!(generated.get_pixel(12, 0), &Rgba([0, 0, 0, 255]));
+        assert_eq!(generated.get_pixel(13, 0), &Rgba([255, 255, 255, 255]));
+    }
+
     #[test]
     fn image_buffer_fails_on_generate() {
         let itf = TF::interleaved("98766543561").unwrap();
@@ -1047,4 +1227,108 @@ mod tests {
 
         assert!(img.generate(&itf.encode()[..]).is_err());
     }
+
+    #[test]
+    fn qr_as_png() {
+        let qr = QRCode::new("https://github.com/buntine/barcoders").unwrap();
+        let png = Image::PNG {
+            height: 0,
+            xdim: 4,
+            rotation: Rotation::Zero,
+            foreground: Color {
+                rgba: [0, 0, 0, 255],
+            },
+            background: Color {
+                rgba: [255, 255, 255, 255],
+            },
+        };
+        let generated = png.generate_matrix(&qr.encode().quiet_zone(4)).unwrap();
+
+        if WRITE_TO_FILE {
+            write_file(&generated[..], "qr.png");
+        }
+
+        assert_eq!(generated.len(), 1876);
+    }
+
+    #[test]
+    fn rotated_qr_as_image_buffer() {
+        let qr = QRCode::with_ecc("BARCODERS", ErrorCorrection::H).unwrap();
+        let img = Image::ImageBuffer {
+            height: 0,
+            xdim: 3,
+            rotation: Rotation::Ninety,
+            foreground: Color {
+                rgba: [0, 0, 0, 255],
+            },
+            background: Color {
+                rgba: [255, 255, 255, 255],
+            },
+        };
+        let generated = img.generate_matrix_buffer(&qr.encode()).unwrap();
+
+        assert_eq!(generated.height(), 63);
+        assert_eq!(generated.width(), 63);
+    }
+
+    #[test]
+    fn micropdf417_as_image_buffer() {
+        let micro = MicroPDF417::new("BARCODERS").unwrap().columns(2).unwrap();
+        let img = Image::ImageBuffer {
+            height: 0,
+            xdim: 2,
+            rotation: Rotation::Zero,
+            foreground: Color {
+                rgba: [0, 0, 0, 255],
+            },
+            background: Color {
+                rgba: [255, 255, 255, 255],
+            },
+        };
+        let generated = img.generate_matrix_buffer(&micro.encode()).unwrap();
+
+        assert_eq!(generated.width(), 110);
+        assert_eq!(generated.height(), 32);
+    }
+
+    #[test]
+    fn maxicode_as_image_buffer() {
+        let maxi = MaxiCode::new("BARCODERS").unwrap();
+        let img = Image::ImageBuffer {
+            height: 0,
+            xdim: 4,
+            rotation: Rotation::Zero,
+            foreground: Color {
+                rgba: [0, 0, 0, 255],
+            },
+            background: Color {
+                rgba: [255, 255, 255, 255],
+            },
+        };
+        let generated = img.generate_matrix_buffer(&maxi.encode()).unwrap();
+
+        assert_eq!(generated.height(), 116);
+        assert_eq!(generated.width(), 122);
+        // The centre of the bullseye is light, surrounded by a dark ring.
+        assert_eq!(generated.get_pixel(58, 57).0, [255, 255, 255, 255]);
+        assert_eq!(generated.get_pixel(58, 53).0, [0, 0, 0, 255]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn image_serde() {
+        let json = r#"{"PNG":{"height":80,"xdim":2,"rotation":"Ninety","foreground":{"rgba":[0,0,0,255]},"background":{"rgba":[255,255,255,255]}}}"#;
+        let img: Image = serde_json::from_str(json).unwrap();
+
+        assert!(matches!(
+            img,
+            Image::PNG {
+                height: 80,
+                xdim: 2,
+                rotation: Rotation::Ninety,
+                ..
+            }
+        ));
+        assert_eq!(serde_json::to_string(&img).unwrap(), json);
+    }
 }
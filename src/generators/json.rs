@@ -10,12 +10,24 @@
 //!   "encoding": [1, 0, 0, 1, 1, 0, ...],
 //! }
 //! ```
+//!
+//! 2D barcodes are output with their size in modules and one array per row:
+//! ```javascript
+//! {
+//!   "height": 21,
+//!   "width": 21,
+//!   "xdim": 1,
+//!   "encoding": [[1, 1, 1, 1, ...], [1, 0, 0, 0, ...], ...],
+//! }
+//! ```
 
 use crate::error::Result;
+use crate::sym::Matrix;
 #[cfg(not(feature = "std"))]
-use alloc::{format, string::String};
+use alloc::{format, string::String, vec::Vec};
 
 /// The JSON  barcode generator type.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, Debug)]
 pub struct JSON {
     /// The height of the barcode.
@@ -39,9 +51,8 @@ impl JSON {
         }
     }
 
-    /// Generates the given barcode. Returns a `Result<String, Error>` indicating success.
-    pub fn generate<T: AsRef<[u8]>>(&self, barcode: T) -> Result<String> {
-        let mut bits = barcode.as_ref().iter().fold(String::new(), |acc, &b| {
+    fn bits(row: &[u8]) -> String {
+        let mut bits = row.iter().fold(String::new(), |acc, &b| {
             let n = match b {
                 0 => "0",
                 _ => "1",
@@ -52,7 +63,12 @@ impl JSON {
 
         // Kill trailing comma.
         bits.pop();
+        bits
+    }
 
+    /// Generates the given barcode. Returns a `Result<String, Error>` indicating success.
+    pub fn generate<T: AsRef<[u8]>>(&self, barcode: T) -> Result<String> {
+        let bits = JSON::bits(barcode.as_ref());
         let output = format!(
             "{{\"height\":{},\"xdim\":{},\"encoding\":[{}]}}",
             self.height, self.xdim, bits
@@ -60,6 +76,26 @@ impl JSON {
 
         Ok(output)
     }
+
+    /// Generates the given 2D barcode. The height is taken from the matrix.
+    /// Returns a `Result<String, Error>` indicating success.
+    pub fn generate_matrix(&self, matrix: &Matrix) -> Result<String> {
+        let rows: Vec<String> = matrix
+            .rows()
+            .map(|row| format!("[{}]", JSON::bits(row)))
+            .collect();
+
+        let output = format!(
+            "{{\"height\":{},\"width\":{},\"row_height\":{},\"xdim\":{},\"encoding\":[{}]}}",
+            matrix.height(),
+            matrix.width(),
+            matrix.row_height(),
+            self.xdim,
+            rows.join(",")
+        );
+
+        Ok(output)
+    }
 }
 
 #[cfg(test)]
@@ -73,6 +109,7 @@ mod tests {
     use crate::sym::ean13::*;
     use crate::sym::ean8::*;
     use crate::sym::ean_supp::*;
+    use crate::sym::qr::*;
     use crate::sym::tf::*;
 
     #[test]
@@ -222,4 +259,14 @@ mod tests {
 
         assert_eq!(generated, "{\"height\":10,\"xdim\":1,\"encoding\":[1,0,1,1,0,0,1,0,1,1,0,1,0,1,1,0,1,1,0,1,0,1,1,0,1,1,0,1,0,1,1,0,1,0,1,1,0,1,0,1,1,0,1,0,1,0,1,1,0,1,0,1,0,1,1,0,1,0,1,0,1,0,1,1,0,1,0,1,1,0,1,0,0,1,0,1,0,1,0,1,1,0,1,0,1,1,0,0,1]}".trim());
     }
+
+    #[test]
+    fn qr_as_json() {
+        let qr = QRCode::new("1").unwrap();
+        let json = JSON::new();
+        let generated = json.generate_matrix(&qr.encode()).unwrap();
+
+        assert!(generated.starts_with("{\"height\":21,\"width\":21,\"row_height\":1,\"xdim\":1,\"encoding\":[[1,1,1,1,1,1,1,0,"));
+        assert_eq!(generated.len(), 985);
+    }
 }
@@ -24,10 +24,13 @@
 //! ```
 
 use crate::error::Result;
+use crate::generators::hexagon;
+use crate::sym::{Elements, Matrix};
 #[cfg(not(feature = "std"))]
 use alloc::{
     format,
     string::{String, ToString},
+    vec::Vec,
 };
 
 trait ToHex {
@@ -50,7 +53,15 @@ trait ToHex {
     }
 }
 
+// Formats a length to at most three decimal places, without trailing zeros.
+fn number(n: f64) -> String {
+    let s = format!("{:.3}", n);
+
+    s.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
 /// Represents a RGBA color for the barcode foreground and background.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, Debug)]
 pub struct Color {
     /// Reg, Green, Blue, Alpha value.
@@ -89,6 +100,7 @@ impl ToHex for Color {
 }
 
 /// The SVG barcode generator type.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug)]
 pub struct SVG {
     /// The height of the barcode (```self.height``` pixels high for SVG).
@@ -145,6 +157,40 @@ impl SVG {
     }
 
     fn rect(&self, style: u8, offset: u32, width: u32) -> String {
+        self.rect_at(style, offset, 0, width, self.height)
+    }
+
+    fn rect_at(&self, style: u8, x: u32, y: u32, width: u32, height: u32) -> String {
+        format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" {}/>",
+            x,
+            y,
+            width,
+            height,
+            self.fill(style)
+        )
+    }
+
+    fn polygon(&self, style: u8, points: &[(f64, f64)]) -> String {
+        let points: Vec<String> = points
+            .iter()
+            .map(|(x, y)| format!("{:.2},{:.2}", x, y))
+            .collect();
+
+        format!("<polygon points=\"{}\" {}/>", points.join(" "), self.fill(style))
+    }
+
+    fn circle(&self, style: u8, (x, y): (f64, f64), r: f64) -> String {
+        format!(
+            "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{:.2}\" {}/>",
+            x,
+            y,
+            r,
+            self.fill(style)
+        )
+    }
+
+    fn fill(&self, style: u8) -> String {
         let fill = match style {
             1 => self.foreground,
             _ => self.background,
@@ -155,14 +201,7 @@ impl SVG {
             o => format!(" fill-opacity=\"{}\" ", o),
         };
 
-        format!(
-            "<rect x=\"{}\" y=\"0\" width=\"{}\" height=\"{}\" fill=\"#{}\"{}/>",
-            offset,
-            width,
-            self.height,
-            fill.to_hex(),
-            opacity
-        )
+        format!("fill=\"#{}\"{}", fill.to_hex(), opacity)
     }
 
     /// Generates the given barcode. Returns a `Result<String, Error>` of the SVG data or an
@@ -191,19 +230,149 @@ impl SVG {
             r = rects
         ))
     }
+
+    /// Generates the given barcode from the widths of its bars and spaces, with each module
+    /// ```self.xdim``` pixels wide. Fractional widths, such as those of wide elements drawn at a
+    /// 2.5:1 ratio, are kept. Returns a `Result<String, Error>` of the SVG data or an error
+    /// message.
+    pub fn generate_elements(&self, elements: &Elements) -> Result<String> {
+        let xdim = self.xdim as f64;
+        let mut x = 0.0;
+        let mut rects = String::new();
+
+        for element in elements {
+            let width = element.width() * xdim;
+
+            if element.is_bar() {
+                rects.push_str(&format!(
+                    "<rect x=\"{}\" y=\"0\" width=\"{}\" height=\"{}\" {}/>",
+                    number(x),
+                    number(width),
+                    self.height,
+                    self.fill(1)
+                ));
+            }
+
+            x += width;
+        }
+
+        let xmlns = match &self.xmlns {
+            Some(xmlns) => format!("xmlns=\"{xmlns}\" "),
+            None => "".to_string()
+        };
+
+        Ok(format!(
+            "<svg version=\"1.1\" {x}viewBox=\"0 0 {w} {h}\"><rect x=\"0\" y=\"0\" width=\"{w}\" height=\"{h}\" {f}/>{r}</svg>",
+            x = xmlns,
+            w = number(x),
+            h = self.height,
+            f = self.fill(0),
+            r = rects
+        ))
+    }
+
+    /// Generates the given 2D barcode with modules ```self.xdim``` pixels wide and the row height
+    /// of the matrix tall. Hexagonal matrices are drawn as interlocking hexagons around a
+    /// bullseye. The height is ignored. Returns a `Result<String, Error>` of the SVG data or an
+    /// error message.
+    pub fn generate_matrix(&self, matrix: &Matrix) -> Result<String> {
+        if matrix.is_hexagonal() {
+            return self.generate_hexagonal(matrix);
+        }
+
+        let ydim = matrix.row_height() as u32 * self.xdim;
+        let width = matrix.width() as u32 * self.xdim;
+        let height = matrix.height() as u32 * ydim;
+        let mut rects = String::new();
+
+        // Horizontal runs of dark modules are drawn as a single rect.
+        for (y, row) in matrix.rows().enumerate() {
+            let mut x = 0;
+
+            while x < row.len() {
+                let run = row[x..].iter().take_while(|&&m| m == row[x]).count();
+
+                if row[x] == 1 {
+                    rects.push_str(&self.rect_at(
+                        1,
+                        x as u32 * self.xdim,
+                        y as u32 * ydim,
+                        run as u32 * self.xdim,
+                        ydim,
+                    ));
+                }
+
+                x += run;
+            }
+        }
+
+        let xmlns = match &self.xmlns {
+            Some(xmlns) => format!("xmlns=\"{xmlns}\" "),
+            None => "".to_string()
+        };
+
+        Ok(format!(
+            "<svg version=\"1.1\" {x}viewBox=\"0 0 {w} {h}\">{s}{r}</svg>",
+            x = xmlns,
+            w = width,
+            h = height,
+            s = self.rect_at(0, 0, 0, width, height),
+            r = rects
+        ))
+    }
+
+    fn generate_hexagonal(&self, matrix: &Matrix) -> Result<String> {
+        let xdim = self.xdim as f64;
+        let (width, height) = hexagon::size(matrix, xdim);
+        let mut shapes = String::new();
+
+        for (y, row) in matrix.rows().enumerate() {
+            for (x, _) in row.iter().enumerate().filter(|&(_, &m)| m == 1) {
+                let centre = hexagon::centre(matrix, x, y, xdim);
+
+                shapes.push_str(&self.polygon(1, &hexagon::corners(centre, xdim)));
+            }
+        }
+
+        let centre = hexagon::bullseye(matrix, xdim);
+
+        for (i, r) in hexagon::BULLSEYE.iter().enumerate() {
+            shapes.push_str(&self.circle((i % 2 == 0) as u8, centre, r * xdim));
+        }
+
+        let xmlns = match &self.xmlns {
+            Some(xmlns) => format!("xmlns=\"{xmlns}\" "),
+            None => "".to_string()
+        };
+
+        Ok(format!(
+            "<svg version=\"1.1\" {x}viewBox=\"0 0 {w:.2} {h:.2}\"><rect x=\"0\" y=\"0\" width=\"{w:.2}\" height=\"{h:.2}\" {f}/>{s}</svg>",
+            x = xmlns,
+            w = width,
+            h = height,
+            f = self.fill(0),
+            s = shapes
+        ))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::generators::svg::*;
+    use crate::sym::aztec::*;
     use crate::sym::codabar::*;
+    use crate::sym::codablock::*;
     use crate::sym::code11::*;
     use crate::sym::code128::*;
     use crate::sym::code39::*;
     use crate::sym::code93::*;
+    use crate::sym::datamatrix::*;
     use crate::sym::ean13::*;
     use crate::sym::ean8::*;
     use crate::sym::ean_supp::*;
+    use crate::sym::maxicode::*;
+    use crate::sym::pdf417::*;
+    use crate::sym::qr::*;
     use crate::sym::tf::*;
     #[cfg(feature = "std")]
     use std::fs::File;
@@ -221,7 +390,7 @@ mod tests {
     fn write_file(data: &str, file: &'static str) {
         let path = open_file(file);
         let mut writer = BufWriter::new(path);
-        writer.write(data.as_bytes()).unwrap();
+        writer.write_all(data.as_bytes()).unwrap();
     }
 
     #[cfg(not(feature = "std"))]
@@ -229,7 +398,7 @@ mod tests {
 
     #[cfg(feature = "std")]
     fn open_file(name: &'static str) -> File {
-        File::create(&Path::new(&format!("{}/{}", TEST_DATA_BASE, name)[..])).unwrap()
+        File::create(Path::new(&format!("{}/{}", TEST_DATA_BASE, name)[..])).unwrap()
     }
 
     #[test]
@@ -406,4 +575,115 @@ mod tests {
 
         assert_eq!(generated.len(), 4219);
     }
+
+    #[test]
+    fn qr_as_svg() {
+        let qr = QRCode::new("https://github.com/buntine/barcoders").unwrap();
+        let svg = SVG::new(0).xdim(4);
+        let generated = svg.generate_matrix(&qr.encode().quiet_zone(4)).unwrap();
+
+        if WRITE_TO_FILE {
+            write_file(&generated[..], "qr.svg");
+        }
+
+        assert_eq!(generated.len(), 12819);
+    }
+
+    #[test]
+    fn datamatrix_as_svg() {
+        let dm = DataMatrix::gs1("(01)09501101020917(17)190508(10)ABC123").unwrap();
+        let svg = SVG::new(0).xdim(4);
+        let generated = svg.generate_matrix(&dm.encode().quiet_zone(1)).unwrap();
+
+        if WRITE_TO_FILE {
+            write_file(&generated[..], "datamatrix.svg");
+        }
+
+        assert_eq!(generated.len(), 6531);
+    }
+
+    #[test]
+    fn pdf417_as_svg() {
+        let pdf = PDF417::new("BARCODERS").unwrap();
+        let svg = SVG::new(0).xdim(2);
+        let generated = svg.generate_matrix(&pdf.encode().quiet_zone(2)).unwrap();
+
+        if WRITE_TO_FILE {
+            write_file(&generated[..], "pdf417.svg");
+        }
+
+        assert!(generated.starts_with("<svg version=\"1.1\" viewBox=\"0 0 180 108\">"));
+    }
+
+    #[test]
+    fn aztec_as_svg() {
+        let aztec = Aztec::new("BARCODERS").unwrap();
+        let svg = SVG::new(0).xdim(4);
+        let generated = svg.generate_matrix(&aztec.encode()).unwrap();
+
+        if WRITE_TO_FILE {
+            write_file(&generated[..], "aztec.svg");
+        }
+
+        assert!(generated.starts_with("<svg version=\"1.1\" viewBox=\"0 0 60 60\">"));
+    }
+
+    #[test]
+    fn maxicode_as_svg() {
+        let maxi = MaxiCode::new("BARCODERS").unwrap();
+        let svg = SVG::new(0).xdim(4);
+        let generated = svg.generate_matrix(&maxi.encode()).unwrap();
+
+        if WRITE_TO_FILE {
+            write_file(&generated[..], "maxicode.svg");
+        }
+
+        assert!(generated.starts_with("<svg version=\"1.1\" viewBox=\"0 0 122.00 115.47\">"));
+        assert_eq!(generated.matches("<circle").count(), 6);
+    }
+
+    #[test]
+    fn codablock_as_svg() {
+        let codablock = CodablockF::new("BARCODERS").unwrap();
+        let svg = SVG::new(0).xdim(2);
+        let generated = svg.generate_matrix(&codablock.encode()).unwrap();
+
+        if WRITE_TO_FILE {
+            write_file(&generated[..], "codablock.svg");
+        }
+
+        assert!(generated.starts_with("<svg version=\"1.1\" viewBox=\"0 0 202 68\">"));
+    }
+
+    #[test]
+    fn itf_elements_as_svg() {
+        let itf = TF::interleaved("1234567").unwrap();
+        let svg = SVG::new(80);
+        let generated = svg
+            .generate_elements(&itf.elements().with_ratio(2.5).unwrap())
+            .unwrap();
+
+        if WRITE_TO_FILE {
+            write_file(&generated[..], "itf_elements.svg");
+        }
+
+        assert!(generated.starts_with("<svg version=\"1.1\" viewBox=\"0 0 72.5 80\">"));
+        assert!(generated.contains("<rect x=\"4\" y=\"0\" width=\"2.5\" height=\"80\""));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn svg_serde() {
+        let svg = SVG::new(80).xdim(2).foreground(Color::new([255, 0, 0, 255]));
+        let json = serde_json::to_string(&svg).unwrap();
+        let deserialized: SVG = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(
+            json,
+            "{\"height\":80,\"xdim\":2,\"foreground\":{\"rgba\":[255,0,0,255]},\"background\":{\"rgba\":[255,255,255,255]},\"xmlns\":null}"
+        );
+        assert_eq!(deserialized.height, 80);
+        assert_eq!(deserialized.xdim, 2);
+        assert_eq!(deserialized.foreground.rgba, [255, 0, 0, 255]);
+    }
 }
@@ -10,19 +10,44 @@
 //! ### Symbologies
 //!
 //! * EAN-13
+//!   * ISBN, ISSN and ISMN
 //!   * JAN
 //!   * Bookland
 //! * UPC-A
 //! * EAN-8
+//! * GTIN-8, GTIN-12, GTIN-13 and GTIN-14 (as EAN-8, UPC-A, EAN-13 or ITF-14)
 //! * EAN Supplementals
 //!   * EAN-2
 //!   * EAN-5
+//!   * Joined to EAN-13, UPC-A or EAN-8 barcodes
 //! * Code39
+//!   * Code 32 (Italian Pharmacode)
+//!   * PZN
+//! * HIBC (LIC and PAS, as Code39 or Code128)
 //! * Code128
+//! * Codablock F
 //! * Two-Of-Five
 //!   * Interleaved (ITF)
-//!   * Standard (STF)
+//!   * Standard (STF, also known as Industrial)
+//!   * Matrix
+//!   * IATA
+//!   * Datalogic
+//!   * COOP
+//!   * Deutsche Post Leitcode and Identcode
 //! * Codabar
+//! * Pharmacode
+//!   * One-track
+//!   * Two-track
+//! * GS1 DataBar Expanded
+//!   * Expanded Stacked
+//! * QR Code
+//! * Data Matrix (ECC 200)
+//!   * GS1 Data Matrix
+//! * PDF417
+//!   * MicroPDF417
+//! * GS1 Composite (CC-A, CC-B and CC-C)
+//! * Aztec Code
+//! * MaxiCode
 //! * More coming!
 //!
 //! ### Generators
@@ -38,6 +63,9 @@
 //! * WEBP (feature: `image`)
 //! * Or add your own
 //!
+//! The generators, symbologies and barcode specifications can be serialized and deserialized
+//! with the `serde` feature.
+//!
 //! ## Examples
 //!
 //! See the Github repository.
@@ -58,6 +86,7 @@
 #[cfg(not(feature = "std"))]
 extern crate alloc;
 
+pub mod checksum;
 pub mod error;
 pub mod generators;
 pub mod sym;
@@ -12,24 +12,376 @@
 //! ```
 //! Each encoder accepts a `String` to be encoded. Valid data is barcode-specific and thus
 //! constructors return an Option<T>.
+//!
+//! Linear symbologies encode to a `Vec<u8>` of bars and spaces, while two-dimensional
+//! symbologies (such as QR Code) encode to a `Matrix` of modules. Linear symbologies can also
+//! encode to `Elements`, the widths of their bars and spaces.
 
+pub mod aztec;
 pub mod codabar;
+pub mod codablock;
 pub mod code11;
 pub mod code128;
+pub mod code32;
 pub mod code39;
 pub mod code93;
+pub mod composite;
+pub mod databar;
+pub mod datamatrix;
+pub mod deutsche_post;
 pub mod ean13;
 pub mod ean8;
 pub mod ean_supp;
+pub mod gtin;
+pub mod hibc;
+pub mod isbn;
+pub mod maxicode;
+pub mod metadata;
+pub mod pdf417;
+pub mod pharmacode;
+pub mod pzn;
+pub mod qr;
+pub mod sanitize;
+pub mod spec;
+pub mod tf;
 pub mod upca;
 mod helpers;
-pub mod tf;
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 
 use crate::error::Error;
 use core::iter::Iterator;
 use core::ops::Range;
+use core::slice::Chunks;
+
+/// A two-dimensional grid of modules produced by the 2D symbologies.
+///
+/// Each module is a binary digit (0 = light, 1 = dark). Rows are stored top to bottom.
+/// Stacked symbologies (such as PDF417) use rows that are taller than they are wide, which is
+/// recorded as a row height in multiples of the module width. MaxiCode uses hexagonal modules,
+/// with every other row offset by half a module and a bullseye finder pattern in the centre.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Matrix {
+    width: usize,
+    height: usize,
+    row_height: usize,
+    offset_rows: Option<usize>,
+    modules: Vec<u8>,
+}
+
+impl Matrix {
+    /// Creates a new matrix of the given size with every module light.
+    pub fn new(width: usize, height: usize) -> Matrix {
+        Matrix {
+            width,
+            height,
+            row_height: 1,
+            offset_rows: None,
+            modules: helpers::vec![0; width * height],
+        }
+    }
+
+    /// Returns the width of the matrix in modules.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the matrix in modules.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the height of each row as a multiple of the module width.
+    pub fn row_height(&self) -> usize {
+        self.row_height
+    }
+
+    /// Returns the matrix with each row rendered the given multiple of the module width tall.
+    pub fn with_row_height(mut self, row_height: usize) -> Matrix {
+        self.row_height = row_height.max(1);
+        self
+    }
+
+    /// Returns the matrix as a grid of hexagonal modules, with the odd rows offset to the right
+    /// by half a module. The generators draw a bullseye finder pattern around the central module.
+    pub fn hexagonal(mut self) -> Matrix {
+        self.offset_rows = Some(1);
+        self
+    }
+
+    /// Returns true if the matrix is a grid of hexagonal modules.
+    pub fn is_hexagonal(&self) -> bool {
+        self.offset_rows.is_some()
+    }
+
+    /// Returns true if the given row of a hexagonal matrix is offset by half a module.
+    pub fn is_offset(&self, y: usize) -> bool {
+        self.offset_rows.is_some_and(|parity| y % 2 == parity)
+    }
+
+    /// Returns the module at the given column and row.
+    pub fn get(&self, x: usize, y: usize) -> u8 {
+        self.modules[y * self.width + x]
+    }
+
+    /// Sets the module at the given column and row.
+    pub fn set(&mut self, x: usize, y: usize, module: u8) {
+        self.modules[y * self.width + x] = module;
+    }
+
+    /// Returns an iterator over the rows of the matrix, top to bottom.
+    pub fn rows(&self) -> Chunks<'_, u8> {
+        self.modules.chunks(self.width.max(1))
+    }
+
+    /// Returns a copy of the matrix surrounded by a light quiet zone of the given size.
+    /// The quiet zone rows above and below share the row height of the matrix.
+    pub fn quiet_zone(&self, size: usize) -> Matrix {
+        let mut matrix = Matrix::new(self.width + size * 2, self.height + size * 2)
+            .with_row_height(self.row_height);
+        matrix.offset_rows = self.offset_rows.map(|parity| (parity + size) % 2);
+
+        for (y, row) in self.rows().enumerate() {
+            for (x, &module) in row.iter().enumerate() {
+                matrix.set(x + size, y + size, module);
+            }
+        }
+
+        matrix
+    }
+}
+
+/// A single bar or space of a linear barcode and its width in modules.
+///
+/// Widths are whole numbers of modules, except for the wide elements of binary-width
+/// symbologies (such as Code39 and 2-of-5) drawn at a fractional wide-to-narrow ratio.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Element {
+    bar: bool,
+    width: f64,
+    kind: Kind,
+}
+
+// The role of an element in a binary-width symbology.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Kind {
+    Fixed,
+    Wide,
+    Gap,
+}
+
+impl Element {
+    /// Creates a bar of the given width.
+    pub fn bar(width: f64) -> Element {
+        Element {
+            bar: true,
+            width,
+            kind: Kind::Fixed,
+        }
+    }
+
+    /// Creates a space of the given width.
+    pub fn space(width: f64) -> Element {
+        Element {
+            bar: false,
+            width,
+            kind: Kind::Fixed,
+        }
+    }
+
+    // Marks the element as a wide element of a binary-width symbology.
+    pub(crate) fn wide(self) -> Element {
+        Element {
+            kind: Kind::Wide,
+            ..self
+        }
+    }
+
+    // Marks the element as the space between two characters of a discrete symbology.
+    pub(crate) fn gap(self) -> Element {
+        Element {
+            kind: Kind::Gap,
+            ..self
+        }
+    }
+
+    /// Returns true if the element is a bar, or false if it is a space.
+    pub fn is_bar(&self) -> bool {
+        self.bar
+    }
+
+    /// Returns true if the element is a wide element of a binary-width symbology, whose width
+    /// is set by the wide-to-narrow ratio.
+    pub fn is_wide(&self) -> bool {
+        self.kind == Kind::Wide
+    }
+
+    /// Returns true if the element is the inter-character gap of a discrete symbology (such as
+    /// Code39), whose width can be set independently of the characters.
+    pub fn is_gap(&self) -> bool {
+        self.kind == Kind::Gap
+    }
+
+    /// Returns the width of the element in modules.
+    pub fn width(&self) -> f64 {
+        self.width
+    }
+}
+
+/// The bars and spaces of a linear barcode as a sequence of element widths.
+///
+/// This is the run-length form of the `Vec<u8>` of modules returned by the linear encoders.
+/// Adjacent elements are always of different kinds, so pushing a bar after a bar widens it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Elements(Vec<Element>);
+
+impl Elements {
+    /// Creates an empty sequence of elements.
+    pub fn new() -> Elements {
+        Elements(Vec::new())
+    }
+
+    // Run-length encodes the modules of a character in a binary-width symbology, marking the
+    // elements wider than one module as wide.
+    pub(crate) fn binary(modules: &[u8]) -> Elements {
+        Elements::from(modules)
+            .into_iter()
+            .map(|e| if e.width > 1.0 { e.wide() } else { e })
+            .collect()
+    }
+
+    /// Appends a bar or space of the given width, merging it into the last element if that is
+    /// of the same kind. Elements of zero, negative or non-finite width are ignored.
+    pub fn push(&mut self, element: Element) {
+        match self.0.last_mut() {
+            _ if !(element.width > 0.0 && element.width.is_finite()) => {}
+            Some(last) if last.bar == element.bar => {
+                last.width += element.width;
+                last.kind = Kind::Fixed;
+            }
+            _ => self.0.push(element),
+        }
+    }
+
+    /// Returns the elements with each wide element the given multiple of the narrow width.
+    /// The ratio must be between 2 and 3, the range allowed by the binary-width symbologies.
+    /// Only elements of those symbologies are wide, so others are unchanged.
+    /// Returns Result<Elements, Error> indicating whether the ratio is valid.
+    pub fn with_ratio(mut self, ratio: f64) -> Result<Elements, Error> {
+        if !(2.0..=3.0).contains(&ratio) {
+            return Err(Error::option_f64("ratio", 2.0..=3.0, ratio));
+        }
+
+        for e in self.0.iter_mut().filter(|e| e.is_wide()) {
+            e.width = ratio;
+        }
+
+        Ok(self)
+    }
+
+    /// Returns the elements with each inter-character gap the given width in modules. The gap
+    /// must be between 1 and 5 modules wide. Only the discrete symbologies (Code39, Codabar and
+    /// Code11) have gaps, so others are unchanged.
+    /// Returns Result<Elements, Error> indicating whether the gap is valid.
+    pub fn with_gap(mut self, gap: f64) -> Result<Elements, Error> {
+        if !(1.0..=5.0).contains(&gap) {
+            return Err(Error::option_f64("gap", 1.0..=5.0, gap));
+        }
+
+        for e in self.0.iter_mut().filter(|e| e.is_gap()) {
+            e.width = gap;
+        }
+
+        Ok(self)
+    }
+
+    /// Returns an iterator over the elements, starting with the leftmost.
+    pub fn iter(&self) -> core::slice::Iter<'_, Element> {
+        self.0.iter()
+    }
+
+    /// Returns the number of bars and spaces.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the total width of the elements in modules.
+    pub fn width(&self) -> f64 {
+        self.0.iter().map(Element::width).sum()
+    }
+
+    /// Returns the elements as a Vec<u8> of binary digits, one per module. Fractional widths
+    /// are rounded to the nearest whole module.
+    pub fn modules(&self) -> Vec<u8> {
+        self.0
+            .iter()
+            .flat_map(|e| core::iter::repeat_n(e.bar as u8, (e.width + 0.5) as usize))
+            .collect()
+    }
+}
+
+impl Extend<Element> for Elements {
+    fn extend<I: IntoIterator<Item = Element>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl FromIterator<Element> for Elements {
+    fn from_iter<I: IntoIterator<Item = Element>>(iter: I) -> Elements {
+        let mut elements = Elements::new();
+        elements.extend(iter);
+        elements
+    }
+}
+
+impl IntoIterator for Elements {
+    type Item = Element;
+    type IntoIter = <Vec<Element> as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Elements {
+    type Item = &'a Element;
+    type IntoIter = core::slice::Iter<'a, Element>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl From<&[u8]> for Elements {
+    fn from(modules: &[u8]) -> Elements {
+        modules
+            .iter()
+            .map(|&m| match m {
+                0 => Element::space(1.0),
+                _ => Element::bar(1.0),
+            })
+            .collect()
+    }
+}
+
+impl From<Vec<u8>> for Elements {
+    fn from(modules: Vec<u8>) -> Elements {
+        Elements::from(&modules[..])
+    }
+}
+
+impl From<Elements> for Vec<u8> {
+    fn from(elements: Elements) -> Vec<u8> {
+        elements.modules()
+    }
+}
 
 trait Parse {
     fn valid_chars() -> Vec<char>;
@@ -38,19 +390,110 @@ trait Parse {
     fn parse(data: &str) -> Result<&str, Error> {
         let valid_chars = Self::valid_chars();
         let valid_len = Self::valid_len();
-        let data_len = data.len() as u32;
+        let data_len = data.chars().count();
 
-        if data_len < valid_len.start || data_len > valid_len.end {
-            return Err(Error::Length);
+        if data_len < valid_len.start as usize || data_len > valid_len.end as usize {
+            return Err(Error::length(
+                valid_len.start as usize..=valid_len.end as usize,
+                data_len,
+            ));
         }
 
-        let bad_char = data
-            .chars()
-            .find(|&c| !valid_chars.iter().any(|vc| *vc == c));
-
-        match bad_char {
-            Some(_) => Err(Error::Character),
+        match Error::find_character(data, 0, |c| valid_chars.contains(&c)) {
+            Some(e) => Err(e),
             None => Ok(data),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::sym::*;
+    use helpers::vec;
+
+    #[test]
+    fn elements_from_modules() {
+        let elements = Elements::from(vec![1, 1, 0, 1, 0, 0, 0, 1]);
+
+        assert_eq!(elements.len(), 5);
+        assert_eq!(elements.width(), 8.0);
+        assert_eq!(
+            elements.iter().cloned().collect::<Vec<_>>(),
+            vec![
+                Element::bar(2.0),
+                Element::space(1.0),
+                Element::bar(1.0),
+                Element::space(3.0),
+                Element::bar(1.0)
+            ]
+        );
+        assert_eq!(Vec::from(elements), vec![1, 1, 0, 1, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn elements_push() {
+        let mut elements = Elements::new();
+
+        assert!(elements.is_empty());
+
+        elements.push(Element::bar(1.0));
+        elements.push(Element::bar(2.0));
+        elements.push(Element::space(0.0));
+        elements.push(Element::space(1.0));
+        elements.push(Element::bar(f64::INFINITY));
+        elements.push(Element::bar(f64::NAN));
+
+        assert_eq!(elements.len(), 2);
+        assert!(elements.iter().next().unwrap().is_bar());
+        assert_eq!(elements.iter().next().unwrap().width(), 3.0);
+        assert_eq!(elements.modules(), vec![1, 1, 1, 0]);
+    }
+
+    #[test]
+    fn elements_with_ratio() {
+        let elements = Elements::binary(&[1, 1, 0, 1, 0, 0, 1]);
+        let fixed = Elements::from(vec![1, 1, 0]);
+
+        assert_eq!(elements.iter().filter(|e| e.is_wide()).count(), 2);
+        assert_eq!(elements.clone().with_ratio(2.5).unwrap().width(), 8.0);
+        assert_eq!(
+            elements.clone().with_ratio(2.5).unwrap().modules(),
+            vec![1, 1, 1, 0, 1, 0, 0, 0, 1]
+        );
+        assert_eq!(fixed.with_ratio(3.0).unwrap().width(), 3.0);
+        assert_eq!(
+            elements.clone().with_ratio(4.0).err().unwrap(),
+            Error::option_f64("ratio", 2.0..=3.0, 4.0)
+        );
+        assert!(matches!(
+            elements.clone().with_ratio(f64::NAN),
+            Err(Error::Option { name: "ratio", actual, .. }) if actual.is_nan()
+        ));
+        assert!(elements.with_ratio(f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn elements_with_gap() {
+        let mut elements = Elements::binary(&[1, 0, 1]);
+        elements.push(Element::space(1.0).gap());
+        elements.extend(Elements::binary(&[1, 1, 0, 1]));
+        let widened = elements.clone().with_gap(2.0).unwrap();
+
+        assert_eq!(elements.iter().filter(|e| e.is_gap()).count(), 1);
+        assert_eq!(elements.clone().with_gap(3.0).unwrap().width(), 10.0);
+        assert_eq!(elements.clone().with_gap(1.5).unwrap().width(), 8.5);
+        assert_eq!(
+            elements.clone().with_gap(0.0).err().unwrap(),
+            Error::option_f64("gap", 1.0..=5.0, 0.0)
+        );
+        assert_eq!(
+            elements.clone().with_gap(f64::INFINITY).err().unwrap(),
+            Error::option_f64("gap", 1.0..=5.0, f64::INFINITY)
+        );
+        assert!(elements.with_gap(f64::NAN).is_err());
+        assert_eq!(
+            widened.with_ratio(3.0).unwrap().modules(),
+            vec![1, 0, 1, 0, 0, 1, 1, 1, 0, 1]
+        );
+    }
+}
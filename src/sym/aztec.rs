@@ -0,0 +1,751 @@
+//! Encoder for Aztec Code barcodes.
+//!
+//! Aztec Code is a two-dimensional matrix symbology defined by ISO/IEC 24778. It is built
+//! around a central bullseye finder pattern and needs no quiet zone, which makes it popular on
+//! airline and rail boarding passes. Compact symbols (1-4 layers, 15x15 to 27x27 modules) and
+//! full-range symbols (1-32 layers, 19x19 to 151x151 modules) are supported.
+//!
+//! Data is compacted using the upper, lower, mixed, punctuation and digit modes, with binary
+//! shifts for anything else, choosing the latches and shifts that produce the fewest bits.
+//! Characters in the ISO/IEC 8859-1 (Latin-1) range are supported.
+//!
+//! The error correction percentage defaults to the recommended 23% of the symbol plus three
+//! codewords, and can be set with `Aztec::with_ecc`. The smallest symbol that fits the data is
+//! chosen, preferring compact symbols.
+
+use crate::error::{Error, Result};
+use crate::sym::helpers::{self, vec, Vec};
+use crate::sym::Matrix;
+
+// The recommended error correction percentage.
+const DEFAULT_ECC_PERCENT: u8 = 23;
+
+// The longest run of bytes a single binary shift can hold.
+const MAX_BINARY: usize = 2078;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Mode {
+    Upper,
+    Lower,
+    Mixed,
+    Punct,
+    Digit,
+}
+
+const MODES: [Mode; 5] = [
+    Mode::Upper,
+    Mode::Lower,
+    Mode::Mixed,
+    Mode::Punct,
+    Mode::Digit,
+];
+
+// Latch sequences between modes as (code, width) pairs, indexed by [from][to].
+const LATCHES: [[&[(u32, usize)]; 5]; 5] = [
+    [&[], &[(28, 5)], &[(29, 5)], &[(29, 5), (30, 5)], &[(30, 5)]],
+    [
+        &[(30, 5), (14, 4)],
+        &[],
+        &[(29, 5)],
+        &[(29, 5), (30, 5)],
+        &[(30, 5)],
+    ],
+    [&[(29, 5)], &[(28, 5)], &[], &[(30, 5)], &[(29, 5), (30, 5)]],
+    [
+        &[(31, 5)],
+        &[(31, 5), (28, 5)],
+        &[(31, 5), (29, 5)],
+        &[],
+        &[(31, 5), (30, 5)],
+    ],
+    [
+        &[(14, 4)],
+        &[(14, 4), (28, 5)],
+        &[(14, 4), (29, 5)],
+        &[(14, 4), (29, 5), (30, 5)],
+        &[],
+    ],
+];
+
+// Punctuation mode characters with values 6 to 30.
+const PUNCT: &[u8; 25] = b"!\"#$%&'()*+,-./:;<=>?[]{}";
+
+// Mixed mode characters with values 20 to 27.
+const MIXED: &[u8; 8] = b"@\\^_`|~\x7F";
+
+impl Mode {
+    fn width(self) -> usize {
+        match self {
+            Mode::Digit => 4,
+            _ => 5,
+        }
+    }
+
+    // Returns the value of a character in this mode, if it has one.
+    fn value(self, c: u8) -> Option<u32> {
+        let value = match (self, c) {
+            (Mode::Punct, b' ') => return None,
+            (_, b' ') => 1,
+            (Mode::Upper, b'A'..=b'Z') => c - b'A' + 2,
+            (Mode::Lower, b'a'..=b'z') => c - b'a' + 2,
+            (Mode::Mixed, 1..=13) => c + 1,
+            (Mode::Mixed, 27..=31) => c - 12,
+            (Mode::Mixed, _) => MIXED.iter().position(|&m| m == c)? as u8 + 20,
+            (Mode::Punct, b'\r') => 1,
+            (Mode::Punct, _) => PUNCT.iter().position(|&p| p == c)? as u8 + 6,
+            (Mode::Digit, b'0'..=b'9') => c - b'0' + 2,
+            (Mode::Digit, b',') => 12,
+            (Mode::Digit, b'.') => 13,
+            _ => return None,
+        };
+
+        Some(value as u32)
+    }
+}
+
+// Returns the punctuation mode value of a two character sequence, if it has one.
+fn pair_value(pair: &[u8]) -> Option<u32> {
+    match pair {
+        b"\r\n" => Some(2),
+        b". " => Some(3),
+        b", " => Some(4),
+        b": " => Some(5),
+        _ => None,
+    }
+}
+
+// A way of consuming input without changing mode, identified by its starting position.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Edge {
+    Start,
+    Char(usize),
+    Pair(usize),
+    PunctShift(usize, usize),
+    UpperShift(usize),
+    Binary(usize, usize),
+}
+
+fn push_bits(bits: &mut Vec<u8>, value: u32, len: usize) {
+    bits.extend((0..len).rev().map(|i| ((value >> i) & 1) as u8));
+}
+
+fn relax(slot: &mut Option<(usize, Edge)>, cost: usize, edge: Edge) {
+    if slot.is_none_or(|(best, _)| cost < best) {
+        *slot = Some((cost, edge));
+    }
+}
+
+// Encodes the data into the shortest sequence of bits, found by searching every combination
+// of latches, shifts and binary runs.
+fn encode_data(data: &[u8]) -> Vec<u8> {
+    let n = data.len();
+    // The cheapest arrival at each position in each mode, and the mode latched from to be in
+    // each mode once there.
+    let mut arrive: Vec<[Option<(usize, Edge)>; 5]> = vec![[None; 5]; n + 1];
+    let mut latched: Vec<[Option<(usize, Mode)>; 5]> = vec![[None; 5]; n + 1];
+
+    arrive[0][Mode::Upper as usize] = Some((0, Edge::Start));
+
+    for i in 0..=n {
+        for to in MODES {
+            latched[i][to as usize] = MODES
+                .iter()
+                .filter_map(|&from| {
+                    let (cost, _) = arrive[i][from as usize]?;
+                    let latch: usize = LATCHES[from as usize][to as usize]
+                        .iter()
+                        .map(|&(_, w)| w)
+                        .sum();
+
+                    Some((cost + latch, from))
+                })
+                .min_by_key(|&(cost, _)| cost);
+        }
+
+        if i == n {
+            break;
+        }
+
+        for mode in MODES {
+            let Some((cost, _)) = latched[i][mode as usize] else {
+                continue;
+            };
+            let m = mode as usize;
+            let width = mode.width();
+            let pair = data.get(i..i + 2).and_then(pair_value);
+
+            if mode.value(data[i]).is_some() {
+                relax(&mut arrive[i + 1][m], cost + width, Edge::Char(i));
+            }
+
+            if mode == Mode::Punct {
+                if pair.is_some() {
+                    relax(&mut arrive[i + 2][m], cost + 5, Edge::Pair(i));
+                }
+
+                continue;
+            }
+
+            if Mode::Punct.value(data[i]).is_some() {
+                relax(
+                    &mut arrive[i + 1][m],
+                    cost + width + 5,
+                    Edge::PunctShift(i, 1),
+                );
+            }
+
+            if pair.is_some() {
+                relax(
+                    &mut arrive[i + 2][m],
+                    cost + width + 5,
+                    Edge::PunctShift(i, 2),
+                );
+            }
+
+            if mode == Mode::Digit {
+                if data[i].is_ascii_uppercase() {
+                    relax(&mut arrive[i + 1][m], cost + 9, Edge::UpperShift(i));
+                }
+
+                continue;
+            }
+
+            if mode == Mode::Lower && data[i].is_ascii_uppercase() {
+                relax(&mut arrive[i + 1][m], cost + 10, Edge::UpperShift(i));
+            }
+
+            for len in 1..=MAX_BINARY.min(n - i) {
+                let header = if len <= 31 { 10 } else { 21 };
+                relax(
+                    &mut arrive[i + len][m],
+                    cost + header + 8 * len,
+                    Edge::Binary(i, len),
+                );
+            }
+        }
+    }
+
+    // Walk back from the cheapest finish, collecting the edges and latches taken.
+    let mut mode = MODES
+        .into_iter()
+        .filter(|&m| arrive[n][m as usize].is_some())
+        .min_by_key(|&m| arrive[n][m as usize].map(|(cost, _)| cost))
+        .unwrap_or(Mode::Upper);
+    let mut steps = vec![];
+    let mut position = n;
+
+    while let Some((_, edge)) = arrive[position][mode as usize] {
+        position = match edge {
+            Edge::Start => break,
+            Edge::Char(i)
+            | Edge::Pair(i)
+            | Edge::PunctShift(i, _)
+            | Edge::UpperShift(i)
+            | Edge::Binary(i, _) => i,
+        };
+        steps.push((mode, None, edge));
+
+        if let Some((_, from)) = latched[position][mode as usize] {
+            if from != mode {
+                steps.push((mode, Some(from), edge));
+            }
+
+            mode = from;
+        }
+    }
+
+    let mut bits = vec![];
+
+    for (mode, latch, edge) in steps.into_iter().rev() {
+        if let Some(from) = latch {
+            for &(code, width) in LATCHES[from as usize][mode as usize] {
+                push_bits(&mut bits, code, width);
+            }
+
+            continue;
+        }
+
+        let width = mode.width();
+
+        match edge {
+            Edge::Char(i) => push_bits(&mut bits, mode.value(data[i]).unwrap_or(0), width),
+            Edge::Pair(i) => push_bits(&mut bits, pair_value(&data[i..i + 2]).unwrap_or(0), 5),
+            Edge::PunctShift(i, len) => {
+                let value = match len {
+                    2 => pair_value(&data[i..i + 2]),
+                    _ => Mode::Punct.value(data[i]),
+                };
+
+                push_bits(&mut bits, 0, width);
+                push_bits(&mut bits, value.unwrap_or(0), 5);
+            }
+            Edge::UpperShift(i) => {
+                push_bits(&mut bits, if mode == Mode::Digit { 15 } else { 28 }, width);
+                push_bits(&mut bits, Mode::Upper.value(data[i]).unwrap_or(0), 5);
+            }
+            Edge::Binary(i, len) => {
+                push_bits(&mut bits, 31, 5);
+
+                if len <= 31 {
+                    push_bits(&mut bits, len as u32, 5);
+                } else {
+                    push_bits(&mut bits, 0, 5);
+                    push_bits(&mut bits, (len - 31) as u32, 11);
+                }
+
+                for &b in data[i..i + len].iter() {
+                    push_bits(&mut bits, b as u32, 8);
+                }
+            }
+            Edge::Start => {}
+        }
+    }
+
+    bits
+}
+
+// Splits the bits into codewords, stuffing an extra bit into any codeword that would
+// otherwise be all zeros or all ones. The final codeword is padded with ones.
+fn stuff_bits(bits: &[u8], word: usize) -> Vec<u16> {
+    let mask = (1 << word) - 2;
+    let mut words = vec![];
+    let mut i = 0;
+
+    while i < bits.len() {
+        let value = (0..word).fold(0u16, |acc, j| {
+            (acc << 1) | *bits.get(i + j).unwrap_or(&1) as u16
+        });
+
+        match value & mask {
+            top if top == mask => {
+                words.push(mask);
+                i += word - 1;
+            }
+            0 => {
+                words.push(1);
+                i += word - 1;
+            }
+            _ => {
+                words.push(value);
+                i += word;
+            }
+        }
+    }
+
+    words
+}
+
+// Returns the codeword size in bits and the primitive polynomial of its Galois field.
+fn word_size(layers: usize) -> (usize, u32) {
+    match layers {
+        1..=2 => (6, 0x43),
+        3..=8 => (8, 0x12D),
+        9..=22 => (10, 0x409),
+        _ => (12, 0x1069),
+    }
+}
+
+// Returns the number of bits held by the data layers of a symbol.
+fn layer_bits(layers: usize, compact: bool) -> usize {
+    (if compact { 88 } else { 112 } + 16 * layers) * layers
+}
+
+/// The Aztec Code barcode type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Aztec {
+    bits: Vec<u8>,
+    ecc_percent: u8,
+    compact: bool,
+    layers: usize,
+}
+
+impl Aztec {
+    /// Creates a new barcode with the recommended 23% error correction.
+    /// Returns Result<Aztec, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<Aztec> {
+        Aztec::with_ecc(data, DEFAULT_ECC_PERCENT)
+    }
+
+    /// Creates a new barcode with the given error correction percentage (5-95). The error
+    /// correction codewords make up at least this much of the symbol, plus three.
+    /// Returns Result<Aztec, Error> indicating parse success.
+    pub fn with_ecc<T: AsRef<str>>(data: T, percent: u8) -> Result<Aztec> {
+        let data = data
+            .as_ref()
+            .chars()
+            .enumerate()
+            .map(|(i, c)| match c as u32 {
+                0..=255 => Ok(c as u8),
+                _ => Err(Error::character(i, c)),
+            })
+            .collect::<Result<Vec<u8>>>()?;
+
+        if data.is_empty() {
+            return Err(Error::length(1..=usize::MAX, 0));
+        }
+
+        if !(5..=95).contains(&percent) {
+            return Err(Error::option("error correction", 5..=95, percent as usize));
+        }
+
+        Aztec::build(encode_data(&data), percent, true, 1)
+    }
+
+    /// Switches the barcode to the smallest full-range symbol that fits the data, with at least
+    /// as many layers as it has now. Returns Result<Aztec, Error> indicating success.
+    pub fn full_range(self) -> Result<Aztec> {
+        Aztec::build(self.bits, self.ecc_percent, false, self.layers)
+    }
+
+    /// Sets the minimum number of layers (1-32). Compact symbols are only used for up to four
+    /// layers. Returns Result<Aztec, Error> indicating success.
+    pub fn min_layers(self, layers: usize) -> Result<Aztec> {
+        if !(1..=32).contains(&layers) {
+            return Err(Error::option("layers", 1..=32, layers));
+        }
+
+        Aztec::build(self.bits, self.ecc_percent, self.compact, layers)
+    }
+
+    /// Returns the number of data layers around the bullseye.
+    pub fn layers(&self) -> usize {
+        self.layers
+    }
+
+    /// Returns true if the symbol is compact rather than full-range.
+    pub fn is_compact(&self) -> bool {
+        self.compact
+    }
+
+    fn build(bits: Vec<u8>, ecc_percent: u8, compact: bool, min_layers: usize) -> Result<Aztec> {
+        let compacts = (1..=4).filter(|_| compact).map(|l| (true, l));
+        let fulls = (1..=32).map(|l| (false, l));
+        // The data codewords that the largest symbol holds, and that the data needs in it.
+        let mut capacity = (0, 0);
+
+        for (compact, layers) in compacts.chain(fulls).filter(|&(_, l)| l >= min_layers) {
+            let (word, _) = word_size(layers);
+            let total = layer_bits(layers, compact) / word;
+            let data = stuff_bits(&bits, word).len();
+            let ecc = (total * ecc_percent as usize).div_ceil(100) + 3;
+
+            if data + ecc <= total && data <= if compact { 64 } else { 2048 } {
+                return Ok(Aztec {
+                    bits,
+                    ecc_percent,
+                    compact,
+                    layers,
+                });
+            }
+
+            capacity = ((total - ecc).min(2048), data);
+        }
+
+        Err(Error::length(0..=capacity.0, capacity.1))
+    }
+
+    // Returns the mode message bits, giving the number of layers and data codewords.
+    fn mode_message(&self, data_words: usize) -> Vec<u8> {
+        let mut bits = vec![];
+
+        if self.compact {
+            push_bits(&mut bits, (self.layers - 1) as u32, 2);
+            push_bits(&mut bits, (data_words - 1) as u32, 6);
+        } else {
+            push_bits(&mut bits, (self.layers - 1) as u32, 5);
+            push_bits(&mut bits, (data_words - 1) as u32, 11);
+        }
+
+        let words: Vec<u16> = bits
+            .chunks(4)
+            .map(|c| c.iter().fold(0, |a, &b| (a << 1) | b as u16))
+            .collect();
+        let ecc = helpers::reed_solomon(&words, if self.compact { 5 } else { 6 }, 0x13, 1);
+        let mut message = vec![];
+
+        for w in words.iter().chain(ecc.iter()) {
+            push_bits(&mut message, *w as u32, 4);
+        }
+
+        message
+    }
+
+    // Returns the data layer bits: the data and error correction codewords, with any
+    // remainder as leading zeros.
+    fn layer_message(&self) -> (Vec<u8>, usize) {
+        let (word, poly) = word_size(self.layers);
+        let total_bits = layer_bits(self.layers, self.compact);
+        let data = stuff_bits(&self.bits, word);
+        let ecc = helpers::reed_solomon(&data, total_bits / word - data.len(), poly, 1);
+        let mut message = vec![0; total_bits % word];
+
+        for w in data.iter().chain(ecc.iter()) {
+            push_bits(&mut message, *w as u32, word);
+        }
+
+        (message, data.len())
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Matrix of modules.
+    pub fn encode(&self) -> Matrix {
+        let (message, data_words) = self.layer_message();
+        let mode_message = self.mode_message(data_words);
+        let base = if self.compact { 11 } else { 14 } + self.layers * 4;
+
+        // Full-range symbols have a reference grid line every 16 modules out from the centre,
+        // so the base coordinates are mapped around it.
+        let size = match self.compact {
+            true => base,
+            false => base + 1 + 2 * ((base / 2 - 1) / 15),
+        };
+        let centre = size / 2;
+        let map: Vec<usize> = (0..base)
+            .map(|i| match (self.compact, i < base / 2) {
+                (true, _) => i,
+                (false, true) => {
+                    let d = base / 2 - 1 - i;
+                    centre - d - d / 15 - 1
+                }
+                (false, false) => {
+                    let d = i - base / 2;
+                    centre + d + d / 15 + 1
+                }
+            })
+            .collect();
+
+        let mut matrix = Matrix::new(size, size);
+        let last = base - 1;
+        let mut row_offset = 0;
+
+        // Layers spiral inwards from the outside, each side holding a two module wide strip.
+        for layer in 0..self.layers {
+            let row_size = (self.layers - layer) * 4 + if self.compact { 9 } else { 12 };
+            let i = layer * 2;
+
+            for j in 0..row_size {
+                for k in 0..2 {
+                    let bit = |side: usize| message[row_offset + row_size * 2 * side + j * 2 + k];
+
+                    matrix.set(map[i + k], map[i + j], bit(0));
+                    matrix.set(map[i + j], map[last - i - k], bit(1));
+                    matrix.set(map[last - i - k], map[last - i - j], bit(2));
+                    matrix.set(map[last - i - j], map[i + k], bit(3));
+                }
+            }
+
+            row_offset += row_size * 8;
+        }
+
+        // The mode message surrounds the bullseye, skipping the reference grid line on
+        // full-range symbols.
+        let (count, reach) = if self.compact { (7, 5) } else { (10, 7) };
+
+        for i in 0..count {
+            let offset = match self.compact {
+                true => centre - 3 + i,
+                false => centre - 5 + i + i / 5,
+            };
+
+            matrix.set(offset, centre - reach, mode_message[i]);
+            matrix.set(centre + reach, offset, mode_message[i + count]);
+            matrix.set(offset, centre + reach, mode_message[count * 3 - 1 - i]);
+            matrix.set(centre - reach, offset, mode_message[count * 4 - 1 - i]);
+        }
+
+        // The reference grid alternates dark and light modules along every 16th row and
+        // column from the centre.
+        if !self.compact {
+            for j in (0..base / 2 - 1).step_by(15).map(|i| i / 15 * 16) {
+                for k in ((centre & 1)..size).step_by(2) {
+                    matrix.set(centre - j, k, 1);
+                    matrix.set(centre + j, k, 1);
+                    matrix.set(k, centre - j, 1);
+                    matrix.set(k, centre + j, 1);
+                }
+            }
+        }
+
+        // The bullseye is a set of concentric squares, with orientation marks at its corners.
+        let rings = if self.compact { 5 } else { 7 };
+
+        for ring in 0..rings {
+            for j in centre - ring..=centre + ring {
+                let module = (ring % 2 == 0) as u8;
+
+                matrix.set(j, centre - ring, module);
+                matrix.set(j, centre + ring, module);
+                matrix.set(centre - ring, j, module);
+                matrix.set(centre + ring, j, module);
+            }
+        }
+
+        for (x, y) in [
+            (centre - rings, centre - rings),
+            (centre - rings + 1, centre - rings),
+            (centre - rings, centre - rings + 1),
+            (centre + rings, centre - rings),
+            (centre + rings, centre - rings + 1),
+            (centre + rings, centre + rings - 1),
+        ] {
+            matrix.set(x, y, 1);
+        }
+
+        matrix
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sym::aztec::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::string::String;
+
+    fn collapse_vec(v: &[u8]) -> String {
+        let chars = v.iter().map(|d| char::from_digit(*d as u32, 10).unwrap());
+        chars.collect()
+    }
+
+    #[test]
+    fn new_aztec() {
+        let aztec1 = Aztec::new("Hello, World!");
+        let aztec2 = Aztec::with_ecc("1234567890", 50);
+        let aztec3 = Aztec::new("Grüße");
+
+        assert!(aztec1.is_ok());
+        assert!(aztec2.is_ok());
+        assert!(aztec3.is_ok());
+    }
+
+    #[test]
+    fn invalid_data_aztec() {
+        let aztec = Aztec::new("€100");
+
+        assert_eq!(aztec.err().unwrap(), Error::character(0, '€'));
+    }
+
+    #[test]
+    fn invalid_len_aztec() {
+        let aztec1 = Aztec::new("");
+        let aztec2 = Aztec::with_ecc("ABC", 4);
+        let aztec3 = Aztec::with_ecc("ABC", 96);
+        let aztec4 = Aztec::new("ABC").unwrap().min_layers(33);
+        let aztec5 = Aztec::new("é".repeat(2079));
+
+        assert_eq!(aztec1.err().unwrap(), Error::length(1..=usize::MAX, 0));
+        assert_eq!(
+            aztec2.err().unwrap(),
+            Error::option("error correction", 5..=95, 4)
+        );
+        assert_eq!(
+            aztec3.err().unwrap(),
+            Error::option("error correction", 5..=95, 96)
+        );
+        assert_eq!(aztec4.err().unwrap(), Error::option("layers", 1..=32, 33));
+        assert_eq!(aztec5.err().unwrap(), Error::length(0..=1278, 1389));
+    }
+
+    #[test]
+    fn aztec_data_bits() {
+        let aztec1 = Aztec::new("AB12").unwrap();
+        let aztec2 = Aztec::new("a.").unwrap();
+
+        assert_eq!(collapse_vec(&aztec1.bits), "00010000111111000110100");
+        assert_eq!(collapse_vec(&aztec2.bits), "1110000010111101101");
+    }
+
+    #[test]
+    fn aztec_bit_stuffing() {
+        let words = stuff_bits(&[1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0], 6);
+
+        assert_eq!(words, vec![0b111110, 0b100000, 0b011111]);
+    }
+
+    #[test]
+    fn aztec_layers() {
+        let small = Aztec::new("A").unwrap();
+        let full = Aztec::new("A").unwrap().full_range().unwrap();
+        let layered = Aztec::new("A").unwrap().min_layers(5).unwrap();
+        let switched = Aztec::new("A").unwrap().min_layers(3).unwrap();
+        let switched = switched.full_range().unwrap();
+        let long = Aztec::new("A".repeat(1000)).unwrap();
+
+        assert_eq!((small.layers(), small.is_compact()), (1, true));
+        assert_eq!((full.layers(), full.is_compact()), (1, false));
+        assert_eq!((layered.layers(), layered.is_compact()), (5, false));
+        assert_eq!((switched.layers(), switched.is_compact()), (3, false));
+        assert!(!long.is_compact());
+    }
+
+    #[test]
+    fn aztec_encode() {
+        let compact = Aztec::new("A").unwrap().encode();
+        let full = Aztec::new("A").unwrap().full_range().unwrap().encode();
+        let large = Aztec::new("A").unwrap().min_layers(32).unwrap().encode();
+
+        assert_eq!((compact.width(), compact.height()), (15, 15));
+        assert_eq!((full.width(), full.height()), (19, 19));
+        assert_eq!((large.width(), large.height()), (151, 151));
+        assert_eq!(
+            collapse_vec(&compact.rows().nth(7).unwrap()[3..12]),
+            "101010101"
+        );
+        assert_eq!(
+            collapse_vec(&full.rows().nth(9).unwrap()[3..16]),
+            "1010101010101"
+        );
+    }
+
+    // Reference symbols generated by the ZXing Aztec encoder.
+    #[test]
+    fn aztec_reference_symbols() {
+        let compact = Aztec::new("Hello, World!").unwrap().encode();
+        let full = Aztec::new("CODE 2D!").unwrap().full_range().unwrap();
+        let full = full.encode();
+        let compact_rows = [
+            "0110001111101101001",
+            "0000111011101000001",
+            "1111100100110111010",
+            "1110111100010000010",
+            "0001110100110010001",
+            "0001111111111110001",
+            "1110110000000111000",
+            "0001110111110100000",
+            "1110110100010110000",
+            "0110010101010110000",
+            "0100010100010110010",
+            "1001110111110100011",
+            "1111010000000111101",
+            "0000011111111110010",
+            "0010001100100000110",
+            "0010000101110000100",
+            "0010111111111011000",
+            "1001101010001011001",
+            "0010101000010110001",
+        ];
+        let full_rows = [
+            "0001011100100100001",
+            "0001100111000101001",
+            "1011000000000000101",
+            "0111111111111111101",
+            "0001000000000001011",
+            "0011011111111101001",
+            "0101010000000101100",
+            "0111010111110101001",
+            "0111010100010101011",
+            "0101010101010101010",
+            "0011010100010101010",
+            "1111010111110101100",
+            "0111010000000101111",
+            "1101011111111101010",
+            "1011000000000001100",
+            "0001111111111111110",
+            "0100011100100100010",
+            "1011000001101111100",
+            "0011011000011000111",
+        ];
+
+        assert!(compact.rows().map(collapse_vec).eq(compact_rows));
+        assert!(full.rows().map(collapse_vec).eq(full_rows));
+    }
+}
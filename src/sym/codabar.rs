@@ -9,7 +9,7 @@
 
 use super::helpers::{vec, Vec};
 use crate::error::Result;
-use crate::sym::Parse;
+use crate::sym::{Element, Elements, Parse};
 use core::ops::Range;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -106,13 +106,19 @@ impl Codabar {
     /// Encodes the barcode.
     /// Returns a Vec<u8> of binary digits.
     pub fn encode(&self) -> Vec<u8> {
-        let mut enc: Vec<u8> = vec![];
+        self.elements().modules()
+    }
+
+    /// Encodes the barcode as a sequence of bar and space widths. Characters are separated by
+    /// a narrow space, which can be widened with `Elements::with_gap`.
+    pub fn elements(&self) -> Elements {
+        let mut enc = Elements::new();
 
         for (i, u) in self.0.iter().enumerate() {
-            enc.extend(u.lookup().iter().cloned());
+            enc.extend(Elements::binary(&u.lookup()));
 
             if i < self.0.len() - 1 {
-                enc.push(0);
+                enc.push(Element::space(1.0).gap());
             }
         }
 
@@ -153,14 +159,14 @@ mod tests {
     fn invalid_length_codabar() {
         let codabar = Codabar::new("");
 
-        assert_eq!(codabar.err().unwrap(), Error::Length);
+        assert_eq!(codabar.err().unwrap(), Error::length(1..=256, 0));
     }
 
     #[test]
     fn invalid_data_codabar() {
         let codabar = Codabar::new("A12345G");
 
-        assert_eq!(codabar.err().unwrap(), Error::Character);
+        assert_eq!(codabar.err().unwrap(), Error::character(6, 'G'));
     }
 
     #[test]
@@ -0,0 +1,446 @@
+//! Encoder for Codablock F barcodes.
+//!
+//! Codablock F is a stacked symbology made up of 2 to 44 rows of Code128. Each row begins with
+//! a start character, a code set selector and a row indicator, and ends with its own check
+//! character and a stop character, so every row can be read by a standard Code128 scanner. The
+//! last row also holds the two check characters K1 and K2, which cover the whole message.
+//!
+//! Code sets A, B and C are chosen automatically, switching and shifting between them as
+//! needed. Characters in the ISO/IEC 8859-1 (Latin-1) range are supported, with those above 127
+//! preceded by FNC4.
+//!
+//! Rows hold 4 to 62 data characters. Unless fixed with `CodablockF::columns`, the number of
+//! columns is chosen to make the symbol roughly square. Rows are ten modules tall by default
+//! and are separated by one module bars, with bars above and below the symbol.
+
+use crate::error::{Error, Result};
+use crate::sym::code128::{CHARS, STOP, TERM};
+use crate::sym::helpers::{vec, Vec};
+use crate::sym::Matrix;
+
+// Code128 values for switching code sets. Code A and Code B double as FNC4 in code sets B and
+// A respectively.
+const SHIFT: u16 = 98;
+const CODE_C: u16 = 99;
+const CODE_B: u16 = 100;
+const CODE_A: u16 = 101;
+const START_A: u16 = 103;
+
+const MIN_COLUMNS: usize = 4;
+const MAX_COLUMNS: usize = 62;
+const MAX_ROWS: usize = 44;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Set {
+    A,
+    B,
+    C,
+}
+
+impl Set {
+    // Returns the value of the character in this code set, ignoring its high bit.
+    fn value(self, c: u8) -> Option<u16> {
+        match (self, c & 0x7F) {
+            (Set::A, c @ 0..=31) => Some(c as u16 + 64),
+            (Set::A, c @ 32..=95) | (Set::B, c @ 32..=127) => Some(c as u16 - 32),
+            _ => None,
+        }
+    }
+
+    // Returns the value that switches to this code set.
+    fn switch(self) -> u16 {
+        match self {
+            Set::A => CODE_A,
+            Set::B => CODE_B,
+            Set::C => CODE_C,
+        }
+    }
+
+    fn fnc4(self) -> u16 {
+        match self {
+            Set::A => CODE_A,
+            _ => CODE_B,
+        }
+    }
+
+    // Returns the value that represents the number (0-85) as a row indicator or K1 or K2 check
+    // character in this code set.
+    fn number(self, n: u16) -> u16 {
+        match (self, n) {
+            (Set::C, _) => n,
+            (_, 0..=31) => n + 64,
+            (Set::A, _) | (Set::B, 32..=47) => n - 32,
+            (Set::B, _) => n - 22,
+        }
+    }
+}
+
+fn digit_count(data: &[u8], start: usize) -> usize {
+    data[start..]
+        .iter()
+        .take_while(|c| c.is_ascii_digit())
+        .count()
+}
+
+// Returns the code set to begin a row with.
+fn row_set(data: &[u8], start: usize) -> Set {
+    let digits = digit_count(data, start);
+
+    if digits >= 4 && digits.is_multiple_of(2) {
+        Set::C
+    } else if Set::A.value(data[start]).is_some() && Set::B.value(data[start]).is_none() {
+        Set::A
+    } else {
+        Set::B
+    }
+}
+
+// Returns the values that must share a row to encode the next part of the data, the code set
+// they finish in and the number of characters they consume.
+fn next_values(data: &[u8], i: usize, set: Set) -> (Vec<u16>, Set, usize) {
+    let digits = digit_count(data, i);
+    let c = data[i];
+
+    match set {
+        Set::C if digits >= 2 => {
+            let pair = (data[i] - b'0') * 10 + data[i + 1] - b'0';
+
+            (vec![pair as u16], Set::C, 2)
+        }
+        Set::C => {
+            let to = if Set::B.value(c).is_some() {
+                Set::B
+            } else {
+                Set::A
+            };
+
+            (vec![to.switch()], to, 0)
+        }
+        _ if digits >= 4 && digits.is_multiple_of(2) => (vec![CODE_C], Set::C, 0),
+        _ => {
+            let extended = c > 127;
+            let mut values = vec![];
+
+            match set.value(c) {
+                Some(v) => {
+                    values.extend(Some(set.fnc4()).filter(|_| extended));
+                    values.push(v);
+                }
+                None => {
+                    let other = if set == Set::A { Set::B } else { Set::A };
+                    let next = data.get(i + 1).is_some_and(|&n| set.value(n).is_none());
+
+                    // Extended characters and runs switch code sets rather than shifting.
+                    if extended || next {
+                        return (vec![other.switch()], other, 0);
+                    }
+
+                    values.extend([SHIFT, other.value(c).unwrap_or(0)]);
+                }
+            }
+
+            (values, set, 1)
+        }
+    }
+}
+
+// Fills the rest of the row by switching back and forth between code sets B and C.
+fn fill(row: &mut Vec<u16>, set: &mut Set, len: usize) {
+    while row.len() < len {
+        *set = if *set == Set::B { Set::C } else { Set::B };
+        row.push(set.switch());
+    }
+}
+
+/// The Codablock F barcode type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodablockF {
+    data: Vec<u8>,
+    columns: usize,
+    rows: Vec<(Set, Vec<u16>)>,
+    row_height: usize,
+}
+
+impl CodablockF {
+    /// Creates a new barcode.
+    /// Returns Result<CodablockF, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<CodablockF> {
+        let data = data
+            .as_ref()
+            .chars()
+            .enumerate()
+            .map(|(i, c)| match c as u32 {
+                0..=255 => Ok(c as u8),
+                _ => Err(Error::character(i, c)),
+            })
+            .collect::<Result<Vec<u8>>>()?;
+
+        if data.is_empty() {
+            return Err(Error::length(1..=usize::MAX, 0));
+        }
+
+        // Aim for a square symbol, widening it if there would be too many rows.
+        let mut columns = MIN_COLUMNS;
+
+        while columns * columns < data.len() && columns < MAX_COLUMNS {
+            columns += 1;
+        }
+
+        for columns in columns..MAX_COLUMNS {
+            if let Ok(barcode) = CodablockF::layout(data.clone(), columns) {
+                return Ok(barcode);
+            }
+        }
+
+        CodablockF::layout(data, MAX_COLUMNS)
+    }
+
+    /// Sets the number of data characters in each row (4-62).
+    /// Returns Result<CodablockF, Error> indicating success.
+    pub fn columns(self, columns: usize) -> Result<CodablockF> {
+        if !(MIN_COLUMNS..=MAX_COLUMNS).contains(&columns) {
+            return Err(Error::option("columns", MIN_COLUMNS..=MAX_COLUMNS, columns));
+        }
+
+        CodablockF::layout(self.data, columns).map(|barcode| barcode.row_height(self.row_height))
+    }
+
+    /// Sets the height of each row in modules. The default is 10.
+    pub fn row_height(mut self, row_height: usize) -> CodablockF {
+        self.row_height = row_height.max(1);
+        self
+    }
+
+    /// Returns the number of rows and data columns.
+    pub fn size(&self) -> (usize, usize) {
+        (self.rows.len(), self.columns)
+    }
+
+    // Splits the data into rows of the given number of data characters, finishing with the K1
+    // and K2 check characters.
+    fn layout(data: Vec<u8>, columns: usize) -> Result<CodablockF> {
+        let mut rows = vec![];
+        let mut row = vec![];
+        let mut start = Set::B;
+        let mut set = Set::B;
+        let mut i = 0;
+
+        while i < data.len() {
+            if row.is_empty() {
+                start = row_set(&data, i);
+                set = start;
+            }
+
+            let (values, next, used) = next_values(&data, i, set);
+
+            if row.len() + values.len() > columns {
+                fill(&mut row, &mut set, columns);
+                rows.push((start, core::mem::take(&mut row)));
+                continue;
+            }
+
+            row.extend(values);
+            set = next;
+            i += used;
+        }
+
+        // The check characters end the last row, and there are always at least two rows.
+        while row.len() + 2 > columns || rows.is_empty() {
+            fill(&mut row, &mut set, columns);
+            rows.push((start, core::mem::take(&mut row)));
+            start = Set::B;
+            set = Set::B;
+        }
+
+        let (k1, k2) = data.iter().enumerate().fold((0, 0), |(k1, k2), (i, &c)| {
+            let c = c as usize;
+
+            ((k1 + (i + 1) * c) % 86, (k2 + i * c) % 86)
+        });
+
+        fill(&mut row, &mut set, columns - 2);
+        row.extend([set.number(k1 as u16), set.number(k2 as u16)]);
+        rows.push((start, row));
+
+        if rows.len() > MAX_ROWS {
+            return Err(Error::length(2..=MAX_ROWS, rows.len()));
+        }
+
+        Ok(CodablockF {
+            data,
+            columns,
+            rows,
+            row_height: 10,
+        })
+    }
+
+    // Returns the Code128 values of each row, from the start character to the row check
+    // character.
+    fn row_values(&self) -> Vec<Vec<u16>> {
+        let count = self.rows.len();
+
+        self.rows
+            .iter()
+            .enumerate()
+            .map(|(r, (start, data))| {
+                // Rows in code set A shift to B for the row indicator.
+                let (selector, indicator_set) = match start {
+                    Set::A => (SHIFT, Set::B),
+                    set => (set.switch(), *set),
+                };
+                let indicator = match r {
+                    0 => count - 2,
+                    r => r + 42,
+                };
+                let mut values = vec![START_A, selector, indicator_set.number(indicator as u16)];
+
+                values.extend(data);
+
+                let check = values
+                    .iter()
+                    .enumerate()
+                    .fold(0, |sum, (i, &v)| sum + i.max(1) * v as usize);
+
+                values.push((check % 103) as u16);
+                values
+            })
+            .collect()
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Matrix of modules.
+    pub fn encode(&self) -> Matrix {
+        let width = (self.columns + 5) * 11 + 2;
+        let pitch = self.row_height + 1;
+        let mut matrix = Matrix::new(width, self.rows.len() * pitch + 1);
+
+        for (r, values) in self.row_values().iter().enumerate() {
+            let modules = values
+                .iter()
+                .flat_map(|&v| CHARS[v as usize].1)
+                .chain(STOP)
+                .chain(TERM);
+
+            for (x, module) in modules.enumerate() {
+                for y in 0..self.row_height {
+                    matrix.set(x, r * pitch + 1 + y, module);
+                }
+            }
+        }
+
+        // Bars run along the top and bottom of the symbol, and separate the rows between the
+        // start and stop characters.
+        for r in 0..=self.rows.len() {
+            let edge = r == 0 || r == self.rows.len();
+            let xs = if edge { 0..width } else { 11..width - 13 };
+
+            for x in xs {
+                matrix.set(x, r * pitch, 1);
+            }
+        }
+
+        matrix
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sym::codablock::*;
+
+    #[test]
+    fn new_codablock() {
+        let codablock1 = CodablockF::new("Hello, World!");
+        let codablock2 = CodablockF::new("Grüße\r\n0123456789");
+
+        assert!(codablock1.is_ok());
+        assert!(codablock2.is_ok());
+    }
+
+    #[test]
+    fn invalid_data_codablock() {
+        let codablock = CodablockF::new("€100");
+
+        assert_eq!(codablock.err().unwrap(), Error::character(0, '€'));
+    }
+
+    #[test]
+    fn invalid_len_codablock() {
+        let codablock1 = CodablockF::new("");
+        let codablock2 = CodablockF::new("ABC").unwrap().columns(3);
+        let codablock3 = CodablockF::new("ABC").unwrap().columns(63);
+        let codablock4 = CodablockF::new("A".repeat(200)).unwrap().columns(4);
+        let codablock5 = CodablockF::new("A".repeat(2800));
+
+        assert_eq!(codablock1.err().unwrap(), Error::length(1..=usize::MAX, 0));
+        assert_eq!(
+            codablock2.err().unwrap(),
+            Error::option("columns", 4..=62, 3)
+        );
+        assert_eq!(
+            codablock3.err().unwrap(),
+            Error::option("columns", 4..=62, 63)
+        );
+        assert_eq!(codablock4.err().unwrap(), Error::length(2..=44, 51));
+        assert_eq!(codablock5.err().unwrap(), Error::length(2..=44, 46));
+    }
+
+    #[test]
+    fn codablock_size() {
+        let codablock1 = CodablockF::new("ABC").unwrap();
+        let codablock2 = CodablockF::new("A".repeat(100)).unwrap();
+        let codablock3 = CodablockF::new("A".repeat(100))
+            .unwrap()
+            .columns(20)
+            .unwrap();
+        let codablock4 = CodablockF::new("0".repeat(40)).unwrap().columns(4).unwrap();
+
+        assert_eq!(codablock1.size(), (2, 4));
+        assert_eq!(codablock2.size(), (11, 10));
+        assert_eq!(codablock3.size(), (6, 20));
+        assert_eq!(codablock4.size(), (6, 4));
+    }
+
+    #[test]
+    fn codablock_rows() {
+        let codablock = CodablockF::new("ABC").unwrap();
+
+        assert_eq!(
+            codablock.row_values(),
+            vec![
+                vec![103, 100, 64, 33, 34, 35, 99, 99],
+                vec![103, 100, 11, 99, 100, 32, 92, 89],
+            ]
+        );
+    }
+
+    #[test]
+    fn codablock_code_sets() {
+        let codablock1 = CodablockF::new("a\tb").unwrap().columns(10).unwrap();
+        let codablock2 = CodablockF::new("\t\tb").unwrap().columns(10).unwrap();
+        let codablock3 = CodablockF::new("A12345").unwrap().columns(10).unwrap();
+        let codablock4 = CodablockF::new("é").unwrap().columns(10).unwrap();
+
+        assert_eq!(codablock1.rows[0].1[..4], [65, 98, 73, 66]);
+        assert_eq!(
+            codablock2.rows[0],
+            (Set::A, vec![73, 73, 98, 66, 100, 99, 100, 99, 100, 99])
+        );
+        assert_eq!(codablock3.rows[0].1[..6], [33, 17, 99, 23, 45, 100]);
+        assert_eq!(codablock4.rows[0].1[..2], [100, 73]);
+    }
+
+    #[test]
+    fn codablock_encode() {
+        let codablock = CodablockF::new("ABC").unwrap().row_height(2);
+        let matrix = codablock.encode();
+
+        assert_eq!(matrix.width(), 101);
+        assert_eq!(matrix.height(), 7);
+        assert_eq!(matrix.rows().next().unwrap(), &[1; 101][..]);
+        assert_eq!(matrix.get(10, 3), 0);
+        assert_eq!(matrix.get(11, 3), 1);
+        assert_eq!(matrix.get(87, 3), 1);
+        assert_eq!(matrix.get(88, 3), 0);
+        assert_eq!(matrix.get(100, 6), 1);
+    }
+}
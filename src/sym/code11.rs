@@ -6,10 +6,13 @@
 //! Code11 is a discrete symbology. This encoder always provides a C checksum. For barcodes longer
 //! than 10 characters, a second checksum digit (K) is appended.
 
+use crate::checksum;
 use crate::error::Result;
-use crate::sym::{helpers, Parse};
+use crate::sym::{helpers, Element, Elements, Parse};
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
 use core::ops::Range;
-use helpers::{vec, Vec};
+use helpers::Vec;
 
 // Character -> Binary mappings for each of the allowable characters.
 // The special "full-ASCII" characters are represented with (, ), [, ].
@@ -29,7 +32,6 @@ const CHARS: [(char, &[u8]); 11] = [
 
 // Code11 barcodes must start and end with a special character.
 const GUARD: [u8; 7] = [1, 0, 1, 1, 0, 0, 1];
-const SEPARATOR: [u8; 1] = [0];
 
 /// The Code11 barcode type.
 #[derive(Debug)]
@@ -52,47 +54,34 @@ impl Code11 {
         }
     }
 
-    /// Calculates a checksum character using a weighted modulo-11 algorithm.
-    fn checksum_char(&self, data: &[char], weight_threshold: usize) -> Option<char> {
-        let get_char_pos = |&c| CHARS.iter().position(|t| t.0 == c).unwrap();
-        let weight = |i| match i % weight_threshold {
-            0 => weight_threshold,
-            n => n,
-        };
-        let positions = data.iter().map(&get_char_pos);
-        let index = positions
-            .rev()
-            .enumerate()
-            .fold(0, |acc, (i, pos)| acc + (weight(i + 1) * pos));
-
-        // Some sources suggest that the C checksum should use modulo-11, whilst the K
-        // checksum should use modulo-9. But most generators always use modulo-11.
-        // This algorithm currently just uses 11 for both checksums, but can be easily
-        // changed at a later date.
-        CHARS.get(index % CHARS.len()).map(|&(c, _)| c)
+    /// Returns the C check character, and the K check character for barcodes longer than 10
+    /// characters, calculated using a weighted modulo-11 algorithm.
+    pub fn check_chars(&self) -> (char, Option<char>) {
+        checksum::code11_chars(&self.0)
     }
 
-    /// Calculates the C checksum character using a weighted modulo-11 algorithm.
-    fn c_checksum_char(&self) -> Option<char> {
-        self.checksum_char(&self.0, 10)
-    }
+    /// Returns the encoded data, including the check characters.
+    pub fn data(&self) -> String {
+        let (c, k) = self.check_chars();
 
-    /// Calculates the K checksum character using a weighted modulo-11 algorithm.
-    fn k_checksum_char(&self, c_checksum: char) -> Option<char> {
-        let mut data: Vec<char> = self.0.clone();
-        data.push(c_checksum);
+        self.0.iter().copied().chain(Some(c)).chain(k).collect()
+    }
 
-        self.checksum_char(&data, 9)
+    /// Returns the human readable text, which is the data without the check characters.
+    pub fn text(&self) -> String {
+        self.0.iter().collect()
     }
 
-    fn push_encoding(&self, into: &mut Vec<u8>, from: &[u8]) {
-        into.extend(from.iter().cloned());
-        into.extend(&SEPARATOR);
+    // Encoded characters are separated by a narrow space, which can be widened with
+    // `Elements::with_gap`.
+    fn push_encoding(&self, into: &mut Elements, from: &[u8]) {
+        into.extend(Elements::binary(from));
+        into.push(Element::space(1.0).gap());
     }
 
-    fn payload(&self) -> Vec<u8> {
-        let mut enc = vec![];
-        let c_checksum = self.c_checksum_char().expect("Cannot compute checksum C");
+    fn payload(&self) -> Elements {
+        let mut enc = Elements::new();
+        let (c_checksum, k_checksum) = self.check_chars();
 
         for &c in &self.0 {
             self.push_encoding(&mut enc, self.char_encoding(c));
@@ -101,11 +90,7 @@ impl Code11 {
         self.push_encoding(&mut enc, self.char_encoding(c_checksum));
 
         // K-checksum is only appended on barcodes greater than 10 characters.
-        if self.0.len() > 10 {
-            let k_checksum = self
-                .k_checksum_char(c_checksum)
-                .expect("Cannot compute checksum K");
-
+        if let Some(k_checksum) = k_checksum {
             self.push_encoding(&mut enc, self.char_encoding(k_checksum));
         }
 
@@ -115,9 +100,17 @@ impl Code11 {
     /// Encodes the barcode.
     /// Returns a Vec<u8> of encoded binary digits.
     pub fn encode(&self) -> Vec<u8> {
-        let guard = &GUARD[..];
+        self.elements().modules()
+    }
+
+    /// Encodes the barcode as a sequence of bar and space widths.
+    pub fn elements(&self) -> Elements {
+        let mut elements = Elements::new();
 
-        helpers::join_slices(&[guard, &SEPARATOR, &self.payload()[..], guard][..])
+        self.push_encoding(&mut elements, &GUARD);
+        elements.extend(self.payload());
+        elements.extend(Elements::binary(&GUARD));
+        elements
     }
 }
 
@@ -152,14 +145,14 @@ mod tests {
     fn invalid_length_code11() {
         let code11 = Code11::new("");
 
-        assert_eq!(code11.err().unwrap(), Error::Length);
+        assert_eq!(code11.err().unwrap(), Error::length(1..=256, 0));
     }
 
     #[test]
     fn invalid_data_code11() {
         let code11 = Code11::new("NOTDIGITS");
 
-        assert_eq!(code11.err().unwrap(), Error::Character);
+        assert_eq!(code11.err().unwrap(), Error::character(0, 'N'));
     }
 
     #[test]
@@ -188,4 +181,16 @@ mod tests {
 
         assert_eq!(collapse_vec(code111.encode()), "101100101101011010010110110010101011011010110101101101010011010101001101101001010110101011011011001010100101101101011011011010100110101011001");
     }
+
+    #[test]
+    fn code11_text() {
+        let code111 = Code11::new("123-45").unwrap();
+        let code112 = Code11::new("12345678901").unwrap();
+
+        assert_eq!(code111.check_chars(), ('5', None));
+        assert_eq!(code111.data(), "123-455");
+        assert_eq!(code111.text(), "123-45");
+        assert_eq!(code112.check_chars(), ('4', Some('-')));
+        assert_eq!(code112.data(), "123456789014-");
+    }
 }
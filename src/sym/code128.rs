@@ -52,11 +52,14 @@
 //! - FNC4: ```ż``` (```\u{017C}```)
 //! - SHIFT: ```Ž``` (```\u{017D}```)
 
+use crate::checksum;
 use crate::error::*;
-use crate::sym::helpers;
+use crate::sym::{helpers, Elements};
 #[cfg(not(feature = "std"))]
-use alloc::{format, string::ToString};
-use core::cmp;
+use alloc::{
+    format,
+    string::{String, ToString},
+};
 use helpers::{vec, Vec};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -72,7 +75,7 @@ enum UnitKind {
     C,
 }
 
-type Encoding = [u8; 11];
+pub(crate) type Encoding = [u8; 11];
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum CharacterSet {
@@ -83,7 +86,7 @@ enum CharacterSet {
 }
 
 // Character -> Binary mappings for each of the allowable characters in each character-set.
-const CHARS: [([&str; 3], Encoding); 106] = [
+pub(crate) const CHARS: [([&str; 3], Encoding); 106] = [
     ([" ", " ", "00"], [1, 1, 0, 1, 1, 0, 0, 1, 1, 0, 0]),
     (["!", "!", "01"], [1, 1, 0, 0, 1, 1, 0, 1, 1, 0, 0]),
     (["\"", "\"", "02"], [1, 1, 0, 0, 1, 1, 0, 0, 1, 1, 0]),
@@ -216,11 +219,32 @@ const CHARS: [([&str; 3], Encoding); 106] = [
     ),
 ];
 
+// Returns every character that can appear in the data in some character-set, including the
+// character-set and function characters.
+pub(crate) fn valid_chars() -> Vec<char> {
+    let mut chars: Vec<char> = CHARS
+        .iter()
+        .flat_map(|c| c.0.iter())
+        .filter_map(|s| {
+            let mut chars = s.chars();
+
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Some(c),
+                _ => None,
+            }
+        })
+        .collect();
+
+    chars.sort_unstable();
+    chars.dedup();
+    chars
+}
+
 // Stop sequence.
-const STOP: Encoding = [1, 1, 0, 0, 0, 1, 1, 1, 0, 1, 0];
+pub(crate) const STOP: Encoding = [1, 1, 0, 0, 0, 1, 1, 1, 0, 1, 0];
 
 // Termination sequence.
-const TERM: [u8; 2] = [1, 1];
+pub(crate) const TERM: [u8; 2] = [1, 1];
 
 /// The Code128 barcode type.
 ///
@@ -246,41 +270,41 @@ impl Unit {
 }
 
 impl CharacterSet {
-    fn from_char(c: char) -> Result<CharacterSet> {
+    fn from_char(c: char) -> Option<CharacterSet> {
         match c {
-            'À' => Ok(CharacterSet::A),
-            'Ɓ' => Ok(CharacterSet::B),
-            'Ć' => Ok(CharacterSet::C),
-            _ => Err(Error::Character),
+            'À' => Some(CharacterSet::A),
+            'Ɓ' => Some(CharacterSet::B),
+            'Ć' => Some(CharacterSet::C),
+            _ => None,
         }
     }
 
-    fn unit(self, n: usize) -> Result<Unit> {
+    fn unit(self, n: usize) -> Option<Unit> {
         let kind = match self {
             CharacterSet::A => UnitKind::A,
             CharacterSet::B => UnitKind::B,
             CharacterSet::C => UnitKind::C,
-            CharacterSet::None => return Err(Error::Character),
+            CharacterSet::None => return None,
         };
-        Ok(Unit { kind, index: n })
+        Some(Unit { kind, index: n })
     }
 
-    fn index(self) -> Result<usize> {
+    fn index(self) -> Option<usize> {
         match self {
-            CharacterSet::A => Ok(0),
-            CharacterSet::B => Ok(1),
-            CharacterSet::C => Ok(2),
-            CharacterSet::None => Err(Error::Character),
+            CharacterSet::A => Some(0),
+            CharacterSet::B => Some(1),
+            CharacterSet::C => Some(2),
+            CharacterSet::None => None,
         }
     }
 
-    fn lookup(self, s: &str) -> Result<Unit> {
+    fn lookup(self, s: &str) -> Option<Unit> {
         let p = self.index()?;
 
-        match CHARS.iter().position(|&c| c.0[p] == s) {
-            Some(i) => self.unit(i),
-            None => Err(Error::Character),
-        }
+        CHARS
+            .iter()
+            .position(|&c| c.0[p] == s)
+            .and_then(|i| self.unit(i))
     }
 }
 
@@ -289,8 +313,8 @@ impl Code128 {
     /// Returns Result<Code128, Error> indicating parse success.
     pub fn new<T: AsRef<str>>(data: T) -> Result<Code128> {
         let data = data.as_ref();
-        if data.len() < 2 {
-            return Err(Error::Length);
+        if data.chars().count() < 2 {
+            return Err(Error::length(2..=usize::MAX, data.chars().count()));
         }
 
         Code128::parse(data.chars().collect()).map(Code128)
@@ -300,62 +324,106 @@ impl Code128 {
     fn parse(chars: Vec<char>) -> Result<Vec<Unit>> {
         let mut units: Vec<Unit> = vec![];
         let mut char_set = CharacterSet::None;
-        let mut carry: Option<char> = None;
+        // An unpaired digit of character-set C, and its index.
+        let mut carry: Option<(usize, char)> = None;
+
+        for (i, ch) in chars.into_iter().enumerate() {
+            let invalid = || Error::character(i, ch);
 
-        for ch in chars {
             match ch {
                 'À' | 'Ɓ' | 'Ć' if units.is_empty() => {
-                    char_set = CharacterSet::from_char(ch)?;
+                    char_set = CharacterSet::from_char(ch).ok_or_else(invalid)?;
 
                     let c = format!("START-{}", ch);
-                    let u = char_set.lookup(&c)?;
+                    let u = char_set.lookup(&c).ok_or_else(invalid)?;
                     units.push(u);
                 }
                 'À' | 'Ɓ' | 'Ć' => {
-                    if char_set == CharacterSet::C && carry.is_some() {
-                        return Err(Error::Character);
+                    if let (CharacterSet::C, Some((n, d))) = (char_set, carry) {
+                        return Err(Error::character(n, d));
                     } else {
-                        let u = char_set.lookup(&ch.to_string())?;
+                        let u = char_set.lookup(&ch.to_string()).ok_or_else(invalid)?;
                         units.push(u);
 
-                        char_set = CharacterSet::from_char(ch)?;
+                        char_set = CharacterSet::from_char(ch).ok_or_else(invalid)?;
                     }
                 }
                 d if d.is_ascii_digit() && char_set == CharacterSet::C => match carry {
-                    None => carry = Some(d),
-                    Some(n) => {
+                    None => carry = Some((i, d)),
+                    Some((_, n)) => {
                         let num = format!("{}{}", n, d);
-                        let u = char_set.lookup(&num)?;
+                        let u = char_set.lookup(&num).ok_or_else(invalid)?;
                         units.push(u);
                         carry = None;
                     }
                 },
                 _ => {
-                    let u = char_set.lookup(&ch.to_string())?;
+                    let u = char_set.lookup(&ch.to_string()).ok_or_else(invalid)?;
                     units.push(u);
                 }
             }
         }
 
         match carry {
-            Some(_) => Err(Error::Character),
+            Some((n, d)) => Err(Error::character(n, d)),
             None => Ok(units),
         }
     }
 
-    /// Calculates the checksum index using a modulo-103 algorithm.
-    fn checksum_value(&self) -> u8 {
-        let sum: i32 = self
-            .0
+    // Returns a copy of the barcode with the linkage flag of a GS1 Composite barcode appended, a
+    // code set character chosen by the final code set and by whether the 2D component is a CC-C.
+    // The barcode must be GS1-128, beginning with FNC1.
+    pub(crate) fn linked(&self, cc_c: bool) -> Result<Code128> {
+        match self.0.get(1) {
+            Some(unit) if unit.index() == 102 => {}
+            // The first character of a unit is the one given in the data.
+            Some(unit) => {
+                let text = CHARS[unit.index()].0[unit.kind as usize];
+                return Err(Error::character(1, text.chars().next().unwrap_or(' ')));
+            }
+            None => return Err(Error::length(2..=usize::MAX, self.0.len())),
+        }
+
+        let kind = self.0[self.0.len() - 1].kind;
+        let index = match (kind, cc_c) {
+            (UnitKind::A, false) | (UnitKind::C, true) => 100,
+            (UnitKind::B, false) | (UnitKind::A, true) => 99,
+            (UnitKind::C, false) | (UnitKind::B, true) => 101,
+        };
+
+        let mut units = self.0.clone();
+        units.push(Unit { kind, index });
+        Ok(Code128(units))
+    }
+
+    /// Returns the check value, the symbol value (0-102) calculated using a weighted modulo-103
+    /// algorithm.
+    pub fn check_value(&self) -> u8 {
+        checksum::modulo_103_value(self.0.iter().map(Unit::index))
+    }
+
+    /// Returns the encoded data, including the character-set, function and shift characters but
+    /// not the check value.
+    pub fn data(&self) -> String {
+        self.0
             .iter()
-            .zip(0..self.0.len() as i32)
-            .fold(0, |t, (u, i)| t + (u.index() as i32 * cmp::max(1, i)));
+            .map(|u| CHARS[u.index].0[u.kind as usize])
+            .map(|s| s.trim_start_matches("START-"))
+            .collect()
+    }
 
-        (sum % 103) as u8
+    /// Returns the human readable text, which is the data without the character-set, function
+    /// and shift characters.
+    pub fn text(&self) -> String {
+        self.0
+            .iter()
+            .filter(|u| u.index < if u.kind == UnitKind::C { 100 } else { 96 })
+            .map(|u| CHARS[u.index].0[u.kind as usize])
+            .collect()
     }
 
     fn checksum_encoding(&self) -> Encoding {
-        let v = self.checksum_value();
+        let v = self.check_value();
         self.unit_encoding(&Unit {
             kind: UnitKind::A,
             index: v as usize,
@@ -384,6 +452,11 @@ impl Code128 {
             ][..],
         )
     }
+
+    /// Encodes the barcode as a sequence of bar and space widths.
+    pub fn elements(&self) -> Elements {
+        Elements::from(self.encode())
+    }
 }
 
 #[cfg(test)]
@@ -412,7 +485,7 @@ mod tests {
     fn invalid_length_code128() {
         let code128_a = Code128::new("");
 
-        assert_eq!(code128_a.err().unwrap(), Error::Length);
+        assert_eq!(code128_a.err().unwrap(), Error::length(2..=usize::MAX, 0));
     }
 
     #[test]
@@ -421,9 +494,30 @@ mod tests {
         let code128_b = Code128::new("ÀHELLOĆ12352"); // Trailing carry at the end.
         let code128_c = Code128::new("HELLO"); // No Character-Set specified.
 
-        assert_eq!(code128_a.err().unwrap(), Error::Character);
-        assert_eq!(code128_b.err().unwrap(), Error::Character);
-        assert_eq!(code128_c.err().unwrap(), Error::Character);
+        assert_eq!(code128_a.err().unwrap(), Error::character(1, '☺'));
+        assert_eq!(code128_b.err().unwrap(), Error::character(11, '2'));
+        assert_eq!(code128_c.err().unwrap(), Error::character(0, 'H'));
+    }
+
+    #[test]
+    fn invalid_data_position_code128() {
+        let code128_a = Code128::new("ƁLOT 2024-07 ☺ BATCH 0001 / LINE 7 OK");
+        let code128_b = Code128::new("ÀHELLOĆ12352");
+
+        assert!(matches!(
+            code128_a,
+            Err(Error::Character {
+                character: '☺',
+                index: 13
+            })
+        ));
+        assert!(matches!(
+            code128_b,
+            Err(Error::Character {
+                character: '2',
+                index: 11
+            })
+        ));
     }
 
     #[test]
@@ -464,4 +558,16 @@ mod tests {
         assert_eq!(collapse_vec(code128_b.encode()), "110100001001110001011011101101000101110111101101110010010111011110100111011001100011101011");
         assert_eq!(collapse_vec(code128_c.encode()), "1101001000011110010010110110111101110110001011101011110100111001101110010110011100101100110011011001100100010010011100110100101111001100011101011");
     }
+
+    #[test]
+    fn code128_text() {
+        let code128_a = Code128::new("ÀHELLO").unwrap();
+        let code128_b = Code128::new("ƁHiĆ3456ƁŹx").unwrap();
+
+        assert_eq!(code128_a.check_value(), 39);
+        assert_eq!(code128_a.data(), "ÀHELLO");
+        assert_eq!(code128_a.text(), "HELLO");
+        assert_eq!(code128_b.data(), "ƁHiĆ3456ƁŹx");
+        assert_eq!(code128_b.text(), "Hi3456x");
+    }
 }
@@ -0,0 +1,160 @@
+//! Encoder for Code 32 barcodes.
+//!
+//! Code 32, also known as Italian Pharmacode, is used to mark pharmaceutical products sold in
+//! Italy. It encodes the nine digit AIC (Autorizzazione all'Immissione in Commercio) number,
+//! including its check digit, as six base-32 characters in a Code39 barcode.
+//!
+//! The human readable text is the AIC number prefixed by "A".
+
+use crate::checksum;
+use crate::error::{Error, Result};
+use crate::sym::code39::Code39;
+use crate::sym::{helpers, Elements, Parse};
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+use core::ops::Range;
+use helpers::Vec;
+
+// The base-32 digits, which are the Code39 digits and consonants.
+const DIGITS: &[u8; 32] = b"0123456789BCDFGHJKLMNPQRSTUVWXYZ";
+
+/// The Code 32 barcode type.
+#[derive(Debug)]
+pub struct Code32(Vec<u8>);
+
+impl Code32 {
+    /// Creates a new barcode from an eight digit AIC number, with or without its check digit.
+    /// Returns Result<Code32, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<Code32> {
+        let d = Code32::parse(data.as_ref())?;
+        let digits: Vec<u8> = d
+            .chars()
+            .map(|c| c.to_digit(10).expect("Unknown character") as u8)
+            .collect();
+
+        let code32 = Code32(digits[0..8].to_vec());
+
+        // If checksum digit is provided, check the checksum.
+        if digits.len() == 9 && code32.check_digit() != digits[8] {
+            return Err(Error::checksum(code32.check_digit(), digits[8]));
+        }
+
+        Ok(code32)
+    }
+
+    /// Returns the check digit of the AIC number, calculated by doubling the digits in even
+    /// positions and summing the digits of the results.
+    pub fn check_digit(&self) -> u8 {
+        checksum::code32_digit(&self.0)
+    }
+
+    // Returns the AIC number, with its check digit, in base 32.
+    fn base32(&self) -> [u8; 6] {
+        let mut value = self
+            .0
+            .iter()
+            .chain(Some(&self.check_digit()))
+            .fold(0, |value, &d| value * 10 + d as usize);
+        let mut chars = [0; 6];
+
+        for c in chars.iter_mut().rev() {
+            *c = DIGITS[value % 32];
+            value /= 32;
+        }
+
+        chars
+    }
+
+    /// Returns the encoded data, the AIC number in six base-32 characters.
+    pub fn data(&self) -> String {
+        self.base32().iter().map(|&c| c as char).collect()
+    }
+
+    /// Returns the human readable text, the nine digit AIC number prefixed by "A".
+    pub fn text(&self) -> String {
+        let mut text = String::from("A");
+
+        text.push_str(&helpers::digit_string(&self.0));
+        text.push((b'0' + self.check_digit()) as char);
+        text
+    }
+
+    /// Encodes the barcode as a sequence of bar and space widths.
+    pub fn elements(&self) -> Elements {
+        Code39::new(self.data())
+            .expect("Base-32 digits are valid Code39")
+            .elements()
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Vec<u8> of binary digits.
+    pub fn encode(&self) -> Vec<u8> {
+        self.elements().modules()
+    }
+}
+
+impl Parse for Code32 {
+    /// Returns the valid length of data acceptable in this type of barcode.
+    fn valid_len() -> Range<u32> {
+        8..9
+    }
+
+    /// Returns the set of valid characters allowed in this type of barcode.
+    fn valid_chars() -> Vec<char> {
+        (0..10).map(|i| char::from_digit(i, 10).unwrap()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sym::code32::*;
+
+    #[test]
+    fn new_code32() {
+        let code321 = Code32::new("01234567");
+        let code322 = Code32::new("012345676");
+
+        assert!(code321.is_ok());
+        assert!(code322.is_ok());
+    }
+
+    #[test]
+    fn invalid_data_code32() {
+        let code32 = Code32::new("0123456A");
+
+        assert_eq!(code32.err().unwrap(), Error::character(7, 'A'));
+    }
+
+    #[test]
+    fn invalid_len_code32() {
+        let code32 = Code32::new("1234567");
+
+        assert_eq!(code32.err().unwrap(), Error::length(8..=9, 7));
+    }
+
+    #[test]
+    fn invalid_checksum_code32() {
+        let code32 = Code32::new("012345677");
+
+        assert_eq!(code32.err().unwrap(), Error::checksum(6, 7));
+    }
+
+    #[test]
+    fn code32_encode() {
+        let code321 = Code32::new("01234567").unwrap();
+        let code322 = Code32::new("000000000").unwrap();
+
+        assert_eq!(&code321.base32(), b"0CSSBD");
+        assert_eq!(code321.encode(), Code39::new("0CSSBD").unwrap().encode());
+        assert_eq!(code322.encode(), Code39::new("000000").unwrap().encode());
+    }
+
+    #[test]
+    fn code32_text() {
+        let code32 = Code32::new("01234567").unwrap();
+
+        assert_eq!(code32.check_digit(), 6);
+        assert_eq!(code32.data(), "0CSSBD");
+        assert_eq!(code32.text(), "A012345676");
+    }
+}
@@ -6,10 +6,13 @@
 //! popular in non-retail environments. It was one of the first symbologies to support encoding
 //! of the ASCII alphabet.
 
+use crate::checksum;
 use crate::error::Result;
-use crate::sym::{helpers, Parse};
+use crate::sym::{helpers, Element, Elements, Parse};
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
 use core::ops::Range;
-use helpers::{vec, Vec};
+use helpers::Vec;
 
 // Character -> Binary mappings for each of the 43 allowable character.
 const CHARS: [(char, [u8; 12]); 43] = [
@@ -90,19 +93,31 @@ impl Code39 {
     }
 
     /// Calculates the checksum character using a modulo-43 algorithm.
-    fn checksum_char(&self) -> Option<char> {
-        let get_char_pos = |&c| CHARS.iter().position(|t| t.0 == c).unwrap();
-        let indices = self.data.iter().map(&get_char_pos);
-        let index = indices.sum::<usize>() % CHARS.len();
-
-        CHARS.get(index).map(|&(c, _)| c)
+    fn checksum_char(&self) -> char {
+        checksum::code39_char(&self.data)
     }
 
     fn checksum_encoding(&self) -> [u8; 12] {
-        match self.checksum_char() {
-            Some(c) => self.char_encoding(c),
-            None => panic!("Cannot compute checksum"),
-        }
+        self.char_encoding(self.checksum_char())
+    }
+
+    /// Returns the modulo-43 check character, if the barcode has one.
+    pub fn check_char(&self) -> Option<char> {
+        self.checksum.then(|| self.checksum_char())
+    }
+
+    /// Returns the encoded data, including the check character if the barcode has one.
+    pub fn data(&self) -> String {
+        self.data.iter().copied().chain(self.check_char()).collect()
+    }
+
+    /// Returns the human readable text, the encoded data between '*' start and stop characters.
+    pub fn text(&self) -> String {
+        let mut text = String::from("*");
+
+        text.push_str(&self.data());
+        text.push('*');
+        text
     }
 
     fn char_encoding(&self, c: char) -> [u8; 12] {
@@ -112,15 +127,15 @@ impl Code39 {
         }
     }
 
-    // Encoded characters are separated by a single "narrow" bar in
-    // Code39 barcodes.
-    fn push_encoding(&self, into: &mut Vec<u8>, from: [u8; 12]) {
-        into.extend(from.iter().cloned());
-        into.push(0);
+    // Encoded characters are separated by a single "narrow" space in
+    // Code39 barcodes. This inter-character gap can be widened with `Elements::with_gap`.
+    fn push_encoding(&self, into: &mut Elements, from: [u8; 12]) {
+        into.extend(Elements::binary(&from));
+        into.push(Element::space(1.0).gap());
     }
 
-    fn payload(&self) -> Vec<u8> {
-        let mut enc = vec![0];
+    fn payload(&self) -> Elements {
+        let mut enc = Elements::new();
 
         for c in &self.data {
             self.push_encoding(&mut enc, self.char_encoding(*c));
@@ -133,12 +148,20 @@ impl Code39 {
         enc
     }
 
+    /// Encodes the barcode as a sequence of bar and space widths.
+    pub fn elements(&self) -> Elements {
+        let mut elements = Elements::new();
+
+        self.push_encoding(&mut elements, GUARD);
+        elements.extend(self.payload());
+        elements.extend(Elements::binary(&GUARD));
+        elements
+    }
+
     /// Encodes the barcode.
     /// Returns a Vec<u8> of binary digits.
     pub fn encode(&self) -> Vec<u8> {
-        let guard = &GUARD[..];
-
-        helpers::join_slices(&[guard, &self.payload()[..], guard][..])
+        self.elements().modules()
     }
 }
 
@@ -177,14 +200,32 @@ mod tests {
     fn invalid_data_code39() {
         let code39 = Code39::new("1212s");
 
-        assert_eq!(code39.err().unwrap(), Error::Character);
+        assert_eq!(code39.err().unwrap(), Error::character(4, 's'));
     }
 
     #[test]
     fn invalid_len_code39() {
         let code39 = Code39::new("");
 
-        assert_eq!(code39.err().unwrap(), Error::Length);
+        assert_eq!(code39.err().unwrap(), Error::length(1..=256, 0));
+    }
+
+    #[test]
+    fn invalid_position_code39() {
+        let code391 = Code39::new("");
+        let code392 = Code39::new("AB-c12");
+
+        assert!(matches!(
+            code391,
+            Err(Error::Length { allowed, actual: 0 }) if !allowed.contains(0) && allowed.contains(1)
+        ));
+        assert!(matches!(
+            code392,
+            Err(Error::Character {
+                character: 'c',
+                index: 3
+            })
+        ));
     }
 
     #[test]
@@ -209,4 +250,59 @@ mod tests {
         assert_eq!(collapse_vec(code391.encode()), "100101101101011010010101101011001010110110110010101010100110101101101010010110100101101101");
         assert_eq!(collapse_vec(code392.encode()), "1001011011010101100101101011010010110101101100101010110101011001010101100101101101001101010110100101011010110010101101011011010010100101101101");
     }
+
+    #[test]
+    fn code39_elements() {
+        let code39 = Code39::with_checksum("1234").unwrap();
+        let elements = code39.elements();
+
+        // Each of the seven characters has five bars, four spaces and an inter-character gap.
+        assert_eq!(elements.len(), 7 * 10 - 1);
+        assert!(elements
+            .iter()
+            .all(|e| e.width() == 1.0 || e.width() == 2.0));
+        assert_eq!(elements.modules(), code39.encode());
+    }
+
+    #[test]
+    fn code39_ratio() {
+        let code39 = Code39::new("1234").unwrap();
+
+        // Each of the six characters has three wide elements.
+        assert_eq!(code39.elements().width(), 77.0);
+        assert_eq!(
+            code39.elements().with_ratio(3.0).unwrap().width(),
+            77.0 + 18.0
+        );
+        assert_eq!(
+            code39.elements().with_ratio(2.5).unwrap().width(),
+            77.0 + 9.0
+        );
+    }
+
+    #[test]
+    fn code39_gap() {
+        let code39 = Code39::new("1234").unwrap();
+        let elements = code39.elements().with_gap(2.0).unwrap();
+
+        // Each of the six characters is followed by a gap, except the stop character.
+        assert_eq!(elements.iter().filter(|e| e.is_gap()).count(), 5);
+        assert_eq!(elements.width(), 77.0 + 5.0);
+        assert_eq!(
+            &elements.modules()[..15],
+            &[1, 0, 0, 1, 0, 1, 1, 0, 1, 1, 0, 1, 0, 0, 1]
+        );
+    }
+
+    #[test]
+    fn code39_text() {
+        let code391 = Code39::with_checksum("CODE39").unwrap();
+        let code392 = Code39::new("CODE39").unwrap();
+
+        assert_eq!(code391.check_char(), Some('W'));
+        assert_eq!(code391.data(), "CODE39W");
+        assert_eq!(code391.text(), "*CODE39W*");
+        assert_eq!(code392.check_char(), None);
+        assert_eq!(code392.text(), "*CODE39*");
+    }
 }
@@ -9,8 +9,11 @@
 //! mode.
 
 use super::helpers::{vec, Vec};
+use crate::checksum;
 use crate::error::Result;
-use crate::sym::{helpers, Parse};
+use crate::sym::{helpers, Elements, Parse};
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
 use core::ops::Range;
 
 // Character -> Binary mappings for each of the 47 allowable character.
@@ -87,32 +90,21 @@ impl Code93 {
         }
     }
 
-    /// Calculates a checksum character using a weighted modulo-47 algorithm.
-    fn checksum_char(&self, data: &[char], weight_threshold: usize) -> Option<char> {
-        let get_char_pos = |&c| CHARS.iter().position(|t| t.0 == c).unwrap();
-        let weight = |i| match (data.len() - i) % weight_threshold {
-            0 => weight_threshold,
-            n => n,
-        };
-        let positions = data.iter().map(&get_char_pos);
-        let index = positions
-            .enumerate()
-            .fold(0, |acc, (i, pos)| acc + (weight(i) * pos));
-
-        CHARS.get(index % CHARS.len()).map(|&(c, _)| c)
+    /// Returns the C and K check characters, calculated using a weighted modulo-47 algorithm.
+    pub fn check_chars(&self) -> (char, char) {
+        checksum::code93_chars(&self.0)
     }
 
-    /// Calculates the C checksum character using a weighted modulo-47 algorithm.
-    fn c_checksum_char(&self) -> Option<char> {
-        self.checksum_char(&self.0, 20)
-    }
+    /// Returns the encoded data, including the C and K check characters.
+    pub fn data(&self) -> String {
+        let (c, k) = self.check_chars();
 
-    /// Calculates the K checksum character using a weighted modulo-47 algorithm.
-    fn k_checksum_char(&self, c_checksum: char) -> Option<char> {
-        let mut data: Vec<char> = self.0.clone();
-        data.push(c_checksum);
+        self.0.iter().copied().chain([c, k]).collect()
+    }
 
-        self.checksum_char(&data, 15)
+    /// Returns the human readable text, which is the data without the check characters.
+    pub fn text(&self) -> String {
+        self.0.iter().collect()
     }
 
     fn push_encoding(&self, into: &mut Vec<u8>, from: [u8; 9]) {
@@ -121,10 +113,7 @@ impl Code93 {
 
     fn payload(&self) -> Vec<u8> {
         let mut enc = vec![];
-        let c_checksum = self.c_checksum_char().expect("Cannot compute checksum C");
-        let k_checksum = self
-            .k_checksum_char(c_checksum)
-            .expect("Cannot compute checksum K");
+        let (c_checksum, k_checksum) = self.check_chars();
 
         for &c in &self.0 {
             self.push_encoding(&mut enc, self.char_encoding(c));
@@ -145,6 +134,11 @@ impl Code93 {
 
         helpers::join_slices(&[guard, &self.payload()[..], guard, terminator][..])
     }
+
+    /// Encodes the barcode as a sequence of bar and space widths.
+    pub fn elements(&self) -> Elements {
+        Elements::from(self.encode())
+    }
 }
 
 impl Parse for Code93 {
@@ -178,14 +172,14 @@ mod tests {
     fn invalid_length_code93() {
         let code93 = Code93::new("");
 
-        assert_eq!(code93.err().unwrap(), Error::Length);
+        assert_eq!(code93.err().unwrap(), Error::length(1..=256, 0));
     }
 
     #[test]
     fn invalid_data_code93() {
         let code93 = Code93::new("lowerCASE");
 
-        assert_eq!(code93.err().unwrap(), Error::Character);
+        assert_eq!(code93.err().unwrap(), Error::character(0, 'l'));
     }
 
     #[test]
@@ -207,4 +201,13 @@ mod tests {
         );
         assert_eq!(collapse_vec(code934.encode()), "1010111101010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001000101101110010101010111101");
     }
+
+    #[test]
+    fn code93_text() {
+        let code93 = Code93::new("TEST93").unwrap();
+
+        assert_eq!(code93.check_chars(), ('+', '6'));
+        assert_eq!(code93.data(), "TEST93+6");
+        assert_eq!(code93.text(), "TEST93");
+    }
 }
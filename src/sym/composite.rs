@@ -0,0 +1,606 @@
+//! Encoder for GS1 Composite barcodes.
+//!
+//! A GS1 Composite barcode stacks a 2D component above a linear component. The linear component
+//! carries the primary identification of an item, such as its GTIN. The 2D component carries
+//! supplementary GS1 element strings, such as batch or lot numbers and expiry dates. A separator
+//! pattern joins the two components, and a linkage flag in the linear component tells scanners
+//! to read the 2D component too.
+//!
+//! There are three types of 2D component:
+//!   * CC-A, a variant of MicroPDF417 with its own row address patterns, holding up to 56 digits.
+//!   * CC-B, a MicroPDF417 symbol, holding up to 338 digits.
+//!   * CC-C, a PDF417 symbol, holding up to 2361 digits. It can only be used with GS1-128.
+//!
+//! The smallest type that holds the data is chosen automatically. The linear component can be an
+//! EAN-13, UPC-A, EAN-8, GS1-128 (Code128 beginning with FNC1) or GS1 DataBar Expanded barcode.
+//!
+//! The data of the 2D component is given in the bracketed human-readable form, for example:
+//!
+//! <ul><li>(17)260630(10)AB-123</li></ul>
+//!
+//! Data beginning with a batch or lot number (10), or with a production (11) or expiry (17) date
+//! optionally followed by a batch or lot number, is compressed using a dedicated method.
+
+use crate::error::{Error, Result};
+use crate::sym::code128::Code128;
+use crate::sym::databar::{self, DataBarExpanded};
+use crate::sym::ean13::EAN13;
+use crate::sym::ean8::EAN8;
+use crate::sym::helpers::{self, vec, Vec, FNC1};
+use crate::sym::pdf417::{self, MicroPDF417, MICRO_SIZES, PDF417};
+use crate::sym::upca::UPCA;
+use crate::sym::Matrix;
+use core::cell::Cell;
+
+// CC-A sizes: (columns, rows, error correction codewords, left, centre and right row address
+// patterns of the first row, cluster of the first row). Three column symbols have no left row
+// address pattern, and two column symbols have no centre row address pattern.
+const CC_A_SIZES: [(usize, usize, usize, usize, usize, usize, usize); 17] = [
+    (2, 5, 4, 39, 0, 19, 2),
+    (2, 6, 4, 1, 0, 33, 0),
+    (2, 7, 5, 32, 0, 12, 1),
+    (2, 8, 5, 8, 0, 40, 1),
+    (2, 9, 6, 14, 0, 46, 1),
+    (2, 10, 6, 43, 0, 23, 0),
+    (2, 12, 7, 20, 0, 52, 1),
+    (3, 4, 4, 11, 43, 23, 1),
+    (3, 5, 5, 1, 33, 13, 0),
+    (3, 6, 6, 5, 37, 17, 1),
+    (3, 7, 7, 15, 47, 27, 2),
+    (3, 8, 7, 21, 1, 33, 2),
+    (4, 3, 4, 40, 20, 52, 0),
+    (4, 4, 5, 43, 23, 3, 0),
+    (4, 5, 6, 46, 26, 6, 0),
+    (4, 6, 7, 34, 14, 46, 0),
+    (4, 7, 8, 29, 9, 41, 1),
+];
+
+/// The type of the 2D component of a GS1 Composite barcode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Component {
+    /// CC-A, a variant of MicroPDF417.
+    A,
+    /// CC-B, a MicroPDF417 symbol.
+    B,
+    /// CC-C, a PDF417 symbol.
+    C,
+}
+
+/// The GS1 Composite barcode type.
+#[derive(Debug)]
+pub struct Composite {
+    component: Component,
+    matrix: Matrix,
+    offset: usize,
+    separator: Vec<Vec<u8>>,
+    separator_height: usize,
+    linear: Vec<u8>,
+    linear_offset: usize,
+    linear_height: usize,
+}
+
+impl Composite {
+    /// Creates a new barcode with an EAN-13 linear component.
+    /// Returns Result<Composite, Error> indicating parse success.
+    pub fn ean13<T: AsRef<str>>(linear: &EAN13, data: T) -> Result<Composite> {
+        Composite::ean(linear.encode(), data.as_ref(), 4)
+    }
+
+    /// Creates a new barcode with a UPC-A linear component.
+    /// Returns Result<Composite, Error> indicating parse success.
+    pub fn upca<T: AsRef<str>>(linear: &UPCA, data: T) -> Result<Composite> {
+        Composite::ean(linear.encode(), data.as_ref(), 4)
+    }
+
+    /// Creates a new barcode with an EAN-8 linear component.
+    /// Returns Result<Composite, Error> indicating parse success.
+    pub fn ean8<T: AsRef<str>>(linear: &EAN8, data: T) -> Result<Composite> {
+        Composite::ean(linear.encode(), data.as_ref(), 3)
+    }
+
+    /// Creates a new barcode with a GS1-128 linear component, which must begin with FNC1. The
+    /// linkage flag is added to it.
+    /// Returns Result<Composite, Error> indicating parse success.
+    pub fn gs1_128<T: AsRef<str>>(linear: &Code128, data: T) -> Result<Composite> {
+        let elements = helpers::gs1_elements(data.as_ref())?;
+        // The linkage flag adds one symbol character.
+        let width = linear.encode().len() + 11;
+        let (component, matrix) = component(&elements, 4, Some(width))?;
+        let linear = linear.linked(component == Component::C)?.encode();
+
+        // CC-C components overhang the linear component by seven modules. CC-A and CC-B
+        // components end above the last space of one of the final symbol characters.
+        let (offset, linear_offset) = if component == Component::C {
+            (0, 7)
+        } else {
+            let position = ((width as isize - 2) / 11 - 9) / 2;
+            let stop = if position != 0 { 2 } else { 0 };
+            let shift = width as isize - position * 11 - 1 - matrix.width() as isize - stop;
+
+            (shift.max(0) as usize, (-shift).max(0) as usize)
+        };
+
+        let mut separator = vec![0; linear_offset + linear.len()];
+
+        for (s, &m) in separator[linear_offset..].iter_mut().zip(linear.iter()) {
+            *s = 1 - m;
+        }
+
+        Ok(Composite {
+            component,
+            matrix,
+            offset,
+            separator: vec![separator],
+            separator_height: 1,
+            linear,
+            linear_offset,
+            linear_height: 50,
+        })
+    }
+
+    /// Creates a new barcode with a GS1 DataBar Expanded linear component, given in the same
+    /// bracketed form as the data of the 2D component.
+    /// Returns Result<Composite, Error> indicating parse success.
+    pub fn databar_expanded<L: AsRef<str>, D: AsRef<str>>(linear: L, data: D) -> Result<Composite> {
+        let linear = DataBarExpanded::linked(linear.as_ref())?;
+        let elements = helpers::gs1_elements(data.as_ref())?;
+        let (component, matrix) = component(&elements, 4, None)?;
+
+        Ok(Composite {
+            component,
+            matrix,
+            offset: 2,
+            separator: vec![linear.composite_separator()],
+            separator_height: 1,
+            linear: linear.encode(),
+            linear_offset: 0,
+            linear_height: 50,
+        })
+    }
+
+    // Creates a new barcode with an EAN or UPC linear component. The 2D component has the given
+    // number of columns.
+    fn ean(linear: Vec<u8>, data: &str, columns: usize) -> Result<Composite> {
+        let elements = helpers::gs1_elements(data)?;
+        let (component, matrix) = component(&elements, columns, None)?;
+        let linear_offset = match (columns, component) {
+            (3, Component::A) => 4,
+            (3, _) => 14,
+            _ => 3,
+        };
+        let (left, right) = (linear_offset, linear_offset + linear.len() - 1);
+        let mut separator = vec![vec![0; right + 2]; 3];
+
+        // Short bars extend the outer guard bars up towards the 2D component.
+        for (row, (l, r)) in
+            separator
+                .iter_mut()
+                .zip([(left, right), (left - 1, right + 1), (left, right)])
+        {
+            row[l] = 1;
+            row[r] = 1;
+        }
+
+        Ok(Composite {
+            component,
+            matrix,
+            offset: 0,
+            separator,
+            separator_height: 2,
+            linear,
+            linear_offset,
+            linear_height: 50,
+        })
+    }
+
+    /// Sets the height of the linear component in modules. The default is 50.
+    pub fn linear_height(mut self, height: usize) -> Composite {
+        self.linear_height = height.max(1);
+        self
+    }
+
+    /// Returns the type of the 2D component.
+    pub fn component(&self) -> Component {
+        self.component
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Matrix of modules, with each row of the 2D component two modules tall (three
+    /// for CC-C) above the separator pattern and the linear component.
+    pub fn encode(&self) -> Matrix {
+        let row_height = if self.component == Component::C { 3 } else { 2 };
+        let width = (self.offset + self.matrix.width())
+            .max(self.separator.iter().map(|s| s.len()).max().unwrap_or(0))
+            .max(self.linear_offset + self.linear.len());
+        let height = self.matrix.height() * row_height
+            + self.separator.len() * self.separator_height
+            + self.linear_height;
+        let mut matrix = Matrix::new(width, height);
+        let mut rows: Vec<(usize, &[u8], usize)> = vec![];
+
+        for row in self.matrix.rows() {
+            rows.push((self.offset, row, row_height));
+        }
+
+        for row in self.separator.iter() {
+            rows.push((0, row, self.separator_height));
+        }
+
+        rows.push((self.linear_offset, &self.linear, self.linear_height));
+
+        let mut y = 0;
+
+        for (offset, row, repeat) in rows {
+            for _ in 0..repeat {
+                for (x, &module) in row.iter().enumerate() {
+                    matrix.set(offset + x, y, module);
+                }
+
+                y += 1;
+            }
+        }
+
+        matrix
+    }
+}
+
+// Encodes the 2D component in the smallest type that holds the data: CC-A, then CC-B, then CC-C
+// if the linear component is a GS1-128 barcode of the given width.
+fn component(
+    elements: &[(&str, &str)],
+    columns: usize,
+    gs1_128: Option<usize>,
+) -> Result<(Component, Matrix)> {
+    if let Some(bits) = binary_data(elements, |len| cc_a_capacity(columns, len)) {
+        return Ok((Component::A, cc_a(&bits, columns)));
+    }
+
+    if let Some(bits) = binary_data(elements, |len| cc_b_capacity(columns, len)) {
+        let codewords = byte_codewords(&bits);
+        let micro = MicroPDF417::from_codewords(codewords, columns).expect("CC-B data fits");
+
+        return Ok((Component::B, micro.encode().with_row_height(1)));
+    }
+
+    if let Some(width) = gs1_128 {
+        let size = Cell::new(None);
+        let bits = binary_data(elements, |len| {
+            size.set(cc_c_size(len, width));
+            size.get().map(|(bits, ..)| bits)
+        });
+
+        if let (Some(bits), Some((_, columns, level))) = (bits, size.get()) {
+            let codewords = byte_codewords(&bits);
+            let pdf = PDF417::from_codewords(codewords, level, columns).expect("CC-C data fits");
+
+            return Ok((Component::C, pdf.encode().with_row_height(1)));
+        }
+    }
+
+    // The length is counted in bits of the binary data string, against the largest component.
+    let needed = binary_data(elements, Some).map_or(0, |bits| bits.len());
+    let largest = |capacity: &dyn Fn(usize) -> Option<usize>| {
+        (0..=8 * 1200)
+            .rev()
+            .step_by(8)
+            .find_map(capacity)
+            .unwrap_or(0)
+    };
+    let capacity = match gs1_128 {
+        Some(width) => largest(&|len| cc_c_size(len, width).map(|(bits, ..)| bits)),
+        None => largest(&|len| cc_b_capacity(columns, len)),
+    };
+
+    Err(Error::length(0..=capacity, needed))
+}
+
+// Builds the binary data string, padded to the length given by `capacity`. Returns None if the
+// data is too long.
+fn binary_data<F: Fn(usize) -> Option<usize>>(
+    elements: &[(&str, &str)],
+    capacity: F,
+) -> Option<Vec<u8>> {
+    let mut bits = vec![];
+    let mut general = vec![];
+    let mut rest = elements;
+
+    // Encodation method "10" compresses a leading date and lot number. Method "0" leaves all
+    // of the data to the general-purpose data field.
+    let lot = match elements[0] {
+        ("10", _) => {
+            databar::push_bits(&mut bits, 0b1011, 4);
+            true
+        }
+        (ai @ ("11" | "17"), date) if databar::date_value(date).is_some() => {
+            databar::push_bits(&mut bits, 0b10, 2);
+            databar::push_bits(&mut bits, databar::date_value(date).unwrap(), 16);
+            bits.push((ai == "17") as u8);
+            rest = &elements[1..];
+
+            match rest.first() {
+                Some(("10", _)) => true,
+                Some(_) => {
+                    general.push(FNC1);
+                    false
+                }
+                None => {
+                    // An alphanumeric latch and FNC1 end the date element string.
+                    databar::push_bits(&mut bits, 0b000001111, 9);
+                    false
+                }
+            }
+        }
+        _ => {
+            bits.push(0);
+            false
+        }
+    };
+
+    let element_string = helpers::gs1_element_string(rest);
+
+    // The AI of a compressed lot number is implied.
+    general.extend(&element_string[if lot { 2 } else { 0 }..]);
+
+    // The capacity is last checked for the length of the data before padding.
+    let fits = Cell::new(false);
+
+    databar::encode_general(&mut bits, &general, |len| {
+        fits.set(capacity(len).is_some());
+        capacity(len).unwrap_or(len)
+    });
+
+    fits.get().then_some(bits)
+}
+
+// Returns the most bits that the given number of codewords can hold in base 928: seven
+// codewords for each 69 bits, and one codeword more than a tenth of the remaining bits.
+fn base928_bits(codewords: usize) -> usize {
+    match codewords % 7 {
+        0 => codewords / 7 * 69,
+        n => codewords / 7 * 69 + n * 10 - 1,
+    }
+}
+
+// Returns the length of the binary data string of the smallest CC-A component that holds the
+// given number of bits.
+fn cc_a_capacity(columns: usize, len: usize) -> Option<usize> {
+    CC_A_SIZES
+        .iter()
+        .filter(|s| s.0 == columns)
+        .map(|&(c, r, ecc_len, ..)| base928_bits(c * r - ecc_len))
+        .find(|&bits| bits >= len)
+}
+
+// Returns the length of the binary data string of the smallest CC-B component that holds the
+// given number of bits. Two codewords are taken by the CC-B identifier and the byte latch, and
+// each five further codewords hold six bytes.
+fn cc_b_capacity(columns: usize, len: usize) -> Option<usize> {
+    MICRO_SIZES
+        .iter()
+        .filter(|s| s.0 == columns)
+        .map(|&(c, r, ecc_len, ..)| {
+            let codewords = c * r - ecc_len - 2;
+            8 * (codewords / 5 * 6 + codewords % 5)
+        })
+        .find(|&bits| bits >= len)
+}
+
+// Returns the (length of the binary data string, columns, security level) of the smallest CC-C
+// component that holds the given number of bits. The component is as wide as the GS1-128 linear
+// component of the given width allows.
+fn cc_c_size(len: usize, linear_width: usize) -> Option<(usize, usize, u8)> {
+    let bytes = len.div_ceil(8);
+    let codewords = bytes / 6 * 5 + bytes % 6;
+    let level = match codewords {
+        0..=40 => 2,
+        41..=160 => 3,
+        161..=320 => 4,
+        321..=833 => 5,
+        834..=865 => 4,
+        _ => return None,
+    };
+    let ecc_len = 2 << level;
+    // The length descriptor, CC-C identifier and byte latch take three more codewords.
+    let total = codewords + ecc_len + 3;
+    let mut columns = (linear_width.saturating_sub(52) / 17).clamp(1, 30);
+
+    while total.div_ceil(columns) > 30 && columns < 30 {
+        columns += 1;
+    }
+
+    if total.div_ceil(columns) > 30 {
+        return None;
+    }
+
+    let rows = total.div_ceil(columns).max(3);
+    let data_len = columns * rows - ecc_len - 3;
+
+    Some((8 * (data_len / 5 * 6 + data_len % 5), columns, level))
+}
+
+// Builds a CC-A component, converting the binary data string to base 928.
+fn cc_a(bits: &[u8], columns: usize) -> Matrix {
+    let mut codewords = vec![];
+
+    for group in bits.chunks(69) {
+        let mut value = group.iter().fold(0u128, |acc, &b| (acc << 1) | b as u128);
+        let mut values = vec![0; group.len() / 10 + 1];
+
+        for cw in values.iter_mut().rev() {
+            *cw = (value % 928) as u16;
+            value /= 928;
+        }
+
+        codewords.extend(values);
+    }
+
+    let &(_, _, ecc_len, left, centre, right, cluster) = CC_A_SIZES
+        .iter()
+        .find(|&&(c, r, ecc_len, ..)| c == columns && c * r - ecc_len == codewords.len())
+        .expect("CC-A data fits");
+
+    codewords.extend(pdf417::error_correction(&codewords, ecc_len));
+
+    pdf417::micro_matrix(
+        &codewords,
+        columns,
+        ((columns != 3).then_some(left), centre, right),
+        cluster,
+    )
+}
+
+// Converts the binary data string of a CC-B or CC-C component to codewords: the CC-B/CC-C
+// identifier followed by the bytes in Byte Compaction.
+fn byte_codewords(bits: &[u8]) -> Vec<u16> {
+    let bytes: Vec<u8> = bits
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0, |acc, &b| (acc << 1) | b))
+        .collect();
+    let mut codewords = vec![920];
+
+    pdf417::encode_bytes(&bytes, &mut codewords);
+    codewords
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sym::code128::Code128;
+    use crate::sym::composite::*;
+    use crate::sym::ean13::EAN13;
+    use crate::sym::ean8::EAN8;
+    use crate::sym::upca::UPCA;
+    #[cfg(not(feature = "std"))]
+    use alloc::string::{String, ToString};
+
+    fn collapse_rows(matrix: &Matrix) -> Vec<String> {
+        let mut rows: Vec<String> = vec![];
+
+        for row in matrix.rows() {
+            let row: String = row.iter().map(|&d| char::from(b'0' + d)).collect();
+
+            if rows.last() != Some(&row) {
+                rows.push(row);
+            }
+        }
+
+        rows
+    }
+
+    #[test]
+    fn new_composite() {
+        let ean13 = EAN13::new("331234567890").unwrap();
+        let upca = UPCA::new("01234567890").unwrap();
+        let ean8 = EAN8::new("1234567").unwrap();
+        let gs1_128 = Code128::new("ĆŹ0112345678901231").unwrap();
+        let databar = String::from("(01)98898765432106");
+
+        assert!(Composite::ean13(&ean13, "(10)ABC123").is_ok());
+        assert!(Composite::upca(&upca, "(17)260630(10)AB-123").is_ok());
+        assert!(Composite::ean8(&ean8, "(21)12345").is_ok());
+        assert!(Composite::gs1_128(&gs1_128, "(3103)001234").is_ok());
+        assert!(Composite::databar_expanded("(01)98898765432106", "(11)260101").is_ok());
+        assert!(Composite::databar_expanded(databar, "(11)260101").is_ok());
+    }
+
+    #[test]
+    fn invalid_data_composite() {
+        let ean13 = EAN13::new("331234567890").unwrap();
+        let code128 = Code128::new("Ɓ0112345678901231").unwrap();
+
+        assert_eq!(
+            Composite::ean13(&ean13, "10ABC123").err().unwrap(),
+            Error::character(0, '1')
+        );
+        assert_eq!(
+            Composite::gs1_128(&code128, "(10)ABC123").err().unwrap(),
+            Error::character(1, '0')
+        );
+    }
+
+    #[test]
+    fn invalid_len_composite() {
+        let ean13 = EAN13::new("331234567890").unwrap();
+        let data = "(91)".to_string() + &"ABCDEFGHIJ".repeat(9);
+
+        assert_eq!(
+            Composite::ean13(&ean13, "").err().unwrap(),
+            Error::length(1..=usize::MAX, 0)
+        );
+        assert_eq!(
+            Composite::ean13(&ean13, data.repeat(3)).err().unwrap(),
+            Error::length(0..=1184, 1664)
+        );
+    }
+
+    #[test]
+    fn composite_component() {
+        let ean13 = EAN13::new("331234567890").unwrap();
+        let gs1_128 = Code128::new("ĆŹ0112345678901231").unwrap();
+        let data = "(91)".to_string() + &"ABCDEFGHIJ".repeat(9);
+
+        let cc_a = Composite::ean13(&ean13, "(10)ABC123").unwrap();
+        let cc_b = Composite::ean13(&ean13, &data).unwrap();
+        let cc_c = Composite::gs1_128(&gs1_128, data.repeat(3)).unwrap();
+
+        assert_eq!(cc_a.component(), Component::A);
+        assert_eq!(cc_b.component(), Component::B);
+        assert_eq!(cc_c.component(), Component::C);
+    }
+
+    #[test]
+    fn composite_encode_ean13() {
+        let ean13 = EAN13::new("331234567890").unwrap();
+        let composite = Composite::ean13(&ean13, "(10)ABC123")
+            .unwrap()
+            .linear_height(1);
+        let matrix = composite.encode();
+
+        assert_eq!(matrix.width(), 99);
+        assert_eq!(matrix.height(), 3 * 2 + 3 * 2 + 1);
+        assert_eq!(
+            collapse_rows(&matrix),
+            vec![
+                "110110111010000100100001000101101111000111001001110100111110111101001101010011001100000011011000101",
+                "110110110010000100011110100111110101101110001001100100111101100110010001110101100100000011001000101",
+                "110110100010011100010000110110001111101000101001100110101100001011100001010001100111110011101000101",
+                "000100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010",
+                "001000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001",
+                "000100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010",
+                "000101011110100110010011011010000100111010110001010101010000100010010010001110100111001010000101010",
+            ]
+        );
+    }
+
+    #[test]
+    fn composite_encode_gs1_128() {
+        let gs1_128 = Code128::new("ĆŹ0112345678901231").unwrap();
+        let composite = Composite::gs1_128(&gs1_128, "(17)260630(10)AB-123").unwrap();
+        let rows = collapse_rows(&composite.linear_height(1).encode());
+
+        assert_eq!(rows.len(), 5);
+        assert_eq!(
+            rows[0],
+            "0000000000000000000001101101110100110011110011101110001110001010010011101001000010011101110010000100110011000110110001010000000000000000000000000"
+        );
+        assert_eq!(
+            rows[4],
+            "1101001110011110101110110011011001011001110010001011000111000101101100001010011011110110101100111001101100011011101011110111010110001100011101011"
+        );
+    }
+
+    #[test]
+    fn composite_encode_databar_expanded() {
+        let composite = Composite::databar_expanded("(01)98898765432106", "(10)ABC123").unwrap();
+        let rows = collapse_rows(&composite.linear_height(1).encode());
+
+        assert_eq!(rows.len(), 5);
+        assert_eq!(
+            rows[0],
+            "00110110111010000100100001000101101111000111001001110100111110111101001101010011001100000011011000101000000000000000000000000000000000"
+        );
+        assert_eq!(
+            rows[3],
+            "00001101001111110101000000001010010010001100010001100101111110011101010000101010000101100011111101101011000001000110010100000010100000"
+        );
+    }
+}
@@ -0,0 +1,926 @@
+//! Encoder for GS1 DataBar Expanded and DataBar Expanded Stacked barcodes.
+//!
+//! GS1 DataBar Expanded (formerly RSS Expanded) is a variable-length linear symbology that
+//! encodes GS1 Application Identifier (AI) element strings. It is common on variable-measure
+//! fresh foods and coupons where the weight, price or dates must travel with the GTIN.
+//!
+//! Data is given in the bracketed human-readable form, for example:
+//!
+//! <ul><li>(01)98898765432106(3103)001750</li></ul>
+//!
+//! The most compact encodation method available is chosen automatically. Element strings
+//! containing a GTIN with indicator digit 9 followed by a weight (310x/320x), an optional date
+//! (11, 13, 15 or 17) or a price (392x/393x) are compressed using the dedicated methods.
+//!
+//! The stacked variant splits the symbol into several rows of a configurable number of
+//! segments (symbol characters) per row, which is useful where horizontal space is limited.
+
+use crate::checksum;
+use crate::error::{Error, Result};
+use crate::sym::{helpers, Elements};
+use helpers::{vec, Vec, FNC1};
+
+// The maximum number of bits in the binary data string (21 data characters).
+const MAX_BITS: usize = 252;
+
+// Finder patterns A to F. The "2" variant of each is the mirror image.
+const FINDERS: [[u8; 5]; 6] = [
+    [1, 8, 4, 1, 1],
+    [3, 6, 4, 1, 1],
+    [3, 4, 6, 1, 1],
+    [3, 2, 8, 1, 1],
+    [2, 6, 5, 1, 1],
+    [2, 2, 9, 1, 1],
+];
+
+// Finder pattern sequences, indexed by the number of character pairs (2 to 11).
+// 1 = A1, 2 = A2, 3 = B1, 4 = B2, ..., 12 = F2.
+const SEQUENCES: [&[u8]; 10] = [
+    &[1, 2],
+    &[1, 4, 3],
+    &[1, 6, 3, 8],
+    &[1, 10, 3, 8, 5],
+    &[1, 10, 3, 8, 7, 12],
+    &[1, 10, 3, 8, 9, 12, 11],
+    &[1, 2, 3, 4, 5, 6, 7, 8],
+    &[1, 2, 3, 4, 5, 6, 7, 10, 9],
+    &[1, 2, 3, 4, 5, 6, 7, 10, 11, 12],
+    &[1, 2, 3, 4, 5, 8, 7, 10, 9, 12, 11],
+];
+
+// Character groups: (first value, odd modules, even modules, widest odd, widest even, even
+// combinations).
+const GROUPS: [(u16, i32, i32, i32, i32, u16); 5] = [
+    (0, 12, 5, 7, 2, 4),
+    (348, 10, 7, 5, 4, 20),
+    (1388, 8, 9, 4, 5, 52),
+    (2948, 6, 11, 3, 6, 104),
+    (3988, 4, 13, 1, 8, 204),
+];
+
+/// The GS1 DataBar Expanded barcode type.
+#[derive(Debug)]
+pub struct DataBarExpanded {
+    chars: Vec<u16>,
+    segments: usize,
+}
+
+/// The GS1 DataBar Expanded Stacked barcode type.
+pub type DataBarExpandedStacked = DataBarExpanded;
+
+// The compaction method used for the binary data string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Method {
+    General,
+    Gtin,
+    Weight3103,
+    Weight320x,
+    WeightDate(u32),
+    Price392x,
+    Price393x,
+}
+
+impl DataBarExpanded {
+    /// Creates a new barcode.
+    /// Returns Result<DataBarExpanded, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<DataBarExpanded> {
+        DataBarExpanded::stacked(data, 22)
+    }
+
+    /// Creates a new stacked barcode with the given number of segments per row.
+    /// The number of segments must be even and between 2 and 22.
+    /// Returns Result<DataBarExpanded, Error> indicating parse success.
+    pub fn stacked<T: AsRef<str>>(data: T, segments: usize) -> Result<DataBarExpanded> {
+        DataBarExpanded::init(data.as_ref(), segments, false)
+    }
+
+    /// Returns the number of segments (symbol characters) per row of the stacked variant.
+    pub fn segments(&self) -> usize {
+        self.segments
+    }
+
+    // Creates a new barcode with the linkage flag set, for use as the linear component of a
+    // GS1 Composite barcode.
+    pub(crate) fn linked(data: &str) -> Result<DataBarExpanded> {
+        DataBarExpanded::init(data, 22, true)
+    }
+
+    fn init(data: &str, segments: usize, linked: bool) -> Result<DataBarExpanded> {
+        if !(2..=22).contains(&segments) || segments % 2 == 1 {
+            return Err(Error::option("segments", 2..=22, segments));
+        }
+
+        let elements = helpers::gs1_elements(data)?;
+        let bits = binary_data(data, &elements, segments, linked)?;
+        let chars = bits
+            .chunks(12)
+            .map(|c| c.iter().fold(0, |acc, &b| (acc << 1) | b as u16))
+            .collect();
+
+        Ok(DataBarExpanded { chars, segments })
+    }
+
+    fn symbol_chars(&self) -> Vec<u16> {
+        let mut chars = vec![self.checksum_value()];
+        chars.extend(self.chars.iter().cloned());
+        chars
+    }
+
+    fn sequence(&self) -> &'static [u8] {
+        SEQUENCES[(self.chars.len() + 2) / 2 - 2]
+    }
+
+    /// Calculates the check character value using the modulo-211 weighting algorithm.
+    fn checksum_value(&self) -> u16 {
+        let sequence = self.sequence();
+        let mut sum = 0;
+
+        for (i, &c) in self.chars.iter().enumerate() {
+            let position = i + 1;
+            let finder = sequence[position / 2] as u32;
+            let row = 2 * (finder - 1) + (position % 2) as u32 - 1;
+            let mut weight = (0..8 * row).fold(1, |w, _| (w * 3) % 211);
+
+            for width in char_widths(c).iter() {
+                sum += weight * *width as u32;
+                weight = (weight * 3) % 211;
+            }
+        }
+
+        (211 * (self.chars.len() as u32 + 1 - 4) + sum % 211) as u16
+    }
+
+    // Returns the element widths of each block (a pair of characters and their finder).
+    fn blocks(&self) -> Vec<Vec<u8>> {
+        let chars = self.symbol_chars();
+        let sequence = self.sequence();
+
+        chars
+            .chunks(2)
+            .zip(sequence.iter())
+            .map(|(pair, &finder)| {
+                let mut block = char_widths(pair[0]).to_vec();
+                let mut pattern = FINDERS[(finder as usize - 1) / 2];
+
+                if finder % 2 == 0 {
+                    pattern.reverse();
+                }
+
+                block.extend(pattern.iter().cloned());
+
+                if let Some(&c) = pair.get(1) {
+                    block.extend(char_widths(c).iter().rev().cloned());
+                }
+
+                block
+            })
+            .collect()
+    }
+
+    /// Encodes the barcode as a single row.
+    /// Returns a Vec<u8> of binary digits.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut elements = vec![1, 1];
+
+        for block in self.blocks() {
+            elements.extend(block);
+        }
+
+        elements.extend([1, 1].iter());
+        expand(&elements, 0)
+    }
+
+    /// Encodes the barcode as a sequence of bar and space widths.
+    pub fn elements(&self) -> Elements {
+        Elements::from(self.encode())
+    }
+
+    // Returns the separator pattern between the barcode and the 2D component of a GS1 Composite
+    // barcode.
+    pub(crate) fn composite_separator(&self) -> Vec<u8> {
+        separator(&self.encode(), true, self.blocks().len(), 0)
+    }
+
+    /// Encodes the barcode as a stacked symbol using the configured number of segments per row.
+    /// Returns a Vec<Vec<u8>> of rows of binary digits. Rows are separated by three separator
+    /// rows, each of which should be printed one module high.
+    pub fn encode_stacked(&self) -> Vec<Vec<u8>> {
+        let blocks = self.blocks();
+        let columns = self.segments / 2;
+        let total_rows = blocks.len().div_ceil(columns);
+        let mut symbol: Vec<Vec<u8>> = vec![];
+        let mut previous: Option<(Vec<u8>, bool, usize)> = None;
+
+        for (r, row_blocks) in blocks.chunks(columns).enumerate() {
+            let even_row = r % 2 == 1;
+            let partial = row_blocks.len() < columns;
+            let last_row = r == total_rows - 1;
+            let left_to_right = columns % 2 == 1 || !even_row || (last_row && partial);
+            let special = last_row && partial && even_row && columns.is_multiple_of(2);
+            let mut elements = vec![if special { 2 } else { 1 }, 1];
+
+            if left_to_right {
+                for block in row_blocks {
+                    elements.extend(block.iter().cloned());
+                }
+            } else {
+                for block in row_blocks.iter().rev() {
+                    elements.extend(block.iter().rev().cloned());
+                }
+            }
+
+            elements.extend([1, 1].iter());
+
+            let start = if even_row && !special { 1 } else { 0 };
+            let row = expand(&elements, start);
+            let offset = if special { 1 } else { 0 };
+
+            if let Some((above, above_ltr, above_blocks)) = previous.take() {
+                let width = 49 * columns + 4;
+                let mut middle = vec![0; width];
+
+                for m in (5..49 * columns).step_by(2) {
+                    middle[m] = 1;
+                }
+
+                symbol.push(separator(&above, above_ltr, above_blocks, 0));
+                symbol.push(middle);
+                symbol.push(separator(&row, left_to_right, row_blocks.len(), offset));
+            }
+
+            symbol.push(row.clone());
+            previous = Some((row, left_to_right, row_blocks.len()));
+        }
+
+        let width = symbol.iter().map(|r| r.len()).max().unwrap_or(0);
+
+        for row in &mut symbol {
+            row.resize(width, 0);
+        }
+
+        symbol
+    }
+}
+
+// Builds the separator pattern for the given row: the complement of the row, with the modules
+// over the finder patterns alternating where the finder has wide spaces.
+fn separator(row: &[u8], left_to_right: bool, blocks: usize, offset: usize) -> Vec<u8> {
+    let mut sep = vec![0; row.len()];
+
+    for j in (4 + offset)..(row.len() - 4) {
+        sep[j] = 1 - row[j];
+    }
+
+    for b in 0..blocks {
+        let k = 49 * b + 19 + offset;
+
+        if left_to_right {
+            for i in k..(k + 15).min(row.len() - 1) {
+                if row[i - 1] == 0 && row[i] == 0 && sep[i - 1] == 1 {
+                    sep[i] = 0;
+                }
+            }
+        } else {
+            for i in (k..(k + 15).min(row.len() - 1)).rev() {
+                if row[i + 1] == 0 && row[i] == 0 && sep[i + 1] == 1 {
+                    sep[i] = 0;
+                }
+            }
+        }
+    }
+
+    sep
+}
+
+// Expands a sequence of element widths into modules, starting with the given colour.
+fn expand(elements: &[u8], start: u8) -> Vec<u8> {
+    let mut modules = vec![];
+
+    for (i, &w) in elements.iter().enumerate() {
+        let colour = (start + i as u8) % 2;
+        modules.extend((0..w).map(|_| colour));
+    }
+
+    modules
+}
+
+// Returns the 8 element widths (odd and even elements interleaved) for a character value.
+fn char_widths(value: u16) -> [u8; 8] {
+    let &(start, odd_modules, even_modules, odd_widest, even_widest, even_total) = GROUPS
+        .iter()
+        .rev()
+        .find(|g| value >= g.0)
+        .expect("Unknown character value");
+    let v = value - start;
+    let odd = rss_widths((v / even_total) as i32, odd_modules, odd_widest, false);
+    let even = rss_widths((v % even_total) as i32, even_modules, even_widest, true);
+    let mut widths = [0; 8];
+
+    for i in 0..4 {
+        widths[i * 2] = odd[i];
+        widths[i * 2 + 1] = even[i];
+    }
+
+    widths
+}
+
+// Calculates the number of combinations of r selected from n.
+fn combins(n: i32, r: i32) -> i32 {
+    let (min_denom, max_denom) = if n - r > r { (r, n - r) } else { (n - r, r) };
+    let mut val = 1;
+    let mut j = 1;
+    let mut i = n;
+
+    while i > max_denom {
+        val *= i;
+
+        if j <= min_denom {
+            val /= j;
+            j += 1;
+        }
+
+        i -= 1;
+    }
+
+    while j <= min_denom {
+        val /= j;
+        j += 1;
+    }
+
+    val
+}
+
+// Calculates the widths of the four elements that represent the given value when spread over n
+// modules (ISO/IEC 24724 Annex B). Unless no_narrow is set, at least one element must be a
+// single module wide.
+fn rss_widths(mut val: i32, mut n: i32, max_width: i32, no_narrow: bool) -> [u8; 4] {
+    const ELEMENTS: i32 = 4;
+    let mut widths = [0; 4];
+    let mut narrow_mask = 0;
+
+    for bar in 0..ELEMENTS - 1 {
+        let mut elm_width = 1;
+        let mut sub_val;
+        narrow_mask |= 1 << bar;
+
+        loop {
+            sub_val = combins(n - elm_width - 1, ELEMENTS - bar - 2);
+
+            if !no_narrow
+                && narrow_mask == 0
+                && n - elm_width - (ELEMENTS - bar - 1) >= ELEMENTS - bar - 1
+            {
+                sub_val -= combins(n - elm_width - (ELEMENTS - bar), ELEMENTS - bar - 2);
+            }
+
+            if ELEMENTS - bar - 1 > 1 {
+                let mut less_val = 0;
+                let mut mxw_element = n - elm_width - (ELEMENTS - bar - 2);
+
+                while mxw_element > max_width {
+                    less_val += combins(n - elm_width - mxw_element - 1, ELEMENTS - bar - 3);
+                    mxw_element -= 1;
+                }
+
+                sub_val -= less_val * (ELEMENTS - 1 - bar);
+            } else if n - elm_width > max_width {
+                sub_val -= 1;
+            }
+
+            val -= sub_val;
+
+            if val < 0 {
+                break;
+            }
+
+            elm_width += 1;
+            narrow_mask &= !(1 << bar);
+        }
+
+        val += sub_val;
+        n -= elm_width;
+        widths[bar as usize] = elm_width as u8;
+    }
+
+    widths[3] = n as u8;
+    widths
+}
+
+fn digits(data: &str) -> Option<Vec<u8>> {
+    data.chars()
+        .map(|c| c.to_digit(10).map(|d| d as u8))
+        .collect()
+}
+
+fn number(data: &str) -> Option<u32> {
+    digits(data).map(|d| d.iter().fold(0, |acc, &n| acc * 10 + n as u32))
+}
+
+// Encodes a YYMMDD date into the 16-bit value used by the compressed date methods.
+pub(crate) fn date_value(data: &str) -> Option<u32> {
+    let d = digits(data).filter(|d| d.len() == 6)?;
+    let (yy, mm, dd) = (
+        d[0] as u32 * 10 + d[1] as u32,
+        d[2] as u32 * 10 + d[3] as u32,
+        d[4] as u32 * 10 + d[5] as u32,
+    );
+
+    match (mm, dd) {
+        (1..=12, 0..=31) => Some(yy * 384 + (mm - 1) * 32 + dd),
+        _ => None,
+    }
+}
+
+// Chooses the most compact encodation method for the given element strings.
+fn select_method(elements: &[(&str, &str)]) -> Method {
+    let (ai, gtin) = elements[0];
+
+    if ai != "01" {
+        return Method::General;
+    }
+
+    if !gtin.starts_with('9') {
+        return Method::Gtin;
+    }
+
+    let weight_ai = |ai: &str| ai.len() == 4 && (ai.starts_with("310") || ai.starts_with("320"));
+    let date_ai = |ai: &str| ["11", "13", "15", "17"].contains(&ai);
+    let weight = |w: &str| number(w).filter(|_| w.len() == 6);
+
+    match elements[1..] {
+        [("3103", w)] if weight(w).is_some_and(|n| n <= 32767) => Method::Weight3103,
+        [("3202", w)] if weight(w).is_some_and(|n| n <= 9999) => Method::Weight320x,
+        [("3203", w)] if weight(w).is_some_and(|n| n <= 22767) => Method::Weight320x,
+        [(ai, w)] if weight_ai(ai) && weight(w).is_some_and(|n| n <= 99999) => {
+            Method::WeightDate(if ai.starts_with("310") { 56 } else { 57 })
+        }
+        [(ai, w), (date, d)]
+            if weight_ai(ai)
+                && weight(w).is_some_and(|n| n <= 99999)
+                && date_ai(date)
+                && date_value(d).is_some() =>
+        {
+            let offset = if ai.starts_with("310") { 0 } else { 1 };
+            let code = match date {
+                "11" => 56,
+                "13" => 58,
+                "15" => 60,
+                _ => 62,
+            };
+
+            Method::WeightDate(code + offset)
+        }
+        [(ai, price), ..] if ai.len() == 4 && ai.starts_with("392") && ai <= "3923" => {
+            if digits(price).is_some() {
+                Method::Price392x
+            } else {
+                Method::Gtin
+            }
+        }
+        [(ai, price), ..] if ai.len() == 4 && ai.starts_with("393") && ai <= "3933" => {
+            match digits(price) {
+                Some(d) if d.len() > 3 => Method::Price393x,
+                _ => Method::Gtin,
+            }
+        }
+        _ => Method::Gtin,
+    }
+}
+
+pub(crate) fn push_bits(bits: &mut Vec<u8>, value: u32, count: usize) {
+    for i in (0..count).rev() {
+        bits.push(((value >> i) & 1) as u8);
+    }
+}
+
+// Appends the 12 GTIN digits following the indicator digit in groups of three.
+fn push_gtin(bits: &mut Vec<u8>, gtin: &[u8]) {
+    for group in gtin[1..13].chunks(3) {
+        let value = group.iter().fold(0, |acc, &d| acc * 10 + d as u32);
+        push_bits(bits, value, 10);
+    }
+}
+
+// Returns the total number of bits of the binary data string once padded.
+fn padded_len(len: usize, segments: usize) -> usize {
+    let mut padded = len.max(36).div_ceil(12) * 12;
+    let symbol_chars = padded / 12 + 1;
+
+    // The last row of a stacked symbol must contain at least two segments.
+    if segments < symbol_chars && symbol_chars % segments == 1 {
+        padded += 12;
+    }
+
+    padded
+}
+
+// Builds the full (padded) binary data string for the element strings of the data.
+fn binary_data(
+    data: &str,
+    elements: &[(&str, &str)],
+    segments: usize,
+    linked: bool,
+) -> Result<Vec<u8>> {
+    let method = select_method(elements);
+    let mut bits = vec![linked as u8]; // Linkage flag, set if there is a 2D composite component.
+    let mut general: Vec<char> = vec![];
+    let mut rest = elements;
+
+    if method != Method::General {
+        let value = elements[0].1;
+        let index = helpers::char_index(data, value);
+
+        if let Some(e) = Error::find_character(value, index, |c| c.is_ascii_digit()) {
+            return Err(e);
+        }
+
+        let gtin = digits(value).expect("GTIN is digits");
+
+        if gtin.len() != 14 {
+            return Err(Error::length(14..=14, gtin.len()));
+        }
+
+        let check_digit = checksum::modulo_10_digit(&gtin[..13], false);
+
+        if check_digit != gtin[13] {
+            return Err(Error::checksum(check_digit, gtin[13]));
+        }
+
+        match method {
+            Method::Gtin => {
+                push_bits(&mut bits, 0b100, 3);
+                push_bits(&mut bits, gtin[0] as u32, 4);
+            }
+            Method::Weight3103 => push_bits(&mut bits, 0b0100, 4),
+            Method::Weight320x => push_bits(&mut bits, 0b0101, 4),
+            Method::WeightDate(code) => push_bits(&mut bits, code, 7),
+            Method::Price392x => push_bits(&mut bits, 0b0110000, 7),
+            _ => push_bits(&mut bits, 0b0110100, 7),
+        }
+
+        push_gtin(&mut bits, &gtin);
+        rest = &elements[1..];
+    } else {
+        push_bits(&mut bits, 0b0000, 4);
+    }
+
+    match method {
+        Method::Weight3103 => {
+            push_bits(&mut bits, number(rest[0].1).unwrap(), 15);
+            rest = &[];
+        }
+        Method::Weight320x => {
+            let weight = number(rest[0].1).unwrap();
+            let value = if rest[0].0 == "3203" { weight + 10000 } else { weight };
+            push_bits(&mut bits, value, 15);
+            rest = &[];
+        }
+        Method::WeightDate(_) => {
+            let decimal = rest[0].0[3..].parse::<u32>().unwrap();
+            push_bits(&mut bits, decimal * 100000 + number(rest[0].1).unwrap(), 20);
+            push_bits(&mut bits, rest.get(1).map_or(38400, |d| date_value(d.1).unwrap()), 16);
+            rest = &[];
+        }
+        Method::Price392x | Method::Price393x => {
+            let (ai, price) = rest[0];
+            push_bits(&mut bits, ai[3..].parse::<u32>().unwrap(), 2);
+
+            let price = if method == Method::Price393x {
+                push_bits(&mut bits, number(&price[..3]).unwrap(), 10);
+                &price[3..]
+            } else {
+                price
+            };
+
+            general.extend(price.chars());
+            rest = &rest[1..];
+
+            if !rest.is_empty() {
+                general.push(FNC1);
+            }
+        }
+        _ => (),
+    }
+
+    general.extend(helpers::gs1_element_string(rest));
+
+    let fixed = matches!(
+        method,
+        Method::Weight3103 | Method::Weight320x | Method::WeightDate(_)
+    );
+
+    if !fixed {
+        encode_general(&mut bits, &general, |len| padded_len(len, segments));
+    }
+
+    // The length is counted in bits of the binary data string.
+    let padded = padded_len(bits.len(), segments);
+
+    if padded > MAX_BITS {
+        return Err(Error::length(0..=MAX_BITS, padded.max(bits.len())));
+    }
+
+    // Variable length symbol field: odd/even number of symbol characters and whether there
+    // are more than 14 of them.
+    let symbol_chars = padded / 12 + 1;
+    let vls = match method {
+        Method::General => Some(3),
+        Method::Gtin => Some(2),
+        Method::Price392x | Method::Price393x => Some(6),
+        _ => None,
+    };
+
+    if let Some(p) = vls {
+        bits[p] = (symbol_chars % 2) as u8;
+        bits[p + 1] = (symbol_chars > 14) as u8;
+    }
+
+    bits.resize(padded, 0);
+    Ok(bits)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Mode {
+    Numeric,
+    Alpha,
+    Iso,
+}
+
+fn numeric_value(c: char) -> Option<u32> {
+    match c {
+        FNC1 => Some(10),
+        _ => c.to_digit(10),
+    }
+}
+
+// Returns the (value, bit length) of a character in alphanumeric mode.
+fn alpha_value(c: char) -> Option<(u32, usize)> {
+    match c {
+        '0'..='9' => Some((c as u32 - '0' as u32 + 5, 5)),
+        FNC1 => Some((15, 5)),
+        'A'..='Z' => Some((c as u32 - 'A' as u32 + 32, 6)),
+        '*' => Some((58, 6)),
+        ',' => Some((59, 6)),
+        '-' => Some((60, 6)),
+        '.' => Some((61, 6)),
+        '/' => Some((62, 6)),
+        _ => None,
+    }
+}
+
+// Returns the (value, bit length) of a character in ISO/IEC 646 mode.
+fn iso_value(c: char) -> Option<(u32, usize)> {
+    const PUNCTUATION: &str = "!\"%&'()*+,-./:;<=>?_ ";
+
+    match c {
+        '0'..='9' | FNC1 => alpha_value(c),
+        'A'..='Z' => Some((c as u32 - 'A' as u32 + 64, 7)),
+        'a'..='z' => Some((c as u32 - 'a' as u32 + 90, 7)),
+        _ => PUNCTUATION.find(c).map(|i| (232 + i as u32, 8)),
+    }
+}
+
+// Encodes the general-purpose data field, followed by the padding pattern. `padded_len` returns
+// the total number of bits once a binary data string of the given length is padded.
+pub(crate) fn encode_general<F: Fn(usize) -> usize>(
+    bits: &mut Vec<u8>,
+    data: &[char],
+    padded_len: F,
+) {
+    let mut mode = Mode::Numeric;
+    let mut i = 0;
+
+    while i < data.len() {
+        let c = data[i];
+
+        match mode {
+            Mode::Numeric => match (numeric_value(c), data.get(i + 1).map(|&n| numeric_value(n))) {
+                (Some(a), Some(Some(b))) if a != 10 || b != 10 => {
+                    push_bits(bits, 11 * a + b + 8, 7);
+                    i += 2;
+                }
+                (Some(a), None) if a < 10 => {
+                    // A final single digit may be encoded in four bits if it fills the symbol.
+                    if padded_len(bits.len() + 4) - bits.len() < 7 {
+                        push_bits(bits, a + 1, 4);
+                        bits.resize(padded_len(bits.len()), 0);
+                        return;
+                    }
+
+                    push_bits(bits, 11 * a + 10 + 8, 7);
+                    i += 1;
+                }
+                _ => {
+                    push_bits(bits, 0b0000, 4);
+                    mode = Mode::Alpha;
+                }
+            },
+            Mode::Alpha | Mode::Iso => {
+                // FNC1 counts as a numeric character when looking ahead for a numeric latch.
+                let rest = &data[i..];
+                let run = |f: fn(char) -> bool| rest.iter().take_while(|&&c| f(c)).count();
+                let numeric = run(|c| numeric_value(c).is_some());
+                let alpha = run(|c| alpha_value(c).is_some());
+
+                if c == FNC1 {
+                    push_bits(bits, 15, 5);
+                    mode = Mode::Numeric;
+                    i += 1;
+                } else if mode == Mode::Alpha {
+                    match alpha_value(c) {
+                        None => {
+                            push_bits(bits, 0b00100, 5);
+                            mode = Mode::Iso;
+                        }
+                        Some(_) if numeric >= 6 || (numeric >= 4 && numeric == rest.len()) => {
+                            push_bits(bits, 0b000, 3);
+                            mode = Mode::Numeric;
+                        }
+                        Some((value, len)) => {
+                            push_bits(bits, value, len);
+                            i += 1;
+                        }
+                    }
+                } else {
+                    // Latching out of ISO/IEC 646 mode needs no such characters in the next ten.
+                    let no_iso = alpha >= rest.len().min(10);
+
+                    if no_iso && numeric >= 4 {
+                        push_bits(bits, 0b000, 3);
+                        mode = Mode::Numeric;
+                    } else if no_iso && alpha >= 5 {
+                        push_bits(bits, 0b00100, 5);
+                        mode = Mode::Alpha;
+                    } else {
+                        let (value, len) = iso_value(c).expect("Unknown character");
+                        push_bits(bits, value, len);
+                        i += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    // Padding: latch out of numeric mode, then repeat the alphanumeric/ISO latch pattern.
+    let padded = padded_len(bits.len());
+    let mut pattern: Vec<u8> = vec![];
+
+    if mode == Mode::Numeric {
+        pattern.extend([0, 0, 0, 0].iter());
+    }
+
+    while bits.len() + pattern.len() < padded {
+        pattern.extend([0, 0, 1, 0, 0].iter());
+    }
+
+    pattern.truncate(padded - bits.len());
+    bits.extend(pattern);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{Error, Lengths};
+    use crate::sym::databar::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::string::String;
+    use core::char;
+
+    fn collapse_vec(v: Vec<u8>) -> String {
+        let chars = v.iter().map(|d| char::from_digit(*d as u32, 10).unwrap());
+        chars.collect()
+    }
+
+    fn collapse_bits(databar: &DataBarExpanded) -> String {
+        databar
+            .chars
+            .iter()
+            .map(|c| {
+                (0..12)
+                    .rev()
+                    .map(|i| char::from_digit(((c >> i) & 1) as u32, 10).unwrap())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn new_databar_expanded() {
+        let databar_a = DataBarExpanded::new("(01)98898765432106(3103)001750");
+        let databar_b = DataBarExpanded::new("(10)ABC123(21)xyz-99");
+
+        assert!(databar_a.is_ok());
+        assert!(databar_b.is_ok());
+    }
+
+    #[test]
+    fn invalid_data_databar_expanded() {
+        let databar_a = DataBarExpanded::new("01)98898765432106");
+        let databar_b = DataBarExpanded::new("(01)9889876543210A");
+        let databar_c = DataBarExpanded::new("(10)ABC~");
+
+        assert_eq!(databar_a.err().unwrap(), Error::character(0, '0'));
+        assert_eq!(databar_b.err().unwrap(), Error::character(17, 'A'));
+        assert_eq!(databar_c.err().unwrap(), Error::character(7, '~'));
+    }
+
+    #[test]
+    fn invalid_len_databar_expanded() {
+        let databar_a = DataBarExpanded::new("");
+        let databar_b = DataBarExpanded::new("(01)9889876543210");
+        let databar_c = DataBarExpanded::new("(10)ABCDEFGHIJKLMNOPQRSTUVWXYZ(21)ABCDEFGHIJKLMNOPQRSTUVWXYZ");
+        let databar_d = DataBarExpanded::stacked("(01)98898765432106", 3);
+
+        assert_eq!(databar_a.err().unwrap(), Error::length(1..=usize::MAX, 0));
+        assert_eq!(databar_b.err().unwrap(), Error::length(14..=14, 13));
+        assert_eq!(databar_c.err().unwrap(), Error::length(0..=252, 348));
+        assert_eq!(
+            databar_d.err().unwrap(),
+            Error::option("segments", 2..=22, 3)
+        );
+    }
+
+    #[test]
+    fn invalid_checksum_databar_expanded() {
+        let databar = DataBarExpanded::new("(01)98898765432107");
+
+        assert_eq!(databar.err().unwrap(), Error::checksum(6, 7));
+    }
+
+    #[test]
+    fn invalid_data_position_databar_expanded() {
+        let databar_a = DataBarExpanded::new("(01)9889876543210A");
+        let databar_b = DataBarExpanded::new("(01)98898765432106(10)AB~C");
+        let databar_c = DataBarExpanded::new("(01)98898765432106(10)");
+
+        assert!(matches!(
+            databar_a,
+            Err(Error::Character {
+                character: 'A',
+                index: 17
+            })
+        ));
+        assert!(matches!(
+            databar_b,
+            Err(Error::Character {
+                character: '~',
+                index: 24
+            })
+        ));
+        assert!(matches!(
+            databar_c,
+            Err(Error::Length { allowed, actual: 0 }) if allowed == Lengths::Range(1..=90)
+        ));
+    }
+
+    #[test]
+    fn databar_expanded_compaction() {
+        let weight = DataBarExpanded::new("(01)98898765432106(3103)001750").unwrap();
+        let date = DataBarExpanded::new("(01)98898765432106(3202)012345(15)991231").unwrap();
+        let gtin = DataBarExpanded::new("(01)00012345678905(10)ABC123").unwrap();
+
+        assert_eq!(
+            collapse_bits(&weight),
+            "001001101111001110110110010000111110011010010000011011010110"
+        );
+        assert_eq!(&collapse_bits(&date)[..8], "00111101");
+        assert_eq!(date.chars.len(), 7);
+        assert_eq!(&collapse_bits(&gtin)[..8], "01100000");
+    }
+
+    #[test]
+    fn databar_expanded_latches() {
+        // FNC1 counts towards the six numeric characters needed to latch out of alphanumeric mode.
+        let fnc1 = DataBarExpanded::new("(10)AB12345(21)X").unwrap();
+        // No latch out of ISO/IEC 646 mode while an ISO/IEC 646 character is within ten characters.
+        let iso = DataBarExpanded::new("(10)a1234567x").unwrap();
+
+        assert_eq!(
+            collapse_bits(&fnc1),
+            "000100010011000010000010000100000101010101101100100100111110000110111001"
+        );
+        assert_eq!(
+            collapse_bits(&iso),
+            "000100010011000000100101101000110001110100001001010100101101100111000100"
+        );
+    }
+
+    #[test]
+    fn databar_expanded_encode() {
+        let databar_a = DataBarExpanded::new("(01)98898765432106(3103)001750").unwrap();
+        let databar_b = DataBarExpanded::new("(90)12").unwrap();
+
+        assert_eq!(collapse_vec(databar_a.encode()), "0101001110000010011011111111000010100010011100001101101111101100010010111100000011101111010011000111011001111100010010001111110000101011000010011111010");
+        assert_eq!(collapse_vec(databar_b.encode()), "010100000001001100101111111100001010001000100000110101111100101110001011110000000010101111100001011101");
+    }
+
+    #[test]
+    fn databar_expanded_stacked_encode() {
+        let databar = DataBarExpanded::stacked("(01)98898765432106(3103)001750", 4).unwrap();
+        let rows = databar.encode_stacked();
+
+        // Two rows of symbol characters with three separator rows between them.
+        assert_eq!(databar.segments(), 4);
+        assert_eq!(rows.len(), 5);
+        assert!(rows.iter().all(|r| r.len() == 2 * 49 + 4));
+    }
+}
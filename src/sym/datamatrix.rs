@@ -0,0 +1,845 @@
+//! Encoder for Data Matrix (ECC 200) barcodes.
+//!
+//! Data Matrix is a two-dimensional matrix symbology defined by ISO/IEC 16022. It is widely
+//! used to mark small items such as electronic components and pharmaceutical packaging.
+//!
+//! Data is compacted using the ASCII, C40, Text, X12, EDIFACT and Base256 encodation schemes,
+//! switching between them as needed to minimise the number of codewords. Characters in the
+//! ISO/IEC 8859-1 (Latin-1) range are supported.
+//!
+//! The smallest square symbol that fits the data is chosen by default. Rectangular symbols
+//! (8x18 to 16x48 modules) can be selected with `DataMatrix::rectangular`.
+//!
+//! GS1 Data Matrix is supported via `DataMatrix::gs1`, which accepts element strings in the
+//! bracketed form, e.g "(01)09501101020917(17)190508(10)ABC123".
+
+use crate::error::{Error, Result};
+use crate::sym::helpers::{self, vec, Vec, FNC1};
+use crate::sym::Matrix;
+
+// Internal value representing FNC1 in the input data.
+const FNC1_VALUE: u16 = 256;
+
+// Codewords with special meaning in ASCII encodation.
+const PAD: u8 = 129;
+const LATCH_C40: u8 = 230;
+const LATCH_BASE256: u8 = 231;
+const FNC1_CODEWORD: u8 = 232;
+const UPPER_SHIFT: u8 = 235;
+const LATCH_X12: u8 = 238;
+const LATCH_TEXT: u8 = 239;
+const LATCH_EDIFACT: u8 = 240;
+const UNLATCH: u8 = 254;
+const UNLATCH_EDIFACT: u8 = 31;
+
+// Symbol sizes: (rows, columns, region rows, region columns, data codewords, error correction
+// codewords, interleaved blocks). The square sizes come first, followed by the rectangular.
+const SIZES: [(usize, usize, usize, usize, usize, usize, usize); 30] = [
+    (10, 10, 8, 8, 3, 5, 1),
+    (12, 12, 10, 10, 5, 7, 1),
+    (14, 14, 12, 12, 8, 10, 1),
+    (16, 16, 14, 14, 12, 12, 1),
+    (18, 18, 16, 16, 18, 14, 1),
+    (20, 20, 18, 18, 22, 18, 1),
+    (22, 22, 20, 20, 30, 20, 1),
+    (24, 24, 22, 22, 36, 24, 1),
+    (26, 26, 24, 24, 44, 28, 1),
+    (32, 32, 14, 14, 62, 36, 1),
+    (36, 36, 16, 16, 86, 42, 1),
+    (40, 40, 18, 18, 114, 48, 1),
+    (44, 44, 20, 20, 144, 56, 1),
+    (48, 48, 22, 22, 174, 68, 1),
+    (52, 52, 24, 24, 204, 84, 2),
+    (64, 64, 14, 14, 280, 112, 2),
+    (72, 72, 16, 16, 368, 144, 4),
+    (80, 80, 18, 18, 456, 192, 4),
+    (88, 88, 20, 20, 576, 224, 4),
+    (96, 96, 22, 22, 696, 272, 4),
+    (104, 104, 24, 24, 816, 336, 6),
+    (120, 120, 18, 18, 1050, 408, 6),
+    (132, 132, 20, 20, 1304, 496, 8),
+    (144, 144, 22, 22, 1558, 620, 10),
+    (8, 18, 6, 16, 5, 7, 1),
+    (8, 32, 6, 14, 10, 11, 1),
+    (12, 26, 10, 24, 16, 14, 1),
+    (12, 36, 10, 16, 22, 18, 1),
+    (16, 36, 14, 16, 32, 24, 1),
+    (16, 48, 14, 22, 49, 28, 1),
+];
+
+const SQUARE_SIZES: usize = 24;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Encodation {
+    Ascii,
+    C40,
+    Text,
+    X12,
+    Edifact,
+    Base256,
+}
+
+const ENCODATIONS: [Encodation; 6] = [
+    Encodation::Ascii,
+    Encodation::C40,
+    Encodation::Text,
+    Encodation::X12,
+    Encodation::Edifact,
+    Encodation::Base256,
+];
+
+impl Encodation {
+    fn index(self) -> usize {
+        ENCODATIONS.iter().position(|&e| e == self).unwrap_or(0)
+    }
+
+    fn latch(self) -> u8 {
+        match self {
+            Encodation::C40 => LATCH_C40,
+            Encodation::Text => LATCH_TEXT,
+            Encodation::X12 => LATCH_X12,
+            Encodation::Edifact => LATCH_EDIFACT,
+            _ => LATCH_BASE256,
+        }
+    }
+
+    // Returns the C40, Text or X12 values for a character, or None if it can't be encoded.
+    fn values(self, c: u16) -> Option<Vec<u8>> {
+        let v = c as u8;
+
+        match (self, c) {
+            (Encodation::X12, 13) => Some(vec![0]),
+            (Encodation::X12, 42) => Some(vec![1]),
+            (Encodation::X12, 62) => Some(vec![2]),
+            (Encodation::X12, 32) => Some(vec![3]),
+            (Encodation::X12, 48..=57) => Some(vec![v - 44]),
+            (Encodation::X12, 65..=90) => Some(vec![v - 51]),
+            (Encodation::X12, _) => None,
+            (_, FNC1_VALUE) => Some(vec![1, 27]),
+            (_, 128..=255) => {
+                let mut values = vec![1, 30];
+                values.extend(self.values(c - 128)?);
+                Some(values)
+            }
+            (_, 32) => Some(vec![3]),
+            (_, 48..=57) => Some(vec![v - 44]),
+            (Encodation::C40, 65..=90) | (Encodation::Text, 97..=122) => {
+                Some(vec![(v & 0x1F) + 13])
+            }
+            (_, 0..=31) => Some(vec![0, v]),
+            (_, 33..=47) => Some(vec![1, v - 33]),
+            (_, 58..=64) => Some(vec![1, v - 43]),
+            (_, 91..=95) => Some(vec![1, v - 69]),
+            (Encodation::Text, 65..=90) => Some(vec![2, v - 64]),
+            (_, 96..=127) => Some(vec![2, v - 96]),
+            _ => None,
+        }
+    }
+}
+
+fn is_digit(c: u16) -> bool {
+    (48..=57).contains(&c)
+}
+
+fn is_extended(c: u16) -> bool {
+    (128..=255).contains(&c)
+}
+
+fn is_native(encodation: Encodation, c: u16) -> bool {
+    match encodation {
+        Encodation::C40 => c == 32 || is_digit(c) || (65..=90).contains(&c),
+        Encodation::Text => c == 32 || is_digit(c) || (97..=122).contains(&c),
+        Encodation::X12 => encodation.values(c).is_some(),
+        Encodation::Edifact => (32..=94).contains(&c),
+        _ => c != FNC1_VALUE,
+    }
+}
+
+// Chooses the encodation for the data from the given position, as per the look-ahead test of
+// ISO/IEC 16022 Annex P. Costs are counted in twelfths of a codeword.
+fn look_ahead(data: &[u16], start: usize, current: Encodation) -> Encodation {
+    let mut counts: [u32; 6] = match current {
+        Encodation::Ascii => [0, 12, 12, 12, 12, 15],
+        _ => [12, 24, 24, 24, 24, 27],
+    };
+    counts[current.index()] = 0;
+
+    let whole = |counts: &[u32; 6]| counts.map(|c| c.div_ceil(12));
+    let beats = |ints: &[u32; 6], e: usize, margin: u32, except: &[usize]| {
+        (0..6).all(|k| k == e || except.contains(&k) || ints[e] + margin < ints[k])
+    };
+    let (ascii, c40, text, x12, edifact, base256) = (0, 1, 2, 3, 4, 5);
+
+    for (processed, &c) in data[start..].iter().enumerate() {
+        if is_digit(c) {
+            counts[ascii] += 6;
+        } else {
+            counts[ascii] = counts[ascii].div_ceil(12) * 12;
+            counts[ascii] += if is_extended(c) { 24 } else { 12 };
+        }
+
+        for (e, native, extended, other) in [(c40, 8, 32, 16), (text, 8, 32, 16)] {
+            counts[e] += match c {
+                _ if is_native(ENCODATIONS[e], c) => native,
+                _ if is_extended(c) => extended,
+                _ => other,
+            };
+        }
+
+        counts[x12] += match c {
+            _ if is_native(Encodation::X12, c) => 8,
+            _ if is_extended(c) => 52,
+            _ => 40,
+        };
+        counts[edifact] += match c {
+            _ if is_native(Encodation::Edifact, c) => 9,
+            _ if is_extended(c) => 51,
+            _ => 39,
+        };
+        counts[base256] += if c == FNC1_VALUE { 48 } else { 12 };
+
+        if processed < 3 {
+            continue;
+        }
+
+        let ints = whole(&counts);
+
+        if beats(&ints, ascii, 0, &[]) {
+            return Encodation::Ascii;
+        }
+
+        if ints[base256] < ints[ascii] || beats(&ints, base256, 1, &[ascii]) {
+            return Encodation::Base256;
+        }
+
+        for e in [edifact, text, x12] {
+            if beats(&ints, e, 1, &[]) {
+                return ENCODATIONS[e];
+            }
+        }
+
+        if beats(&ints, c40, 1, &[x12]) {
+            if ints[c40] < ints[x12] {
+                return Encodation::C40;
+            }
+
+            if ints[c40] == ints[x12] {
+                // Prefer X12 if a segment terminator or separator follows shortly.
+                for &next in data[start + processed + 1..].iter() {
+                    if matches!(next, 13 | 42 | 62) {
+                        return Encodation::X12;
+                    }
+
+                    if !is_native(Encodation::X12, next) {
+                        break;
+                    }
+                }
+
+                return Encodation::C40;
+            }
+        }
+    }
+
+    let ints = whole(&counts);
+    let min = ints.iter().copied().min().unwrap_or(0);
+    let winners: Vec<usize> = (0..6).filter(|&e| ints[e] == min).collect();
+
+    match winners[..] {
+        _ if ints[ascii] == min => Encodation::Ascii,
+        [e] if e != c40 => ENCODATIONS[e],
+        _ => Encodation::C40,
+    }
+}
+
+// Randomises a Base256 codeword by its 1-based position in the symbol.
+fn randomise_255(value: u8, position: usize) -> u8 {
+    let pseudo = (149 * position) % 255 + 1;
+
+    ((value as usize + pseudo) % 256) as u8
+}
+
+// Randomises a pad codeword by its 1-based position in the symbol.
+fn randomise_253(value: u8, position: usize) -> u8 {
+    let temp = value as usize + (149 * position) % 253 + 1;
+
+    if temp <= 254 {
+        temp as u8
+    } else {
+        (temp - 254) as u8
+    }
+}
+
+// Encodes the data into codewords, excluding padding.
+fn encode_data(data: &[u16], gs1: bool) -> Vec<u8> {
+    let mut codewords = vec![];
+    let mut encodation = Encodation::Ascii;
+    let mut force_ascii = false;
+    let mut i = 0;
+
+    if gs1 {
+        codewords.push(FNC1_CODEWORD);
+    }
+
+    while i < data.len() {
+        let start = i;
+
+        match encodation {
+            Encodation::Ascii => {
+                if is_digit(data[i]) && data.get(i + 1).is_some_and(|&c| is_digit(c)) {
+                    codewords.push(130 + ((data[i] - 48) * 10 + data[i + 1] - 48) as u8);
+                    i += 2;
+                    continue;
+                }
+
+                let next = look_ahead(data, i, Encodation::Ascii);
+
+                if next != Encodation::Ascii && !force_ascii {
+                    codewords.push(next.latch());
+                    encodation = next;
+                    continue;
+                }
+
+                match data[i] {
+                    FNC1_VALUE => codewords.push(FNC1_CODEWORD),
+                    c if is_extended(c) => codewords.extend([UPPER_SHIFT, (c - 127) as u8]),
+                    c => codewords.push(c as u8 + 1),
+                }
+
+                force_ascii = false;
+                i += 1;
+            }
+            Encodation::C40 | Encodation::Text | Encodation::X12 => {
+                let mut values = vec![];
+                let mut safe = i;
+
+                while let Some(v) = data.get(i).and_then(|&c| encodation.values(c)) {
+                    values.extend(v);
+                    i += 1;
+
+                    if values.len() % 3 == 0 {
+                        for t in values.chunks(3) {
+                            let packed = 1600 * t[0] as u16 + 40 * t[1] as u16 + t[2] as u16 + 1;
+                            codewords.extend([(packed >> 8) as u8, packed as u8]);
+                        }
+
+                        values.clear();
+                        safe = i;
+
+                        if look_ahead(data, i, encodation) != encodation {
+                            break;
+                        }
+                    }
+                }
+
+                // Characters that don't complete a triple are encoded in ASCII instead.
+                i = safe;
+                codewords.push(UNLATCH);
+                force_ascii = i == start;
+                encodation = Encodation::Ascii;
+            }
+            Encodation::Edifact => {
+                let mut values = vec![];
+
+                while i < data.len() && is_native(Encodation::Edifact, data[i]) {
+                    values.push(data[i] as u8 & 0x3F);
+                    i += 1;
+
+                    if values.len() == 4 {
+                        codewords.extend(pack_edifact(&values));
+                        values.clear();
+
+                        if look_ahead(data, i, encodation) != encodation {
+                            break;
+                        }
+                    }
+                }
+
+                values.push(UNLATCH_EDIFACT);
+                codewords.extend(pack_edifact(&values));
+                force_ascii = i == start;
+                encodation = Encodation::Ascii;
+            }
+            Encodation::Base256 => {
+                let mut field = vec![];
+
+                while i < data.len() && data[i] != FNC1_VALUE {
+                    field.push(data[i] as u8);
+                    i += 1;
+
+                    if look_ahead(data, i, encodation) != encodation {
+                        break;
+                    }
+                }
+
+                let len = field.len();
+                let header = match len {
+                    0..=249 => vec![len as u8],
+                    _ => vec![(len / 250 + 249) as u8, (len % 250) as u8],
+                };
+
+                for b in header.into_iter().chain(field) {
+                    codewords.push(randomise_255(b, codewords.len() + 1));
+                }
+
+                force_ascii = i == start;
+                encodation = Encodation::Ascii;
+            }
+        }
+    }
+
+    codewords
+}
+
+// Packs up to four 6-bit EDIFACT values into codewords, padding the last with zero bits.
+fn pack_edifact(values: &[u8]) -> Vec<u8> {
+    let bits =
+        values.iter().fold(0u32, |acc, &v| (acc << 6) | v as u32) << (6 * (4 - values.len()));
+    let count = (values.len() * 6).div_ceil(8);
+
+    (0..count).map(|i| (bits >> (16 - 8 * i)) as u8).collect()
+}
+
+/// The Data Matrix barcode type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DataMatrix {
+    codewords: Vec<u8>,
+    size: usize,
+}
+
+impl DataMatrix {
+    /// Creates a new barcode using the smallest square symbol that fits the data.
+    /// Returns Result<DataMatrix, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<DataMatrix> {
+        let data = data
+            .as_ref()
+            .chars()
+            .enumerate()
+            .map(|(i, c)| match c as u32 {
+                0..=255 => Ok(c as u16),
+                _ => Err(Error::character(i, c)),
+            })
+            .collect::<Result<Vec<u16>>>()?;
+
+        DataMatrix::build(encode_data(&data, false), 0..SQUARE_SIZES)
+    }
+
+    /// Creates a new GS1 Data Matrix barcode from a bracketed element string, using the
+    /// smallest square symbol that fits the data.
+    /// Returns Result<DataMatrix, Error> indicating parse success.
+    pub fn gs1<T: AsRef<str>>(data: T) -> Result<DataMatrix> {
+        let elements = helpers::gs1_elements(data.as_ref())?;
+        let data: Vec<u16> = helpers::gs1_element_string(&elements)
+            .into_iter()
+            .map(|c| if c == FNC1 { FNC1_VALUE } else { c as u16 })
+            .collect();
+
+        DataMatrix::build(encode_data(&data, true), 0..SQUARE_SIZES)
+    }
+
+    /// Switches the barcode to the smallest rectangular symbol that fits the data.
+    /// Returns Result<DataMatrix, Error> indicating success.
+    pub fn rectangular(self) -> Result<DataMatrix> {
+        DataMatrix::build(self.codewords, SQUARE_SIZES..SIZES.len())
+    }
+
+    /// Returns the size of the symbol as (rows, columns) of modules.
+    pub fn size(&self) -> (usize, usize) {
+        (SIZES[self.size].0, SIZES[self.size].1)
+    }
+
+    fn build(codewords: Vec<u8>, sizes: core::ops::Range<usize>) -> Result<DataMatrix> {
+        if codewords.is_empty() {
+            return Err(Error::length(1..=usize::MAX, 0));
+        }
+
+        let capacity = SIZES[sizes.end - 1].4;
+
+        match sizes.into_iter().find(|&s| SIZES[s].4 >= codewords.len()) {
+            Some(size) => Ok(DataMatrix { codewords, size }),
+            None => Err(Error::length(1..=capacity, codewords.len())),
+        }
+    }
+
+    // Pads the data codewords and appends the interleaved error correction codewords.
+    fn codewords(&self) -> Vec<u8> {
+        let (_, _, _, _, capacity, ecc_len, blocks) = SIZES[self.size];
+        let mut data = self.codewords.clone();
+
+        if data.len() < capacity {
+            data.push(PAD);
+        }
+
+        while data.len() < capacity {
+            data.push(randomise_253(PAD, data.len() + 1));
+        }
+
+        let mut codewords = data.clone();
+        codewords.extend(vec![0; ecc_len]);
+
+        for b in 0..blocks {
+            let block: Vec<u16> = data
+                .iter()
+                .skip(b)
+                .step_by(blocks)
+                .map(|&c| c as u16)
+                .collect();
+            let ecc = helpers::reed_solomon(&block, ecc_len / blocks, 0x12D, 1);
+
+            for (j, &e) in ecc.iter().enumerate() {
+                codewords[capacity + j * blocks + b] = e as u8;
+            }
+        }
+
+        codewords
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Matrix of binary digits.
+    pub fn encode(&self) -> Matrix {
+        let (rows, cols, region_rows, region_cols, _, _, _) = SIZES[self.size];
+        let mapping = place_modules(
+            &self.codewords(),
+            rows / (region_rows + 2) * region_rows,
+            cols / (region_cols + 2) * region_cols,
+        );
+        let mut matrix = Matrix::new(cols, rows);
+
+        for y in 0..rows {
+            for x in 0..cols {
+                let (ry, rx) = (y % (region_rows + 2), x % (region_cols + 2));
+                let module = match (ry, rx) {
+                    _ if ry == region_rows + 1 || rx == 0 => 1,
+                    (0, _) => u8::from(rx % 2 == 0),
+                    (_, _) if rx == region_cols + 1 => u8::from(ry % 2 == 1),
+                    _ => {
+                        let my = y / (region_rows + 2) * region_rows + ry - 1;
+                        let mx = x / (region_cols + 2) * region_cols + rx - 1;
+                        mapping[my][mx]
+                    }
+                };
+
+                matrix.set(x, y, module);
+            }
+        }
+
+        matrix
+    }
+}
+
+// Places the codewords into the mapping matrix (the symbol without its finder and timing
+// patterns) as per ISO/IEC 16022 Annex F.
+fn place_modules(codewords: &[u8], nrow: usize, ncol: usize) -> Vec<Vec<u8>> {
+    let mut placement = Placement {
+        modules: vec![vec![None; ncol]; nrow],
+        codewords,
+        nrow: nrow as isize,
+        ncol: ncol as isize,
+    };
+    let (nrow, ncol) = (nrow as isize, ncol as isize);
+    let mut chr = 0;
+    let (mut row, mut col) = (4isize, 0isize);
+
+    loop {
+        let corner = match (row, col) {
+            (r, 0) if r == nrow => Some(0),
+            (r, 0) if r == nrow - 2 && ncol % 4 != 0 => Some(1),
+            (r, 0) if r == nrow - 2 && ncol % 8 == 4 => Some(2),
+            (r, 2) if r == nrow + 4 && ncol % 8 == 0 => Some(3),
+            _ => None,
+        };
+
+        if let Some(corner) = corner {
+            placement.corner(corner, chr);
+            chr += 1;
+        }
+
+        // Sweep upward diagonally.
+        loop {
+            if row < nrow && col >= 0 && placement.is_free(row, col) {
+                placement.utah(row, col, chr);
+                chr += 1;
+            }
+
+            row -= 2;
+            col += 2;
+
+            if row < 0 || col >= ncol {
+                break;
+            }
+        }
+
+        row += 1;
+        col += 3;
+
+        // Sweep downward diagonally.
+        loop {
+            if row >= 0 && col < ncol && placement.is_free(row, col) {
+                placement.utah(row, col, chr);
+                chr += 1;
+            }
+
+            row += 2;
+            col -= 2;
+
+            if row >= nrow || col < 0 {
+                break;
+            }
+        }
+
+        row += 3;
+        col += 1;
+
+        if row >= nrow && col >= ncol {
+            break;
+        }
+    }
+
+    let (r, c) = ((nrow - 1) as usize, (ncol - 1) as usize);
+
+    // Sizes with four unused modules fill the bottom right corner with a fixed pattern.
+    if placement.modules[r][c].is_none() {
+        placement.modules[r][c] = Some(1);
+        placement.modules[r - 1][c - 1] = Some(1);
+    }
+
+    placement
+        .modules
+        .into_iter()
+        .map(|row| row.into_iter().map(|m| m.unwrap_or(0)).collect())
+        .collect()
+}
+
+struct Placement<'a> {
+    modules: Vec<Vec<Option<u8>>>,
+    codewords: &'a [u8],
+    nrow: isize,
+    ncol: isize,
+}
+
+impl Placement<'_> {
+    fn is_free(&self, row: isize, col: isize) -> bool {
+        self.modules[row as usize][col as usize].is_none()
+    }
+
+    // Places bit (1 = most significant) of codeword chr, wrapping around the edges.
+    fn module(&mut self, mut row: isize, mut col: isize, chr: usize, bit: u8) {
+        if row < 0 {
+            row += self.nrow;
+            col += 4 - (self.nrow + 4) % 8;
+        }
+
+        if col < 0 {
+            col += self.ncol;
+            row += 4 - (self.ncol + 4) % 8;
+        }
+
+        let value = (self.codewords[chr] >> (8 - bit)) & 1;
+        self.modules[row as usize][col as usize] = Some(value);
+    }
+
+    // Places a codeword in the standard "utah" shape, with its last bit at (row, col).
+    fn utah(&mut self, row: isize, col: isize, chr: usize) {
+        let shape = [
+            (-2, -2),
+            (-2, -1),
+            (-1, -2),
+            (-1, -1),
+            (-1, 0),
+            (0, -2),
+            (0, -1),
+            (0, 0),
+        ];
+
+        for (bit, (dr, dc)) in shape.iter().enumerate() {
+            self.module(row + dr, col + dc, chr, bit as u8 + 1);
+        }
+    }
+
+    // Places a codeword in one of the four special corner shapes.
+    fn corner(&mut self, corner: usize, chr: usize) {
+        let (n, m) = (self.nrow, self.ncol);
+        let positions = match corner {
+            0 => [
+                (n - 1, 0),
+                (n - 1, 1),
+                (n - 1, 2),
+                (0, m - 2),
+                (0, m - 1),
+                (1, m - 1),
+                (2, m - 1),
+                (3, m - 1),
+            ],
+            1 => [
+                (n - 3, 0),
+                (n - 2, 0),
+                (n - 1, 0),
+                (0, m - 4),
+                (0, m - 3),
+                (0, m - 2),
+                (0, m - 1),
+                (1, m - 1),
+            ],
+            2 => [
+                (n - 3, 0),
+                (n - 2, 0),
+                (n - 1, 0),
+                (0, m - 2),
+                (0, m - 1),
+                (1, m - 1),
+                (2, m - 1),
+                (3, m - 1),
+            ],
+            _ => [
+                (n - 1, 0),
+                (n - 1, m - 1),
+                (0, m - 3),
+                (0, m - 2),
+                (0, m - 1),
+                (1, m - 3),
+                (1, m - 2),
+                (1, m - 1),
+            ],
+        };
+
+        for (bit, (row, col)) in positions.iter().enumerate() {
+            self.module(*row, *col, chr, bit as u8 + 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sym::datamatrix::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::string::String;
+
+    fn collapse_vec(v: &[u8]) -> String {
+        let chars = v.iter().map(|d| char::from_digit(*d as u32, 10).unwrap());
+        chars.collect()
+    }
+
+    #[test]
+    fn new_datamatrix() {
+        let dm1 = DataMatrix::new("123456");
+        let dm2 = DataMatrix::new("Hello, World! Ünïcödé in Latin-1");
+        let dm3 = DataMatrix::gs1("(01)09501101020917(17)190508(10)ABC123");
+
+        assert!(dm1.is_ok());
+        assert!(dm2.is_ok());
+        assert!(dm3.is_ok());
+    }
+
+    #[test]
+    fn invalid_data_datamatrix() {
+        let dm1 = DataMatrix::new("Price: €5");
+        let dm2 = DataMatrix::gs1("01)09501101020917");
+
+        assert_eq!(dm1.err().unwrap(), Error::character(7, '€'));
+        assert_eq!(dm2.err().unwrap(), Error::character(0, '0'));
+    }
+
+    #[test]
+    fn invalid_len_datamatrix() {
+        let dm1 = DataMatrix::new("");
+        let dm2 = DataMatrix::new("1".repeat(3117));
+        let dm3 = DataMatrix::new("1".repeat(100)).unwrap().rectangular();
+
+        assert_eq!(dm1.err().unwrap(), Error::length(1..=usize::MAX, 0));
+        assert_eq!(dm2.err().unwrap(), Error::length(1..=1558, 1559));
+        assert_eq!(dm3.err().unwrap(), Error::length(1..=49, 50));
+    }
+
+    #[test]
+    fn datamatrix_sizes() {
+        let dm1 = DataMatrix::new("123456").unwrap();
+        let dm2 = DataMatrix::new("1".repeat(3116)).unwrap();
+        let dm3 = DataMatrix::new("1234567890")
+            .unwrap()
+            .rectangular()
+            .unwrap();
+
+        assert_eq!(dm1.size(), (10, 10));
+        assert_eq!(dm2.size(), (144, 144));
+        assert_eq!(dm3.size(), (8, 18));
+    }
+
+    #[test]
+    fn datamatrix_codewords() {
+        // The worked example of ISO/IEC 16022.
+        let dm = DataMatrix::new("123456").unwrap();
+
+        assert_eq!(dm.codewords(), vec![142, 164, 186, 114, 25, 5, 88, 102]);
+    }
+
+    #[test]
+    fn datamatrix_encodations() {
+        let c40 = DataMatrix::new("AIMAIMAIM").unwrap();
+        let text = DataMatrix::new("aimaimaim").unwrap();
+        let x12 = DataMatrix::new("ABC>ABC123>AB").unwrap();
+        let edifact = DataMatrix::new(".A.C1.3.DATA.123DATA.123DATA").unwrap();
+        let base256 = DataMatrix::new("\u{AB}\u{E4}\u{F6}\u{FC}\u{E9}\u{BB}").unwrap();
+        let gs1 = DataMatrix::gs1("(10)ABC(01)09501101020917").unwrap();
+
+        assert_eq!(c40.codewords, vec![230, 91, 11, 91, 11, 91, 11, 254]);
+        assert_eq!(text.codewords, vec![239, 91, 11, 91, 11, 91, 11, 254]);
+        assert_eq!(x12.codewords[0], LATCH_X12);
+        assert_eq!(edifact.codewords[0], LATCH_EDIFACT);
+        assert_eq!(base256.codewords[..2], [LATCH_BASE256, randomise_255(6, 2)]);
+        assert_eq!(gs1.codewords[..5], [232, 140, 66, 67, 68]);
+        assert_eq!(gs1.codewords[5], FNC1_CODEWORD);
+    }
+
+    #[test]
+    fn datamatrix_placement() {
+        // Every module of the mapping matrix is filled by exactly one codeword bit. Each pass
+        // sets the codewords with bit k of their index, so an overlap changes the count.
+        for &(rows, cols, region_rows, region_cols, data, ecc, _) in SIZES.iter() {
+            let nrow = rows / (region_rows + 2) * region_rows;
+            let ncol = cols / (region_cols + 2) * region_cols;
+            let total = data + ecc;
+            let fixed = (nrow * ncol - total * 8) / 2;
+
+            for k in 0..12 {
+                let codewords: Vec<u8> = (0..total)
+                    .map(|c| {
+                        if (c >> k) & 1 == 1 || k == 11 {
+                            0xFF
+                        } else {
+                            0
+                        }
+                    })
+                    .collect();
+                let expected = codewords.iter().filter(|&&c| c == 0xFF).count() * 8 + fixed;
+                let placed: usize = place_modules(&codewords, nrow, ncol)
+                    .iter()
+                    .map(|r| r.iter().filter(|&&m| m == 1).count())
+                    .sum();
+
+                assert_eq!(placed, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn datamatrix_encode() {
+        let dm = DataMatrix::new("123456").unwrap();
+        let matrix = dm.encode();
+        let rows: Vec<String> = matrix.rows().map(collapse_vec).collect();
+
+        assert_eq!(
+            rows,
+            vec![
+                "1010101010",
+                "1100101101",
+                "1100000100",
+                "1100011101",
+                "1100001000",
+                "1000001111",
+                "1110110000",
+                "1111011001",
+                "1001110100",
+                "1111111111",
+            ]
+        );
+    }
+}
@@ -0,0 +1,234 @@
+//! Encoders for Deutsche Post Leitcode and Identcode barcodes.
+//!
+//! Leitcode and Identcode are used by Deutsche Post (DHL) for routing and tracking mail. Both are
+//! Interleaved 2-of-5 barcodes ending with a check digit, weighted alternately by 4 and 9.
+//!
+//! Leitcode encodes a 14 digit routing code made up of the postcode, street, house number and
+//! product. Identcode encodes a 12 digit tracking code made up of the mail centre, customer and
+//! delivery numbers.
+
+use crate::checksum;
+use crate::error::{Error, Result};
+use crate::sym::tf::{Variant, TF};
+use crate::sym::{helpers, Elements, Parse};
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+use core::ops::Range;
+use helpers::Vec;
+
+// Parses the digits, checking the check digit if it is given.
+fn parse_digits(data: &str, len: usize) -> Result<Vec<u8>> {
+    let mut digits: Vec<u8> = data
+        .chars()
+        .map(|c| c.to_digit(10).expect("Unknown character") as u8)
+        .collect();
+
+    if digits.len() != len && digits.len() != len + 1 {
+        return Err(Error::length(len..=len + 1, digits.len()));
+    }
+
+    let check_digit = checksum::deutsche_post_digit(&digits[..len]);
+
+    // If checksum digit is provided, check the checksum.
+    match digits.get(len) {
+        Some(&d) if d != check_digit => return Err(Error::checksum(check_digit, d)),
+        Some(_) => {}
+        None => digits.push(check_digit),
+    }
+
+    Ok(digits)
+}
+
+// Formats the digits in groups, separated by the given characters.
+fn group(digits: &[u8], sizes: &[usize], separators: &[char]) -> String {
+    let mut text = String::new();
+    let mut chars = digits.iter().map(|&d| (b'0' + d) as char);
+
+    for (i, &size) in sizes.iter().enumerate() {
+        if i > 0 {
+            text.push(separators[i - 1]);
+        }
+
+        text.extend(chars.by_ref().take(size));
+    }
+
+    text
+}
+
+/// The Leitcode barcode type.
+#[derive(Debug)]
+pub struct Leitcode(Vec<u8>);
+
+impl Leitcode {
+    /// Creates a new barcode from 13 digits, with or without the check digit.
+    /// Returns Result<Leitcode, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<Leitcode> {
+        let d = Leitcode::parse(data.as_ref())?;
+
+        parse_digits(d, 13).map(Leitcode)
+    }
+
+    /// Returns the check digit, weighted alternately by 4 and 9.
+    pub fn check_digit(&self) -> u8 {
+        self.0[self.0.len() - 1]
+    }
+
+    /// Returns the encoded data, the digits including the check digit.
+    pub fn data(&self) -> String {
+        helpers::digit_string(&self.0)
+    }
+
+    /// Returns the human readable text, grouped as postcode, street, house number, product and
+    /// check digit.
+    pub fn text(&self) -> String {
+        group(&self.0, &[5, 3, 3, 2, 1], &['.', '.', '.', ' '])
+    }
+
+    /// Encodes the barcode as a sequence of bar and space widths.
+    pub fn elements(&self) -> Elements {
+        TF::from_digits(Variant::Interleaved, self.0.clone()).elements()
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Vec<u8> of binary digits.
+    pub fn encode(&self) -> Vec<u8> {
+        self.elements().modules()
+    }
+}
+
+impl Parse for Leitcode {
+    /// Returns the valid length of data acceptable in this type of barcode.
+    fn valid_len() -> Range<u32> {
+        13..14
+    }
+
+    /// Returns the set of valid characters allowed in this type of barcode.
+    fn valid_chars() -> Vec<char> {
+        (0..10).map(|i| char::from_digit(i, 10).unwrap()).collect()
+    }
+}
+
+/// The Identcode barcode type.
+#[derive(Debug)]
+pub struct Identcode(Vec<u8>);
+
+impl Identcode {
+    /// Creates a new barcode from 11 digits, with or without the check digit.
+    /// Returns Result<Identcode, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<Identcode> {
+        let d = Identcode::parse(data.as_ref())?;
+
+        parse_digits(d, 11).map(Identcode)
+    }
+
+    /// Returns the check digit, weighted alternately by 4 and 9.
+    pub fn check_digit(&self) -> u8 {
+        self.0[self.0.len() - 1]
+    }
+
+    /// Returns the encoded data, the digits including the check digit.
+    pub fn data(&self) -> String {
+        helpers::digit_string(&self.0)
+    }
+
+    /// Returns the human readable text, grouped as mail centre, customer and delivery numbers
+    /// and check digit.
+    pub fn text(&self) -> String {
+        group(&self.0, &[2, 3, 3, 3, 1], &['.', ' ', '.', ' '])
+    }
+
+    /// Encodes the barcode as a sequence of bar and space widths.
+    pub fn elements(&self) -> Elements {
+        TF::from_digits(Variant::Interleaved, self.0.clone()).elements()
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Vec<u8> of binary digits.
+    pub fn encode(&self) -> Vec<u8> {
+        self.elements().modules()
+    }
+}
+
+impl Parse for Identcode {
+    /// Returns the valid length of data acceptable in this type of barcode.
+    fn valid_len() -> Range<u32> {
+        11..12
+    }
+
+    /// Returns the set of valid characters allowed in this type of barcode.
+    fn valid_chars() -> Vec<char> {
+        (0..10).map(|i| char::from_digit(i, 10).unwrap()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sym::deutsche_post::*;
+
+    #[test]
+    fn new_deutsche_post() {
+        let leitcode1 = Leitcode::new("2134807501640");
+        let leitcode2 = Leitcode::new("21348075016401");
+        let identcode1 = Identcode::new("56310243031");
+        let identcode2 = Identcode::new("563102430313");
+
+        assert!(leitcode1.is_ok());
+        assert!(leitcode2.is_ok());
+        assert!(identcode1.is_ok());
+        assert!(identcode2.is_ok());
+    }
+
+    #[test]
+    fn invalid_data_deutsche_post() {
+        let leitcode = Leitcode::new("213480750164A");
+        let identcode = Identcode::new("5631024303B");
+
+        assert_eq!(leitcode.err().unwrap(), Error::character(12, 'A'));
+        assert_eq!(identcode.err().unwrap(), Error::character(10, 'B'));
+    }
+
+    #[test]
+    fn invalid_len_deutsche_post() {
+        let leitcode = Leitcode::new("213480750164");
+        let identcode = Identcode::new("5631024303131");
+
+        assert_eq!(leitcode.err().unwrap(), Error::length(13..=14, 12));
+        assert_eq!(identcode.err().unwrap(), Error::length(11..=12, 13));
+    }
+
+    #[test]
+    fn invalid_checksum_deutsche_post() {
+        let leitcode = Leitcode::new("21348075016402");
+        let identcode = Identcode::new("563102430314");
+
+        assert_eq!(leitcode.err().unwrap(), Error::checksum(1, 2));
+        assert_eq!(identcode.err().unwrap(), Error::checksum(3, 4));
+    }
+
+    #[test]
+    fn deutsche_post_text() {
+        let leitcode = Leitcode::new("2134807501640").unwrap();
+        let identcode = Identcode::new("56310243031").unwrap();
+
+        assert_eq!(leitcode.text(), "21348.075.016.40 1");
+        assert_eq!(identcode.text(), "56.310 243.031 3");
+        assert_eq!(leitcode.check_digit(), 1);
+        assert_eq!(leitcode.data(), "21348075016401");
+        assert_eq!(identcode.check_digit(), 3);
+    }
+
+    #[test]
+    fn deutsche_post_encode() {
+        let leitcode = Leitcode::new("2134807501640").unwrap();
+        let identcode = Identcode::new("56310243031").unwrap();
+
+        assert_eq!(
+            leitcode.encode(),
+            TF::interleaved("21348075016401").unwrap().encode()
+        );
+        assert_eq!(
+            identcode.encode(),
+            TF::interleaved("563102430313").unwrap().encode()
+        );
+    }
+}
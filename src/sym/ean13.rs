@@ -8,8 +8,11 @@
 //!   * Bookland
 //!   * JAN
 
+use crate::checksum;
 use crate::error::{Error, Result};
-use crate::sym::{helpers, Parse};
+use crate::sym::{helpers, Elements, Parse};
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
 use core::char;
 use core::ops::Range;
 use helpers::Vec;
@@ -107,16 +110,29 @@ impl EAN13 {
         let ean13 = EAN13(digits[0..12].to_vec());
 
         // If checksum digit is provided, check the checksum.
-        if digits.len() == 13 && ean13.checksum_digit() != digits[12] {
-            return Err(Error::Checksum);
+        if digits.len() == 13 && ean13.check_digit() != digits[12] {
+            return Err(Error::checksum(ean13.check_digit(), digits[12]));
         }
 
         Ok(ean13)
     }
 
-    /// Calculates the checksum digit using a modulo-10 weighting algorithm.
-    fn checksum_digit(&self) -> u8 {
-        helpers::modulo_10_checksum(&self.0[..], true)
+    /// Returns the check digit, calculated using a modulo-10 weighting algorithm.
+    pub fn check_digit(&self) -> u8 {
+        checksum::modulo_10_digit(&self.0[..], true)
+    }
+
+    /// Returns the encoded data, the thirteen digits including the check digit.
+    pub fn data(&self) -> String {
+        let mut data = helpers::digit_string(&self.0);
+
+        data.push((b'0' + self.check_digit()) as char);
+        data
+    }
+
+    /// Returns the human readable text, which is the same as the data.
+    pub fn text(&self) -> String {
+        self.data()
     }
 
     fn number_system_digit(&self) -> u8 {
@@ -128,7 +144,7 @@ impl EAN13 {
     }
 
     fn checksum_encoding(&self) -> [u8; 7] {
-        self.char_encoding(2, self.checksum_digit())
+        self.char_encoding(2, self.check_digit())
     }
 
     fn char_encoding(&self, side: usize, d: u8) -> [u8; 7] {
@@ -183,6 +199,11 @@ impl EAN13 {
             ][..],
         )
     }
+
+    /// Encodes the barcode as a sequence of bar and space widths.
+    pub fn elements(&self) -> Elements {
+        Elements::from(self.encode())
+    }
 }
 
 impl Parse for EAN13 {
@@ -228,21 +249,34 @@ mod tests {
     fn invalid_data_ean13() {
         let ean13 = EAN13::new("1234er123412");
 
-        assert_eq!(ean13.err().unwrap(), Error::Character)
+        assert_eq!(ean13.err().unwrap(), Error::character(4, 'e'))
     }
 
     #[test]
     fn invalid_len_ean13() {
         let ean13 = EAN13::new("1111112222222333333");
 
-        assert_eq!(ean13.err().unwrap(), Error::Length)
+        assert_eq!(ean13.err().unwrap(), Error::length(12..=13, 19))
     }
 
     #[test]
     fn invalid_checksum_ean13() {
         let ean13 = EAN13::new("8801051294881");
 
-        assert_eq!(ean13.err().unwrap(), Error::Checksum)
+        assert_eq!(ean13.err().unwrap(), Error::checksum(0, 1))
+    }
+
+    #[test]
+    fn invalid_checksum_digits_ean13() {
+        let ean13 = EAN13::new("8801051294881");
+
+        assert!(matches!(
+            ean13,
+            Err(Error::Checksum {
+                expected: 0,
+                provided: 1
+            })
+        ));
     }
 
     #[test]
@@ -262,4 +296,13 @@ mod tests {
         assert_eq!(collapse_vec(ean131.encode()), "10101100010100111001100101001110111101011001101010100001011001101100110100001011100101110100101");
         assert_eq!(collapse_vec(ean132.encode()), "10101101110100001001110101011110111001001100101010110110010000101011100111010011101001000010101");
     }
+
+    #[test]
+    fn ean13_text() {
+        let ean13 = EAN13::new("750103131130").unwrap();
+
+        assert_eq!(ean13.check_digit(), 9);
+        assert_eq!(ean13.data(), "7501031311309");
+        assert_eq!(ean13.text(), "7501031311309");
+    }
 }
@@ -3,9 +3,12 @@
 //! EAN-8 barcodes are EAN style barcodes for smaller packages on products like
 //! cigaretts, chewing gum, etc where package space is limited.
 
+use crate::checksum;
 use crate::error::{Error, Result};
 use crate::sym::ean13::{ENCODINGS, LEFT_GUARD, MIDDLE_GUARD, RIGHT_GUARD};
-use crate::sym::{helpers, Parse};
+use crate::sym::{helpers, Elements, Parse};
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
 use core::char;
 use core::ops::Range;
 use helpers::{vec, Vec};
@@ -27,16 +30,29 @@ impl EAN8 {
         let ean8 = EAN8(digits[0..7].to_vec());
 
         // If checksum digit is provided, check the checksum.
-        if digits.len() == 8 && ean8.checksum_digit() != digits[7] {
-            return Err(Error::Checksum);
+        if digits.len() == 8 && ean8.check_digit() != digits[7] {
+            return Err(Error::checksum(ean8.check_digit(), digits[7]));
         }
 
         Ok(ean8)
     }
 
-    /// Calculates the checksum digit using a weighting algorithm.
-    fn checksum_digit(&self) -> u8 {
-        helpers::modulo_10_checksum(&self.0[..], false)
+    /// Returns the check digit, calculated using a modulo-10 weighting algorithm.
+    pub fn check_digit(&self) -> u8 {
+        checksum::modulo_10_digit(&self.0[..], false)
+    }
+
+    /// Returns the encoded data, the eight digits including the check digit.
+    pub fn data(&self) -> String {
+        let mut data = helpers::digit_string(&self.0);
+
+        data.push((b'0' + self.check_digit()) as char);
+        data
+    }
+
+    /// Returns the human readable text, which is the same as the data.
+    pub fn text(&self) -> String {
+        self.data()
     }
 
     fn number_system_digits(&self) -> &[u8] {
@@ -54,7 +70,7 @@ impl EAN8 {
     }
 
     fn checksum_encoding(&self) -> [u8; 7] {
-        self.char_encoding(2, self.checksum_digit())
+        self.char_encoding(2, self.check_digit())
     }
 
     fn char_encoding(&self, side: usize, d: u8) -> [u8; 7] {
@@ -104,6 +120,11 @@ impl EAN8 {
             ][..],
         )
     }
+
+    /// Encodes the barcode as a sequence of bar and space widths.
+    pub fn elements(&self) -> Elements {
+        Elements::from(self.encode())
+    }
 }
 
 impl Parse for EAN8 {
@@ -120,7 +141,6 @@ impl Parse for EAN8 {
 
 #[cfg(test)]
 mod tests {
-    use crate::error::Error;
     use crate::sym::ean8::*;
     #[cfg(not(feature = "std"))]
     use alloc::string::String;
@@ -142,21 +162,21 @@ mod tests {
     fn invalid_data_ean8() {
         let ean8 = EAN8::new("1234er1");
 
-        assert_eq!(ean8.err().unwrap(), Error::Character);
+        assert_eq!(ean8.err().unwrap(), Error::character(4, 'e'));
     }
 
     #[test]
     fn invalid_len_ean8() {
         let ean8 = EAN8::new("1111112222222333333");
 
-        assert_eq!(ean8.err().unwrap(), Error::Length);
+        assert_eq!(ean8.err().unwrap(), Error::length(7..=8, 19));
     }
 
     #[test]
     fn invalid_checksum_ean8() {
         let ean8 = EAN8::new("88023020");
 
-        assert_eq!(ean8.err().unwrap(), Error::Checksum)
+        assert_eq!(ean8.err().unwrap(), Error::checksum(1, 0))
     }
 
     #[test]
@@ -173,4 +193,13 @@ mod tests {
             "1010001011011011101111010100011010101010000100111011001101010000101"
         );
     }
+
+    #[test]
+    fn ean8_text() {
+        let ean8 = EAN8::new("5512345").unwrap();
+
+        assert_eq!(ean8.check_digit(), 7);
+        assert_eq!(ean8.data(), "55123457");
+        assert_eq!(ean8.text(), "55123457");
+    }
 }
@@ -5,10 +5,15 @@
 //! EAN-5 barcodes are often used to indicate the suggested retail price of books.
 //!
 //! These supplemental barcodes never appear without a full EAN-13 barcode alongside them.
+//! `Supplemented` joins one to the right of an EAN-13, UPC-A or EAN-8 barcode, separated by a gap
+//! of 7 to 12 modules (9 to 12 for UPC-A).
 
+use crate::checksum;
 use crate::error::{Error, Result};
-use crate::sym::ean13::ENCODINGS;
-use crate::sym::{helpers, Parse};
+use crate::sym::ean13::{EAN13, ENCODINGS};
+use crate::sym::ean8::EAN8;
+use crate::sym::upca::UPCA;
+use crate::sym::{helpers, Elements, Parse};
 use core::char;
 use core::ops::Range;
 use helpers::{vec, Vec};
@@ -61,7 +66,7 @@ impl EANSUPP {
             match digits.len() {
                 2 => Ok(EANSUPP::EAN2(digits)),
                 5 => Ok(EANSUPP::EAN5(digits)),
-                _ => Err(Error::Length),
+                len => Err(Error::lengths(&[2, 5], len)),
             }
         })
     }
@@ -79,21 +84,7 @@ impl EANSUPP {
     /// Calculates the checksum digit using a modified modulo-10 weighting
     /// algorithm. This only makes sense for EAN5 barcodes.
     fn checksum_digit(&self) -> u8 {
-        let mut odds = 0;
-        let mut evens = 0;
-        let data = self.raw_data();
-
-        for (i, d) in data.iter().enumerate() {
-            match i % 2 {
-                1 => evens += *d,
-                _ => odds += *d,
-            }
-        }
-
-        match ((odds * 3) + (evens * 9)) % 10 {
-            10 => 0,
-            n => n,
-        }
+        checksum::ean5_digit(self.raw_data())
     }
 
     fn parity(&self) -> [usize; 5] {
@@ -135,6 +126,91 @@ impl EANSUPP {
     pub fn encode(&self) -> Vec<u8> {
         helpers::join_slices(&[&LEFT_GUARD[..], &self.payload()[..]][..])
     }
+
+    /// Encodes the barcode as a sequence of bar and space widths.
+    pub fn elements(&self) -> Elements {
+        Elements::from(self.encode())
+    }
+}
+
+/// A main EAN-13, UPC-A or EAN-8 barcode with a supplemental barcode to its right.
+#[derive(Debug)]
+pub struct Supplemented {
+    main: Vec<u8>,
+    guards: Vec<Range<usize>>,
+    supplement: Vec<u8>,
+    min_gap: usize,
+    gap: usize,
+}
+
+impl Supplemented {
+    fn init(main: Vec<u8>, guards: Vec<Range<usize>>, supp: &EANSUPP, gap: usize) -> Supplemented {
+        Supplemented {
+            main,
+            guards,
+            supplement: supp.encode(),
+            min_gap: gap,
+            gap,
+        }
+    }
+
+    /// Creates a new EAN-13 barcode with a supplemental barcode.
+    pub fn ean13(main: &EAN13, supp: &EANSUPP) -> Supplemented {
+        Supplemented::init(main.encode(), vec![0..3, 45..50, 92..95], supp, 7)
+    }
+
+    /// Creates a new UPC-A barcode with a supplemental barcode.
+    pub fn upca(main: &UPCA, supp: &EANSUPP) -> Supplemented {
+        // The bars of the first and last digits extend as far as the guards.
+        Supplemented::init(main.encode(), vec![0..10, 45..50, 85..95], supp, 9)
+    }
+
+    /// Creates a new EAN-8 barcode with a supplemental barcode.
+    pub fn ean8(main: &EAN8, supp: &EANSUPP) -> Supplemented {
+        Supplemented::init(main.encode(), vec![0..3, 31..36, 64..67], supp, 7)
+    }
+
+    /// Sets the gap between the main and supplemental barcodes in modules, from 7 (9 for UPC-A)
+    /// to 12. The default is the minimum.
+    /// Returns Result<Supplemented, Error> indicating success.
+    pub fn gap(mut self, gap: usize) -> Result<Supplemented> {
+        if !(self.min_gap..=12).contains(&gap) {
+            return Err(Error::option("gap", self.min_gap..=12, gap));
+        }
+
+        self.gap = gap;
+        Ok(self)
+    }
+
+    /// Returns the range of modules taken by the main barcode.
+    pub fn main(&self) -> Range<usize> {
+        0..self.main.len()
+    }
+
+    /// Returns the ranges of modules of the main barcode whose bars extend below the others,
+    /// such as the guard patterns, where the text of the main barcode is broken.
+    pub fn guards(&self) -> &[Range<usize>] {
+        &self.guards
+    }
+
+    /// Returns the range of modules taken by the supplemental barcode. The text of the
+    /// supplemental barcode is drawn above its bars, which are shortened to make room for it.
+    pub fn supplement(&self) -> Range<usize> {
+        let start = self.main.len() + self.gap;
+
+        start..start + self.supplement.len()
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Vec<u8> of binary digits.
+    pub fn encode(&self) -> Vec<u8> {
+        helpers::join_slices(&[&self.main[..], &vec![0; self.gap][..], &self.supplement[..]][..])
+    }
+
+    /// Encodes the barcode as a sequence of bar and space widths.
+    pub fn elements(&self) -> Elements {
+        Elements::from(self.encode())
+    }
 }
 
 impl Parse for EANSUPP {
@@ -151,7 +227,6 @@ impl Parse for EANSUPP {
 
 #[cfg(test)]
 mod tests {
-    use crate::error::Error;
     use crate::sym::ean_supp::*;
     #[cfg(not(feature = "std"))]
     use alloc::string::String;
@@ -180,14 +255,14 @@ mod tests {
     fn invalid_data_ean2() {
         let ean2 = EANSUPP::new("AT");
 
-        assert_eq!(ean2.err().unwrap(), Error::Character);
+        assert_eq!(ean2.err().unwrap(), Error::character(0, 'A'));
     }
 
     #[test]
     fn invalid_len_ean2() {
         let ean2 = EANSUPP::new("123");
 
-        assert_eq!(ean2.err().unwrap(), Error::Length);
+        assert_eq!(ean2.err().unwrap(), Error::lengths(&[2, 5], 3));
     }
 
     #[test]
@@ -206,4 +281,46 @@ mod tests {
             "10110110001010011001010011011010111101010011101"
         );
     }
+
+    #[test]
+    fn supplemented_gap() {
+        let ean13 = EAN13::new("978030640615").unwrap();
+        let upca = UPCA::new("03600029145").unwrap();
+        let supp = EANSUPP::new("51234").unwrap();
+
+        assert!(Supplemented::ean13(&ean13, &supp).gap(12).is_ok());
+        assert!(Supplemented::upca(&upca, &supp).gap(9).is_ok());
+        assert_eq!(
+            Supplemented::ean13(&ean13, &supp).gap(13).err().unwrap(),
+            Error::option("gap", 7..=12, 13)
+        );
+        assert_eq!(
+            Supplemented::upca(&upca, &supp).gap(8).err().unwrap(),
+            Error::option("gap", 9..=12, 8)
+        );
+    }
+
+    #[test]
+    fn supplemented_geometry() {
+        let ean8 = EAN8::new("5512345").unwrap();
+        let supp = EANSUPP::new("34").unwrap();
+        let supplemented = Supplemented::ean8(&ean8, &supp).gap(10).unwrap();
+
+        assert_eq!(supplemented.main(), 0..67);
+        assert_eq!(supplemented.guards(), &[0..3, 31..36, 64..67]);
+        assert_eq!(supplemented.supplement(), 77..97);
+    }
+
+    #[test]
+    fn supplemented_encode() {
+        let ean13 = EAN13::new("978030640615").unwrap();
+        let supp = EANSUPP::new("51234").unwrap();
+        let supplemented = Supplemented::ean13(&ean13, &supp);
+        let encoded = collapse_vec(supplemented.encode());
+
+        assert_eq!(encoded.len(), 95 + 7 + 47);
+        assert_eq!(&encoded[..95], collapse_vec(ean13.encode()));
+        assert_eq!(&encoded[95..102], "0000000");
+        assert_eq!(&encoded[102..], collapse_vec(supp.encode()));
+    }
 }
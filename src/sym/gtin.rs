@@ -0,0 +1,359 @@
+//! GS1 Global Trade Item Numbers (GTINs) and the barcodes that carry them.
+//!
+//! A GTIN has 8, 12, 13 or 14 digits, the last of which is a modulo-10 check digit. The shorter
+//! forms are the longer ones with their leading zeros removed, so every GTIN can be written as a
+//! GTIN-14:
+//!   * GTIN-8 numbers are carried by EAN-8 barcodes.
+//!   * GTIN-12 numbers are carried by UPC-A barcodes.
+//!   * GTIN-13 numbers are carried by EAN-13 barcodes.
+//!   * GTIN-14 numbers begin with an indicator digit for the packaging level, and are carried by
+//!     ITF-14 barcodes.
+//!
+//! Hyphens and spaces in the numbers are ignored. A GTIN-14 with six leading zeros is taken to be
+//! a GTIN-8: as a GTIN-12 it would have four leading zeros, and no GTIN-12 is issued with those.
+//!
+//! ```rust
+//! use barcoders::sym::gtin::*;
+//!
+//! let gtin = Gtin::new("00036000291452").unwrap();
+//!
+//! assert_eq!(gtin.format(), Format::Gtin12);
+//! assert_eq!(gtin.gtin13().unwrap(), "0036000291452");
+//! assert!(matches!(gtin.symbol(), Symbol::UPCA(_)));
+//! ```
+
+use crate::checksum;
+use crate::error::{Error, Result};
+use crate::sym::ean13::EAN13;
+use crate::sym::ean8::EAN8;
+use crate::sym::helpers::{vec, Vec};
+use crate::sym::tf::TF;
+use crate::sym::upca::UPCA;
+use crate::sym::Elements;
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
+/// The formats of GTIN, by their number of digits.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// An eight digit GTIN-8.
+    Gtin8,
+    /// A twelve digit GTIN-12, also known as a UPC.
+    Gtin12,
+    /// A thirteen digit GTIN-13, also known as an EAN.
+    Gtin13,
+    /// A fourteen digit GTIN-14, beginning with an indicator digit.
+    Gtin14,
+}
+
+impl Format {
+    // Returns the number of digits of the format, including the check digit.
+    fn len(self) -> usize {
+        match self {
+            Format::Gtin8 => 8,
+            Format::Gtin12 => 12,
+            Format::Gtin13 => 13,
+            Format::Gtin14 => 14,
+        }
+    }
+}
+
+/// The barcode chosen to carry a GTIN.
+#[derive(Debug)]
+pub enum Symbol {
+    /// An EAN-8 barcode, for a GTIN-8.
+    EAN8(EAN8),
+    /// A UPC-A barcode, for a GTIN-12.
+    UPCA(UPCA),
+    /// An EAN-13 barcode, for a GTIN-13.
+    EAN13(EAN13),
+    /// An ITF-14 barcode, for a GTIN-14.
+    ITF14(TF),
+}
+
+impl Symbol {
+    /// Encodes the barcode.
+    /// Returns a Vec<u8> of binary digits.
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            Symbol::EAN8(ean8) => ean8.encode(),
+            Symbol::UPCA(upca) => upca.encode(),
+            Symbol::EAN13(ean13) => ean13.encode(),
+            Symbol::ITF14(itf14) => itf14.encode(),
+        }
+    }
+
+    /// Encodes the barcode as a sequence of bar and space widths.
+    pub fn elements(&self) -> Elements {
+        match self {
+            Symbol::EAN8(ean8) => ean8.elements(),
+            Symbol::UPCA(upca) => upca.elements(),
+            Symbol::EAN13(ean13) => ean13.elements(),
+            Symbol::ITF14(itf14) => itf14.elements(),
+        }
+    }
+}
+
+/// A GTIN, held as the fourteen digits of its GTIN-14 form.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Gtin([u8; 14]);
+
+impl Gtin {
+    /// Creates a new GTIN from a GTIN-8, GTIN-12, GTIN-13 or GTIN-14, including its check digit.
+    /// Returns Result<Gtin, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<Gtin> {
+        let mut digits = vec![];
+
+        for (i, c) in data.as_ref().chars().enumerate() {
+            match c.to_digit(10) {
+                Some(d) => digits.push(d as u8),
+                None if c == '-' || c == ' ' => {}
+                None => return Err(Error::character(i, c)),
+            }
+        }
+
+        let len = digits.len();
+        if ![8, 12, 13, 14].contains(&len) {
+            return Err(Error::lengths(&[8, 12, 13, 14], len));
+        }
+
+        let check_digit = checksum::gs1_digit(&digits[..len - 1]);
+        if check_digit != digits[len - 1] {
+            return Err(Error::checksum(check_digit, digits[len - 1]));
+        }
+
+        let mut gtin = [0; 14];
+        gtin[14 - len..].copy_from_slice(&digits);
+
+        Ok(Gtin(gtin))
+    }
+
+    /// Returns the shortest format that can hold the GTIN.
+    pub fn format(&self) -> Format {
+        match self.0.iter().position(|&d| d != 0).unwrap_or(14) {
+            6.. => Format::Gtin8,
+            2..=5 => Format::Gtin12,
+            1 => Format::Gtin13,
+            _ => Format::Gtin14,
+        }
+    }
+
+    /// Returns the indicator digit of the GTIN-14 form, which is 0 for the GTINs of trade items
+    /// and 1-8 for the packaging levels above them.
+    pub fn indicator(&self) -> u8 {
+        self.0[0]
+    }
+
+    /// Returns the GTIN-14 with the given indicator digit (0-9) and its check digit recomputed.
+    /// An indicator of 0 returns the GTIN of the trade item itself.
+    /// Returns Result<Gtin, Error> indicating a valid indicator digit.
+    pub fn with_indicator(self, indicator: u8) -> Result<Gtin> {
+        if indicator > 9 {
+            return Err(Error::option("indicator", 0..=9, indicator as usize));
+        }
+
+        let mut gtin = self.0;
+        gtin[0] = indicator;
+        gtin[13] = checksum::gs1_digit(&gtin[..13]);
+
+        Ok(Gtin(gtin))
+    }
+
+    // Returns the last digits of the GTIN-14 as a string, if the digits before them are zeros.
+    fn digits(&self, format: Format) -> Option<String> {
+        let (zeros, digits) = self.0.split_at(14 - format.len());
+
+        zeros
+            .iter()
+            .all(|&d| d == 0)
+            .then(|| digits.iter().map(|&d| (b'0' + d) as char).collect())
+    }
+
+    /// Returns the GTIN as a GTIN-8, if it has six leading zeros as a GTIN-14.
+    pub fn gtin8(&self) -> Option<String> {
+        self.digits(Format::Gtin8)
+    }
+
+    /// Returns the GTIN as a GTIN-12, if it has two leading zeros as a GTIN-14.
+    pub fn gtin12(&self) -> Option<String> {
+        self.digits(Format::Gtin12)
+    }
+
+    /// Returns the GTIN as a GTIN-13, if it has a leading zero as a GTIN-14.
+    pub fn gtin13(&self) -> Option<String> {
+        self.digits(Format::Gtin13)
+    }
+
+    /// Returns the GTIN as a GTIN-14.
+    pub fn gtin14(&self) -> String {
+        self.digits(Format::Gtin14)
+            .expect("Every GTIN is a GTIN-14")
+    }
+
+    /// Returns the GTIN as an EAN-8 barcode, if it can be written as a GTIN-8.
+    pub fn ean8(&self) -> Option<EAN8> {
+        self.gtin8()
+            .map(|data| EAN8::new(data).expect("GTIN-8 is valid EAN-8"))
+    }
+
+    /// Returns the GTIN as a UPC-A barcode, if it can be written as a GTIN-12.
+    pub fn upca(&self) -> Option<UPCA> {
+        self.gtin12()
+            .map(|data| UPCA::new(data).expect("GTIN-12 is valid UPC-A"))
+    }
+
+    /// Returns the GTIN as an EAN-13 barcode, if it can be written as a GTIN-13. A GTIN-12 is
+    /// given a leading zero.
+    pub fn ean13(&self) -> Option<EAN13> {
+        self.gtin13()
+            .map(|data| EAN13::new(data).expect("GTIN-13 is valid EAN-13"))
+    }
+
+    /// Returns the GTIN as an ITF-14 barcode.
+    pub fn itf14(&self) -> TF {
+        TF::interleaved(self.gtin14()).expect("GTIN-14 is valid ITF")
+    }
+
+    /// Returns the barcode for the shortest format that can hold the GTIN: EAN-8 for a GTIN-8,
+    /// UPC-A for a GTIN-12, EAN-13 for a GTIN-13 and ITF-14 for a GTIN-14.
+    pub fn symbol(&self) -> Symbol {
+        match self.format() {
+            Format::Gtin8 => Symbol::EAN8(self.ean8().expect("GTIN-8")),
+            Format::Gtin12 => Symbol::UPCA(self.upca().expect("GTIN-12")),
+            Format::Gtin13 => Symbol::EAN13(self.ean13().expect("GTIN-13")),
+            Format::Gtin14 => Symbol::ITF14(self.itf14()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{Error, Lengths};
+    use crate::sym::gtin::*;
+
+    #[test]
+    fn gtin_formats() {
+        let gtin1 = Gtin::new("55123457").unwrap();
+        let gtin2 = Gtin::new("0 36000 29145 2").unwrap();
+        let gtin3 = Gtin::new("4006381333931").unwrap();
+        let gtin4 = Gtin::new("14006381333938").unwrap();
+
+        assert_eq!(gtin1.format(), Format::Gtin8);
+        assert_eq!(gtin2.format(), Format::Gtin12);
+        assert_eq!(gtin3.format(), Format::Gtin13);
+        assert_eq!(gtin4.format(), Format::Gtin14);
+        assert_eq!(gtin4.indicator(), 1);
+    }
+
+    #[test]
+    fn gtin_normalisation() {
+        let gtin1 = Gtin::new("036000291452").unwrap();
+        let gtin2 = Gtin::new("0036000291452").unwrap();
+        let gtin3 = Gtin::new("00036000291452").unwrap();
+
+        assert_eq!(gtin1, gtin2);
+        assert_eq!(gtin1, gtin3);
+        assert_eq!(gtin3.format(), Format::Gtin12);
+    }
+
+    #[test]
+    fn gtin_leading_zeros() {
+        let gtin1 = Gtin::new("000123456784").unwrap();
+        let gtin2 = Gtin::new("000012345670").unwrap();
+
+        assert_eq!(gtin1.format(), Format::Gtin12);
+        assert_eq!(gtin1.gtin12().unwrap(), "000123456784");
+        assert_eq!(gtin1.gtin14(), "00000123456784");
+        assert!(matches!(gtin1.symbol(), Symbol::UPCA(_)));
+        assert_eq!(gtin2.format(), Format::Gtin8);
+        assert_eq!(gtin2.gtin8().unwrap(), "12345670");
+    }
+
+    #[test]
+    fn gtin_conversions() {
+        let gtin1 = Gtin::new("036000291452").unwrap();
+        let gtin2 = Gtin::new("4006381333931").unwrap();
+
+        assert_eq!(gtin1.gtin8(), None);
+        assert_eq!(gtin1.gtin12().unwrap(), "036000291452");
+        assert_eq!(gtin1.gtin13().unwrap(), "0036000291452");
+        assert_eq!(gtin1.gtin14(), "00036000291452");
+        assert_eq!(gtin2.gtin12(), None);
+        assert_eq!(gtin2.gtin13().unwrap(), "4006381333931");
+        assert_eq!(Gtin::new("55123457").unwrap().gtin8().unwrap(), "55123457");
+    }
+
+    #[test]
+    fn gtin_indicator() {
+        let gtin1 = Gtin::new("4006381333931")
+            .unwrap()
+            .with_indicator(1)
+            .unwrap();
+        let gtin2 = gtin1.with_indicator(2).unwrap();
+        let gtin3 = gtin2.with_indicator(0).unwrap();
+
+        assert_eq!(gtin1.gtin14(), "14006381333938");
+        assert_eq!(gtin2.gtin14(), "24006381333935");
+        assert_eq!(gtin3.gtin13().unwrap(), "4006381333931");
+        assert!(matches!(
+            gtin1.with_indicator(10),
+            Err(Error::Option { name: "indicator", allowed, actual })
+                if allowed == (0.0..=9.0) && actual == 10.0
+        ));
+    }
+
+    #[test]
+    fn invalid_gtin() {
+        let gtin1 = Gtin::new("4006381333932");
+        let gtin2 = Gtin::new("4006381333");
+        let gtin3 = Gtin::new("40063813339A1");
+
+        assert!(matches!(
+            gtin1,
+            Err(Error::Checksum {
+                expected: 1,
+                provided: 2
+            })
+        ));
+        assert!(matches!(
+            gtin2,
+            Err(Error::Length { allowed, actual: 10 }) if allowed == Lengths::Set(&[8, 12, 13, 14])
+        ));
+        assert!(matches!(
+            gtin3,
+            Err(Error::Character {
+                character: 'A',
+                index: 11
+            })
+        ));
+    }
+
+    #[test]
+    fn gtin_symbol() {
+        let gtin1 = Gtin::new("00000055123457").unwrap();
+        let gtin2 = Gtin::new("00036000291452").unwrap();
+        let gtin3 = Gtin::new("04006381333931").unwrap();
+        let gtin4 = Gtin::new("14006381333938").unwrap();
+
+        assert_eq!(
+            gtin1.symbol().encode(),
+            EAN8::new("55123457").unwrap().encode()
+        );
+        assert_eq!(
+            gtin2.symbol().encode(),
+            UPCA::new("036000291452").unwrap().encode()
+        );
+        assert_eq!(
+            gtin3.symbol().encode(),
+            EAN13::new("4006381333931").unwrap().encode()
+        );
+        assert_eq!(
+            gtin4.symbol().encode(),
+            TF::interleaved("14006381333938").unwrap().encode()
+        );
+        assert_eq!(
+            gtin2.ean13().unwrap().encode(),
+            EAN13::new("0036000291452").unwrap().encode()
+        );
+    }
+}
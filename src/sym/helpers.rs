@@ -7,6 +7,20 @@ pub(crate) use std::vec;
 #[cfg(feature = "std")]
 pub(crate) use std::vec::Vec;
 
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
+use crate::error::{Error, Result};
+
+/// Marker used internally to represent the FNC1 separator in GS1 element strings.
+pub const FNC1: char = '\u{1D}';
+
+// First two digits of GS1 AIs that have a predefined length and need no FNC1 separator.
+const PREDEFINED_LENGTH: [&str; 22] = [
+    "00", "01", "02", "03", "04", "11", "12", "13", "14", "15", "16", "17", "18", "19", "20",
+    "31", "32", "33", "34", "35", "36", "41",
+];
+
 /// Joins and flattens the given slice of &[u8] slices into a Vec<u8>.
 /// TODO: Work out how to use join_iters with slices and then remove this function.
 pub fn join_slices(slices: &[&[u8]]) -> Vec<u8> {
@@ -21,28 +35,151 @@ where
     iters.flat_map(|b| b.into_iter()).cloned().collect()
 }
 
-/// Calculates the checksum digit using a modulo-10 weighting algorithm.
-pub fn modulo_10_checksum(data: &[u8], even_start: bool) -> u8 {
-    let mut odds = 0;
-    let mut evens = 0;
+/// Returns the given digits as a string of ASCII digits.
+pub fn digit_string(digits: &[u8]) -> String {
+    digits.iter().map(|&d| (b'0' + d) as char).collect()
+}
+
+// The punctuation allowed in the data of GS1 element strings, alongside ASCII letters and digits.
+const GS1_PUNCTUATION: &str = "!\"%&'()*+,-./:;<=>?_ ";
+
+/// Returns the characters allowed in a bracketed GS1 element string.
+pub fn gs1_chars() -> Vec<char> {
+    ('0'..='9')
+        .chain('A'..='Z')
+        .chain('a'..='z')
+        .chain(GS1_PUNCTUATION.chars())
+        .collect()
+}
+
+/// Splits a bracketed GS1 element string, e.g "(01)12345678901231(10)ABC", into (AI, data) pairs.
+pub fn gs1_elements(data: &str) -> Result<Vec<(&str, &str)>> {
+    let mut elements = vec![];
+    let mut rest = data;
+    let index = |s: &str| char_index(data, s);
+
+    if rest.is_empty() {
+        return Err(Error::length(1..=usize::MAX, 0));
+    }
+
+    while !rest.is_empty() {
+        let close = match (rest.strip_prefix('('), rest.find(')')) {
+            (Some(_), Some(i)) => i,
+            _ => return Err(Error::character(index(rest), rest.chars().next().unwrap())),
+        };
+        let ai = &rest[1..close];
+        let next = rest[close + 1..]
+            .find('(')
+            .map_or(rest.len(), |i| close + 1 + i);
+        let value = &rest[close + 1..next];
 
-    for (i, d) in data.iter().enumerate() {
-        match i % 2 {
-            1 => odds += *d,
-            _ => evens += *d,
+        if let Some(e) = Error::find_character(ai, index(ai), |c| c.is_ascii_digit()) {
+            return Err(e);
         }
+
+        // A missing or extra digit is reported at the character that should not be there.
+        match ai.len() {
+            0..=1 => return Err(Error::character(index(&rest[close..]), ')')),
+            5.. => return Err(Error::character(index(&ai[4..]), ai.as_bytes()[4] as char)),
+            _ => {}
+        }
+
+        if value.is_empty() || value.len() > 90 {
+            return Err(Error::length(1..=90, value.chars().count()));
+        }
+
+        if let Some(e) = Error::find_character(value, index(value), |c| {
+            c.is_ascii_alphanumeric() || GS1_PUNCTUATION.contains(c)
+        }) {
+            return Err(e);
+        }
+
+        elements.push((ai, value));
+        rest = &rest[next..];
     }
 
-    // EAN-13 (and some others?) barcodes use EVEN-first weighting to maintain
-    // backwards compatibility.
-    if even_start {
-        odds *= 3;
-    } else {
-        evens *= 3;
+    Ok(elements)
+}
+
+/// Returns the character index at which a slice of the data begins.
+pub fn char_index(data: &str, slice: &str) -> usize {
+    let start = slice.as_ptr() as usize - data.as_ptr() as usize;
+
+    data[..start].chars().count()
+}
+
+/// Joins GS1 (AI, data) pairs into a single element string, with an FNC1 separator following
+/// each element whose AI does not have a predefined length.
+pub fn gs1_element_string(elements: &[(&str, &str)]) -> Vec<char> {
+    let mut chars = vec![];
+
+    for (i, (ai, data)) in elements.iter().enumerate() {
+        chars.extend(ai.chars());
+        chars.extend(data.chars());
+
+        if i < elements.len() - 1 && !PREDEFINED_LENGTH.contains(&&ai[..2]) {
+            chars.push(FNC1);
+        }
+    }
+
+    chars
+}
+
+/// Calculates the Reed-Solomon error correction codewords for the given data over GF(2^m),
+/// where `poly` is the primitive polynomial of the field. The generator polynomial has the
+/// roots a^first_root to a^(first_root + ecc_len - 1).
+pub fn reed_solomon(data: &[u16], ecc_len: usize, poly: u32, first_root: usize) -> Vec<u16> {
+    let size = 1 << (31 - poly.leading_zeros());
+    let mut exp = vec![0; size * 2];
+    let mut log = vec![0; size];
+    let mut x = 1;
+
+    for (i, e) in exp.iter_mut().take(size - 1).enumerate() {
+        *e = x;
+        log[x as usize] = i;
+        x <<= 1;
+
+        if x & size as u32 != 0 {
+            x ^= poly;
+        }
+    }
+
+    for i in size - 1..size * 2 {
+        exp[i] = exp[i - (size - 1)];
     }
 
-    match 10 - ((odds + evens) % 10) {
-        10 => 0,
-        n => n,
+    let multiply = |a: u32, b: u32| match (a, b) {
+        (0, _) | (_, 0) => 0,
+        _ => exp[log[a as usize] + log[b as usize]],
+    };
+
+    // Generator polynomial coefficients, highest degree first.
+    let mut generator = vec![1];
+
+    for i in 0..ecc_len {
+        let root = exp[(first_root + i) % (size - 1)];
+        let mut next = vec![0; generator.len() + 1];
+
+        for (j, &g) in generator.iter().enumerate() {
+            next[j] ^= g;
+            next[j + 1] ^= multiply(g, root);
+        }
+
+        generator = next;
+    }
+
+    let mut remainder = vec![0; ecc_len];
+
+    for &d in data {
+        let factor = d as u32 ^ remainder[0];
+
+        remainder.remove(0);
+        remainder.push(0);
+
+        for (r, &g) in remainder.iter_mut().zip(generator[1..].iter()) {
+            *r ^= multiply(g, factor);
+        }
     }
+
+    remainder.iter().map(|&r| r as u16).collect()
 }
@@ -0,0 +1,283 @@
+//! Encoder for HIBC barcodes.
+//!
+//! The Health Industry Bar Code (HIBC) standards identify medical products and devices (LIC, the
+//! Labeler Identification Code standard) and the patients, staff and assets of healthcare
+//! providers (PAS, the Provider Applications Standard). The data begins with a "+" flag
+//! character and ends with a modulo-43 check character, and is carried in either a Code39 or a
+//! Code128 barcode.
+//!
+//! A LIC primary data structure holds the four character labeler identification code, a product
+//! or catalogue number of up to 18 characters and a unit of measure digit. The secondary data
+//! structure holds quantities, expiry dates and lot or serial numbers. It can be appended to the
+//! primary data after a "/", or carried in a separate barcode that ends with the check character
+//! of the primary data as a link character.
+
+use crate::checksum;
+use crate::error::{Error, Result};
+use crate::sym::code128::Code128;
+use crate::sym::code39::Code39;
+use crate::sym::{helpers, Parse};
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+use core::ops::Range;
+use helpers::{vec, Vec};
+
+// The flag character that begins all HIBC data.
+const FLAG: char = '+';
+
+/// The HIBC barcode type.
+#[derive(Debug)]
+pub struct HIBC(Vec<char>);
+
+impl HIBC {
+    // Adds the flag and check characters to the data, which begins with the given number of
+    // characters that were not given by the caller.
+    fn init(data: Vec<char>, added: usize) -> Result<HIBC> {
+        let mut chars = vec![FLAG];
+        let data: String = data.iter().collect();
+
+        chars.extend(
+            HIBC::parse(&data)
+                .map_err(|e| e.offset(-(added as isize)))?
+                .chars(),
+        );
+        chars.push(checksum::code39_char(&chars));
+
+        Ok(HIBC(chars))
+    }
+
+    /// Creates a new barcode from data without the flag or check characters.
+    /// Returns Result<HIBC, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<HIBC> {
+        HIBC::init(data.as_ref().chars().collect(), 0)
+    }
+
+    /// Creates a new barcode holding a LIC primary data structure, made up of the labeler
+    /// identification code, the product or catalogue number and the unit of measure (0-9).
+    /// Returns Result<HIBC, Error> indicating parse success.
+    pub fn primary<L: AsRef<str>, P: AsRef<str>>(lic: L, product: P, unit: u8) -> Result<HIBC> {
+        let (lic, product) = (lic.as_ref(), product.as_ref());
+
+        if lic.chars().count() != 4 {
+            return Err(Error::length(4..=4, lic.chars().count()));
+        }
+
+        if !(1..=18).contains(&product.chars().count()) {
+            return Err(Error::length(1..=18, product.chars().count()));
+        }
+
+        // The product or catalogue number is indexed as if it followed the labeler code.
+        let lic_char =
+            |(i, c): (usize, char)| c.is_ascii_alphanumeric() && (i > 0 || c.is_ascii_uppercase());
+        let invalid = lic.chars().enumerate().find(|&p| !lic_char(p));
+
+        if let Some(e) = invalid
+            .map(|(i, c)| Error::character(i, c))
+            .or_else(|| Error::find_character(product, 4, |c| c.is_ascii_alphanumeric()))
+        {
+            return Err(e);
+        }
+
+        // The unit of measure is encoded in one digit.
+        if unit > 9 {
+            return Err(Error::option("unit of measure", 0..=9, unit as usize));
+        }
+
+        let mut data: Vec<char> = lic.chars().chain(product.chars()).collect();
+
+        data.extend(char::from_digit(unit as u32, 10));
+        HIBC::init(data, 0)
+    }
+
+    /// Creates a new barcode holding a PAS data structure, from data without the flag, "/" or
+    /// check characters.
+    /// Returns Result<HIBC, Error> indicating parse success.
+    pub fn pas<T: AsRef<str>>(data: T) -> Result<HIBC> {
+        HIBC::init(
+            Some('/').into_iter().chain(data.as_ref().chars()).collect(),
+            1,
+        )
+    }
+
+    /// Appends the secondary data structure to this primary data structure.
+    /// Returns Result<HIBC, Error> indicating parse success.
+    pub fn with_secondary<T: AsRef<str>>(self, data: T) -> Result<HIBC> {
+        let primary = &self.0[1..self.0.len() - 1];
+
+        HIBC::init(
+            primary
+                .iter()
+                .cloned()
+                .chain(Some('/'))
+                .chain(data.as_ref().chars())
+                .collect(),
+            primary.len() + 1,
+        )
+    }
+
+    /// Creates a new barcode holding the secondary data structure, linked to this primary data
+    /// structure by its check character.
+    /// Returns Result<HIBC, Error> indicating parse success.
+    pub fn secondary<T: AsRef<str>>(&self, data: T) -> Result<HIBC> {
+        HIBC::init(
+            data.as_ref()
+                .chars()
+                .chain(Some(self.check_char()))
+                .collect(),
+            0,
+        )
+    }
+
+    /// Returns the check character.
+    pub fn check_char(&self) -> char {
+        self.0[self.0.len() - 1]
+    }
+
+    /// Returns the data, including the flag and check characters.
+    pub fn data(&self) -> String {
+        self.0.iter().collect()
+    }
+
+    /// Returns the human readable text, which is the same as the data.
+    pub fn text(&self) -> String {
+        self.data()
+    }
+
+    /// Returns the data as a Code39 barcode.
+    pub fn code39(&self) -> Code39 {
+        Code39::new(self.data()).expect("HIBC data is valid Code39")
+    }
+
+    /// Returns the data as a Code128 barcode, using character-set A.
+    pub fn code128(&self) -> Code128 {
+        let data: String = Some('À')
+            .into_iter()
+            .chain(self.0.iter().cloned())
+            .collect();
+
+        Code128::new(data).expect("HIBC data is valid Code128")
+    }
+}
+
+impl Parse for HIBC {
+    /// Returns the valid length of data acceptable in this type of barcode.
+    fn valid_len() -> Range<u32> {
+        1..110
+    }
+
+    /// Returns the set of valid characters allowed in this type of barcode.
+    fn valid_chars() -> Vec<char> {
+        let mut chars: Vec<char> = ('0'..='9').chain('A'..='Z').collect();
+
+        chars.extend(['-', '.', ' ', '$', '/', '+', '%']);
+        chars
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::sym::hibc::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::string::String;
+
+    #[test]
+    fn new_hibc() {
+        let hibc1 = HIBC::new("A123BJC5D6E71");
+        let hibc2 = HIBC::primary("A123", "BJC5D6E7", 1);
+        let hibc3 = HIBC::pas("A123 4567");
+        let hibc4 = HIBC::primary("A123", String::from("BJC5D6E7"), 1);
+
+        let hibc1 = hibc1.unwrap();
+
+        assert_eq!(hibc1.check_char(), 'G');
+        assert_eq!(hibc1.data(), "+A123BJC5D6E71G");
+        assert_eq!(hibc1.text(), "+A123BJC5D6E71G");
+        assert_eq!(hibc2.unwrap().data(), "+A123BJC5D6E71G");
+        assert_eq!(hibc3.unwrap().data(), "+/A123 4567S");
+        assert_eq!(hibc4.unwrap().data(), "+A123BJC5D6E71G");
+    }
+
+    #[test]
+    fn invalid_data_hibc() {
+        let hibc1 = HIBC::new("a123");
+        let hibc2 = HIBC::primary("1234", "BJC5D6E7", 1);
+        let hibc3 = HIBC::primary("A123", "BJC5-6E7", 1);
+        let hibc4 = HIBC::primary("A123", "BJC5D6E7", 10);
+
+        assert_eq!(hibc1.err().unwrap(), Error::character(0, 'a'));
+        assert_eq!(hibc2.err().unwrap(), Error::character(0, '1'));
+        assert_eq!(hibc3.err().unwrap(), Error::character(8, '-'));
+        assert!(matches!(
+            hibc4,
+            Err(Error::Option { allowed, actual, .. }) if allowed == (0.0..=9.0) && actual == 10.0
+        ));
+    }
+
+    #[test]
+    fn invalid_data_position_hibc() {
+        let primary = HIBC::new("A123BJC5D6E71").unwrap();
+        let hibc1 = HIBC::pas("A123 456#");
+        let hibc2 = primary.with_secondary("$$5001H1a");
+        let hibc3 = HIBC::primary("A123", "BJC5-6E7", 1);
+
+        assert!(matches!(
+            hibc1,
+            Err(Error::Character {
+                character: '#',
+                index: 8
+            })
+        ));
+        assert!(matches!(
+            hibc2,
+            Err(Error::Character {
+                character: 'a',
+                index: 8
+            })
+        ));
+        assert!(matches!(
+            hibc3,
+            Err(Error::Character {
+                character: '-',
+                index: 8
+            })
+        ));
+    }
+
+    #[test]
+    fn invalid_len_hibc() {
+        let hibc1 = HIBC::new("");
+        let hibc2 = HIBC::primary("A12", "BJC5D6E7", 1);
+        let hibc3 = HIBC::primary("A123", "", 1);
+        let hibc4 = HIBC::primary("A123", "1234567890123456789", 1);
+
+        assert_eq!(hibc1.err().unwrap(), Error::length(1..=110, 0));
+        assert_eq!(hibc2.err().unwrap(), Error::length(4..=4, 3));
+        assert_eq!(hibc3.err().unwrap(), Error::length(1..=18, 0));
+        assert_eq!(hibc4.err().unwrap(), Error::length(1..=18, 19));
+    }
+
+    #[test]
+    fn hibc_secondary() {
+        let primary = HIBC::primary("A123", "BJC5D6E7", 1).unwrap();
+        let secondary = primary.secondary("$$52001510X3").unwrap();
+        let concatenated = primary.with_secondary("$$52001510X3").unwrap();
+
+        assert_eq!(secondary.data(), "+$$52001510X3GD");
+        assert_eq!(concatenated.data(), "+A123BJC5D6E71/$$52001510X3C");
+    }
+
+    #[test]
+    fn hibc_encode() {
+        let hibc = HIBC::primary("A123", "BJC5D6E7", 1).unwrap();
+
+        assert_eq!(
+            hibc.code39().encode(),
+            Code39::new("+A123BJC5D6E71G").unwrap().encode()
+        );
+        assert_eq!(
+            hibc.code128().encode(),
+            Code128::new("À+A123BJC5D6E71G").unwrap().encode()
+        );
+    }
+}
@@ -0,0 +1,322 @@
+//! Conversions from book, serial and printed music numbers to EAN-13 barcodes.
+//!
+//! Books, magazines and sheet music carry EAN-13 barcodes made from their standard numbers:
+//!   * ISBN-10 numbers become Bookland barcodes beginning with 978. ISBN-13 numbers already are
+//!     Bookland numbers, beginning with 978 or 979.
+//!   * ISSN numbers for serials become barcodes beginning with 977, followed by a two digit issue
+//!     variant (normally 00).
+//!   * ISMN numbers for printed music become barcodes beginning with 9790.
+//!
+//! Hyphens and spaces in the numbers are ignored, and check digits (including the `X` check
+//! character of ISBN-10 and ISSN) are validated. A price or issue number can be added as an EAN-5
+//! or EAN-2 supplemental barcode.
+
+use crate::checksum::modulo_11_digit;
+use crate::error::{Error, Result};
+use crate::sym::ean13::{Bookland, EAN13};
+use crate::sym::ean_supp::EANSUPP;
+use crate::sym::helpers::{vec, Vec};
+
+// Returns the digits of the number, ignoring hyphens and spaces. A trailing X is returned as 10.
+fn digits(data: &str, prefix: Option<char>) -> Result<Vec<u8>> {
+    let mut chars = data
+        .chars()
+        .enumerate()
+        .filter(|&(_, c)| !is_separator(c))
+        .peekable();
+    let mut digits = vec![];
+
+    if let Some(prefix) = prefix {
+        if chars
+            .next_if(|&(_, c)| c.eq_ignore_ascii_case(&prefix))
+            .is_none()
+        {
+            let (i, c) = chars.next().unwrap_or((0, ' '));
+            return Err(Error::character(i, c));
+        }
+    }
+
+    while let Some((i, c)) = chars.next() {
+        match (c, c.to_digit(10)) {
+            (_, Some(d)) => digits.push(d as u8),
+            ('X' | 'x', _) if chars.peek().is_none() => digits.push(10),
+            _ => return Err(Error::character(i, c)),
+        }
+    }
+
+    Ok(digits)
+}
+
+fn is_separator(c: char) -> bool {
+    c == '-' || c == ' '
+}
+
+// Returns an invalid character error for the nth digit of the number, given the number of
+// characters that stand in for digits before it.
+fn digit_error(data: &str, n: usize, implied: usize) -> Error {
+    let (i, c) = data
+        .chars()
+        .enumerate()
+        .filter(|&(_, c)| !is_separator(c))
+        .nth(n - implied)
+        .expect("Digit exists");
+
+    Error::character(i, c)
+}
+
+// Returns the digits as an EAN-13, checking any given check digit. The digits begin with the
+// given number of digits that are not in the data.
+fn ean13(data: &str, digits: &[u8], implied: usize) -> Result<EAN13> {
+    if let Some(n) = digits.iter().position(|&d| d == 10) {
+        return Err(digit_error(data, n, implied));
+    }
+
+    let ean13: Vec<u8> = digits.iter().map(|d| b'0' + d).collect();
+
+    EAN13::new(core::str::from_utf8(&ean13).expect("Digits are ASCII"))
+}
+
+/// An ISBN, ISSN or ISMN converted to an EAN-13 barcode, with an optional supplemental barcode.
+#[derive(Debug)]
+pub struct Publication {
+    ean13: EAN13,
+    addon: Option<EANSUPP>,
+}
+
+impl Publication {
+    /// Creates a new Bookland barcode from an ISBN-10 or ISBN-13. ISBN-13s beginning with 979-0
+    /// are rejected, as those are ISMNs and are created with `ismn`.
+    /// Returns Result<Publication, Error> indicating parse success.
+    pub fn isbn<T: AsRef<str>>(data: T) -> Result<Publication> {
+        let data = data.as_ref();
+        let digits = digits(data, None)?;
+
+        let ean13: Bookland = match digits.len() {
+            10 if modulo_11_digit(&digits[..9]) != digits[9] => {
+                return Err(Error::checksum(modulo_11_digit(&digits[..9]), digits[9]))
+            }
+            10 => ean13(data, &[&[9, 7, 8], &digits[..9]].concat(), 3)?,
+            // 979-0 is the prefix of ISMNs.
+            13 if digits[..4] == [9, 7, 9, 0] => return Err(digit_error(data, 3, 0)),
+            13 if digits[..3] == [9, 7, 8] || digits[..3] == [9, 7, 9] => ean13(data, &digits, 0)?,
+            13 => {
+                let n = if digits[..2] == [9, 7] { 2 } else { 0 };
+                return Err(digit_error(data, n, 0));
+            }
+            len => return Err(Error::lengths(&[10, 13], len)),
+        };
+
+        Ok(Publication { ean13, addon: None })
+    }
+
+    /// Creates a new barcode from an ISSN and the issue variant (0-99).
+    /// Returns Result<Publication, Error> indicating parse success.
+    pub fn issn<T: AsRef<str>>(data: T, variant: u8) -> Result<Publication> {
+        let data = data.as_ref();
+        let digits = digits(data, None)?;
+
+        if digits.len() != 8 {
+            return Err(Error::length(8..=8, digits.len()));
+        }
+
+        // The variant is encoded in two digits.
+        if variant > 99 {
+            return Err(Error::option("variant", 0..=99, variant as usize));
+        }
+
+        if modulo_11_digit(&digits[..7]) != digits[7] {
+            return Err(Error::checksum(modulo_11_digit(&digits[..7]), digits[7]));
+        }
+
+        let ean13 = ean13(
+            data,
+            &[&[9, 7, 7], &digits[..7], &[variant / 10, variant % 10]].concat(),
+            3,
+        )?;
+
+        Ok(Publication { ean13, addon: None })
+    }
+
+    /// Creates a new barcode from an ISMN, either in the ten character form beginning with M or
+    /// the thirteen digit form beginning with 9790.
+    /// Returns Result<Publication, Error> indicating parse success.
+    pub fn ismn<T: AsRef<str>>(data: T) -> Result<Publication> {
+        let data = data.as_ref();
+
+        // The M of the ten character form stands for 979-0.
+        let (digits, implied) = if data.trim_start().starts_with(['M', 'm']) {
+            ([&[9, 7, 9, 0], &digits(data, Some('M'))?[..]].concat(), 3)
+        } else {
+            (digits(data, None)?, 0)
+        };
+
+        if digits.len() != 13 {
+            return Err(Error::lengths(&[10, 13], digits.len() - implied));
+        }
+
+        if let Some(n) = (0..4).find(|&n| digits[n] != [9, 7, 9, 0][n]) {
+            return Err(digit_error(data, n, 0));
+        }
+
+        let ean13 = ean13(data, &digits, implied)?;
+
+        Ok(Publication { ean13, addon: None })
+    }
+
+    /// Adds a supplemental barcode, usually the five digit price of a book or the two digit issue
+    /// number of a serial.
+    /// Returns Result<Publication, Error> indicating parse success.
+    pub fn with_addon<T: AsRef<str>>(self, data: T) -> Result<Publication> {
+        EANSUPP::new(data).map(|addon| Publication {
+            addon: Some(addon),
+            ..self
+        })
+    }
+
+    /// Returns the EAN-13 barcode.
+    pub fn ean13(&self) -> &EAN13 {
+        &self.ean13
+    }
+
+    /// Returns the supplemental barcode, if there is one.
+    pub fn addon(&self) -> Option<&EANSUPP> {
+        self.addon.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::sym::isbn::*;
+
+    fn same(publication: Publication, ean13: &str) -> bool {
+        publication.ean13().encode() == EAN13::new(ean13).unwrap().encode()
+    }
+
+    #[test]
+    fn isbn_publication() {
+        let isbn1 = Publication::isbn("0-306-40615-2").unwrap();
+        let isbn2 = Publication::isbn("978-0-306-40615-7").unwrap();
+        let isbn3 = Publication::isbn("979 10 90636 07 1").unwrap();
+        let isbn4 = Publication::isbn("0-8044-2957-X").unwrap();
+
+        assert!(same(isbn1, "9780306406157"));
+        assert!(same(isbn2, "9780306406157"));
+        assert!(same(isbn3, "9791090636071"));
+        assert!(same(isbn4, "9780804429573"));
+    }
+
+    #[test]
+    fn invalid_isbn() {
+        let isbn1 = Publication::isbn("0-306-40615-3");
+        let isbn2 = Publication::isbn("978-0-306-40615-8");
+        let isbn3 = Publication::isbn("977-0-306-40615-7");
+        let isbn4 = Publication::isbn("0-306-4061X-2");
+        let isbn5 = Publication::isbn("0-306-40615");
+
+        assert_eq!(isbn1.err().unwrap(), Error::checksum(2, 3));
+        assert_eq!(isbn2.err().unwrap(), Error::checksum(7, 8));
+        assert_eq!(isbn3.err().unwrap(), Error::character(2, '7'));
+        assert_eq!(isbn4.err().unwrap(), Error::character(10, 'X'));
+        assert_eq!(isbn5.err().unwrap(), Error::lengths(&[10, 13], 9));
+    }
+
+    #[test]
+    fn invalid_isbn_position() {
+        let isbn1 = Publication::isbn("0-306-4061X-2");
+        let isbn2 = Publication::isbn("977-0-306-40615-7");
+        let isbn3 = Publication::isbn("0-306-40615-3");
+        let isbn4 = Publication::isbn("979-0-2600-0043-8");
+
+        assert!(matches!(
+            isbn1,
+            Err(Error::Character {
+                character: 'X',
+                index: 10
+            })
+        ));
+        assert!(matches!(
+            isbn2,
+            Err(Error::Character {
+                character: '7',
+                index: 2
+            })
+        ));
+        assert!(matches!(
+            isbn3,
+            Err(Error::Checksum {
+                expected: 2,
+                provided: 3
+            })
+        ));
+        assert!(matches!(
+            isbn4,
+            Err(Error::Character {
+                character: '0',
+                index: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn issn_publication() {
+        let issn1 = Publication::issn("0317-8471", 0).unwrap();
+        let issn2 = Publication::issn("2049-3630", 15).unwrap();
+        let issn3 = Publication::issn("1050-124X", 0).unwrap();
+
+        assert!(same(issn1, "9770317847001"));
+        assert!(same(issn2, "9772049363156"));
+        assert!(same(issn3, "9771050124008"));
+    }
+
+    #[test]
+    fn invalid_issn() {
+        let issn1 = Publication::issn("0317-8472", 0);
+        let issn2 = Publication::issn("0317-847", 0);
+        let issn3 = Publication::issn("0317-8471", 100);
+
+        assert_eq!(issn1.err().unwrap(), Error::checksum(1, 2));
+        assert_eq!(issn2.err().unwrap(), Error::length(8..=8, 7));
+        assert!(matches!(
+            issn3,
+            Err(Error::Option { allowed, actual, .. }) if allowed == (0.0..=99.0) && actual == 100.0
+        ));
+    }
+
+    #[test]
+    fn ismn_publication() {
+        let ismn1 = Publication::ismn("M-2306-7118-7").unwrap();
+        let ismn2 = Publication::ismn("979-0-2306-7118-7").unwrap();
+
+        assert!(same(ismn1, "9790230671187"));
+        assert!(same(ismn2, "9790230671187"));
+    }
+
+    #[test]
+    fn invalid_ismn() {
+        let ismn1 = Publication::ismn("M-2306-7118-8");
+        let ismn2 = Publication::ismn("978-0-2306-7118-7");
+        let ismn3 = Publication::ismn("M-2306-7118");
+
+        assert_eq!(ismn1.err().unwrap(), Error::checksum(7, 8));
+        assert_eq!(ismn2.err().unwrap(), Error::character(2, '8'));
+        assert_eq!(ismn3.err().unwrap(), Error::lengths(&[10, 13], 9));
+    }
+
+    #[test]
+    fn publication_addon() {
+        let isbn1 = Publication::isbn("0-306-40615-2").unwrap();
+        let isbn2 = Publication::isbn("0-306-40615-2").unwrap();
+        let isbn3 = Publication::isbn("0-306-40615-2").unwrap();
+
+        assert!(isbn1.addon().is_none());
+        assert_eq!(
+            isbn2.with_addon("51299").unwrap().addon().unwrap().encode(),
+            EANSUPP::new("51299").unwrap().encode()
+        );
+        assert_eq!(
+            isbn3.with_addon("512").err().unwrap(),
+            Error::lengths(&[2, 5], 3)
+        );
+    }
+}
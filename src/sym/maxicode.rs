@@ -0,0 +1,666 @@
+//! Encoder for MaxiCode barcodes.
+//!
+//! MaxiCode is a fixed-size two-dimensional matrix symbology defined by ISO/IEC 16023 and used
+//! for sorting parcels. Every symbol is 33 rows of hexagonal modules arranged around a circular
+//! bullseye finder pattern, holding 144 six-bit codewords.
+//!
+//! The mode sets the structure and error correction of the symbol:
+//!
+//! * Modes 2 and 3 begin with a structured carrier message of a numeric (mode 2) or
+//!   alphanumeric (mode 3) postal code, a country code and a class of service. The rest of the
+//!   data follows in a secondary message of up to 84 codewords.
+//! * Mode 4 is the standard mode, holding up to 93 codewords.
+//! * Mode 5 has enhanced error correction and holds up to 77 codewords.
+//! * Mode 6 programs the reader and otherwise matches mode 4.
+//!
+//! Data is encoded using the five code sets, shifting and latching between them as needed to
+//! minimise the number of codewords, with runs of nine digits packed into six codewords.
+//! Characters in the ISO/IEC 8859-1 (Latin-1) range are supported.
+
+use crate::error::{Error, Result};
+use crate::sym::helpers::{self, vec, Vec};
+use crate::sym::Matrix;
+
+// The Galois field polynomial, x^6 + x + 1.
+const POLY: u32 = 0x43;
+
+// Codeword values of the numeric shift, padding and latches.
+const NUMERIC_SHIFT: u8 = 31;
+const PAD: u8 = 33;
+const LATCH_A: u8 = 58;
+const LATCH_B: u8 = 63;
+
+const SETS: [Set; 5] = [Set::A, Set::B, Set::C, Set::D, Set::E];
+
+// Bit numbers of the modules in each row, with the most significant bit of the first codeword
+// as bit 0. The dark modules of the orientation pattern are -1, and -2 are always light.
+const MODULES: [[i16; 30]; 33] = [
+    [
+        121, 120, 127, 126, 133, 132, 139, 138, 145, 144, 151, 150, 157, 156, 163, 162, 169, 168,
+        175, 174, 181, 180, 187, 186, 193, 192, 199, 198, -1, -1,
+    ],
+    [
+        123, 122, 129, 128, 135, 134, 141, 140, 147, 146, 153, 152, 159, 158, 165, 164, 171, 170,
+        177, 176, 183, 182, 189, 188, 195, 194, 201, 200, 816, -2,
+    ],
+    [
+        125, 124, 131, 130, 137, 136, 143, 142, 149, 148, 155, 154, 161, 160, 167, 166, 173, 172,
+        179, 178, 185, 184, 191, 190, 197, 196, 203, 202, 818, 817,
+    ],
+    [
+        283, 282, 277, 276, 271, 270, 265, 264, 259, 258, 253, 252, 247, 246, 241, 240, 235, 234,
+        229, 228, 223, 222, 217, 216, 211, 210, 205, 204, 819, -2,
+    ],
+    [
+        285, 284, 279, 278, 273, 272, 267, 266, 261, 260, 255, 254, 249, 248, 243, 242, 237, 236,
+        231, 230, 225, 224, 219, 218, 213, 212, 207, 206, 821, 820,
+    ],
+    [
+        287, 286, 281, 280, 275, 274, 269, 268, 263, 262, 257, 256, 251, 250, 245, 244, 239, 238,
+        233, 232, 227, 226, 221, 220, 215, 214, 209, 208, 822, -2,
+    ],
+    [
+        289, 288, 295, 294, 301, 300, 307, 306, 313, 312, 319, 318, 325, 324, 331, 330, 337, 336,
+        343, 342, 349, 348, 355, 354, 361, 360, 367, 366, 824, 823,
+    ],
+    [
+        291, 290, 297, 296, 303, 302, 309, 308, 315, 314, 321, 320, 327, 326, 333, 332, 339, 338,
+        345, 344, 351, 350, 357, 356, 363, 362, 369, 368, 825, -2,
+    ],
+    [
+        293, 292, 299, 298, 305, 304, 311, 310, 317, 316, 323, 322, 329, 328, 335, 334, 341, 340,
+        347, 346, 353, 352, 359, 358, 365, 364, 371, 370, 827, 826,
+    ],
+    [
+        409, 408, 403, 402, 397, 396, 391, 390, 79, 78, -1, -1, 13, 12, 37, 36, 2, -2, 44, 43, 109,
+        108, 385, 384, 379, 378, 373, 372, 828, -2,
+    ],
+    [
+        411, 410, 405, 404, 399, 398, 393, 392, 81, 80, 40, -1, 15, 14, 39, 38, 3, -2, -2, 45, 111,
+        110, 387, 386, 381, 380, 375, 374, 830, 829,
+    ],
+    [
+        413, 412, 407, 406, 401, 400, 395, 394, 83, 82, 41, -2, -2, -2, -2, -2, 5, 4, 47, 46, 113,
+        112, 389, 388, 383, 382, 377, 376, 831, -2,
+    ],
+    [
+        415, 414, 421, 420, 427, 426, 103, 102, 55, 54, 16, -2, -2, -2, -2, -2, -2, -2, 20, 19, 85,
+        84, 433, 432, 439, 438, 445, 444, 833, 832,
+    ],
+    [
+        417, 416, 423, 422, 429, 428, 105, 104, 57, 56, -2, -2, -2, -2, -2, -2, -2, -2, 22, 21, 87,
+        86, 435, 434, 441, 440, 447, 446, 834, -2,
+    ],
+    [
+        419, 418, 425, 424, 431, 430, 107, 106, 59, 58, -2, -2, -2, -2, -2, -2, -2, -2, -2, 23, 89,
+        88, 437, 436, 443, 442, 449, 448, 836, 835,
+    ],
+    [
+        481, 480, 475, 474, 469, 468, 48, -1, 30, -2, -2, -2, -2, -2, -2, -2, -2, -2, -2, 0, 53,
+        52, 463, 462, 457, 456, 451, 450, 837, -2,
+    ],
+    [
+        483, 482, 477, 476, 471, 470, 49, -2, -1, -2, -2, -2, -2, -2, -2, -2, -2, -2, -2, -2, -1,
+        -2, 465, 464, 459, 458, 453, 452, 839, 838,
+    ],
+    [
+        485, 484, 479, 478, 473, 472, 51, 50, 31, -2, -2, -2, -2, -2, -2, -2, -2, -2, -2, 1, -1,
+        42, 467, 466, 461, 460, 455, 454, 840, -2,
+    ],
+    [
+        487, 486, 493, 492, 499, 498, 97, 96, 61, 60, -2, -2, -2, -2, -2, -2, -2, -2, -2, 26, 91,
+        90, 505, 504, 511, 510, 517, 516, 842, 841,
+    ],
+    [
+        489, 488, 495, 494, 501, 500, 99, 98, 63, 62, -2, -2, -2, -2, -2, -2, -2, -2, 28, 27, 93,
+        92, 507, 506, 513, 512, 519, 518, 843, -2,
+    ],
+    [
+        491, 490, 497, 496, 503, 502, 101, 100, 65, 64, 17, -2, -2, -2, -2, -2, -2, -2, 18, 29, 95,
+        94, 509, 508, 515, 514, 521, 520, 845, 844,
+    ],
+    [
+        559, 558, 553, 552, 547, 546, 541, 540, 73, 72, 32, -2, -2, -2, -2, -2, -2, 10, 67, 66,
+        115, 114, 535, 534, 529, 528, 523, 522, 846, -2,
+    ],
+    [
+        561, 560, 555, 554, 549, 548, 543, 542, 75, 74, -1, -2, 7, 6, 35, 34, 11, -1, 69, 68, 117,
+        116, 537, 536, 531, 530, 525, 524, 848, 847,
+    ],
+    [
+        563, 562, 557, 556, 551, 550, 545, 544, 77, 76, -1, 33, 9, 8, 25, 24, -2, -1, 71, 70, 119,
+        118, 539, 538, 533, 532, 527, 526, 849, -2,
+    ],
+    [
+        565, 564, 571, 570, 577, 576, 583, 582, 589, 588, 595, 594, 601, 600, 607, 606, 613, 612,
+        619, 618, 625, 624, 631, 630, 637, 636, 643, 642, 851, 850,
+    ],
+    [
+        567, 566, 573, 572, 579, 578, 585, 584, 591, 590, 597, 596, 603, 602, 609, 608, 615, 614,
+        621, 620, 627, 626, 633, 632, 639, 638, 645, 644, 852, -2,
+    ],
+    [
+        569, 568, 575, 574, 581, 580, 587, 586, 593, 592, 599, 598, 605, 604, 611, 610, 617, 616,
+        623, 622, 629, 628, 635, 634, 641, 640, 647, 646, 854, 853,
+    ],
+    [
+        727, 726, 721, 720, 715, 714, 709, 708, 703, 702, 697, 696, 691, 690, 685, 684, 679, 678,
+        673, 672, 667, 666, 661, 660, 655, 654, 649, 648, 855, -2,
+    ],
+    [
+        729, 728, 723, 722, 717, 716, 711, 710, 705, 704, 699, 698, 693, 692, 687, 686, 681, 680,
+        675, 674, 669, 668, 663, 662, 657, 656, 651, 650, 857, 856,
+    ],
+    [
+        731, 730, 725, 724, 719, 718, 713, 712, 707, 706, 701, 700, 695, 694, 689, 688, 683, 682,
+        677, 676, 671, 670, 665, 664, 659, 658, 653, 652, 858, -2,
+    ],
+    [
+        733, 732, 739, 738, 745, 744, 751, 750, 757, 756, 763, 762, 769, 768, 775, 774, 781, 780,
+        787, 786, 793, 792, 799, 798, 805, 804, 811, 810, 860, 859,
+    ],
+    [
+        735, 734, 741, 740, 747, 746, 753, 752, 759, 758, 765, 764, 771, 770, 777, 776, 783, 782,
+        789, 788, 795, 794, 801, 800, 807, 806, 813, 812, 861, -2,
+    ],
+    [
+        737, 736, 743, 742, 749, 748, 755, 754, 761, 760, 767, 766, 773, 772, 779, 778, 785, 784,
+        791, 790, 797, 796, 803, 802, 809, 808, 815, 814, 863, 862,
+    ],
+];
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Set {
+    A,
+    B,
+    C,
+    D,
+    E,
+}
+
+impl Set {
+    // Returns the value of the character in this code set.
+    fn value(self, c: u8) -> Option<u8> {
+        let value = match (self, c) {
+            (Set::A | Set::B | Set::C | Set::D, 0x1C..=0x1E) => c,
+            (Set::A, b'\r') => 0,
+            (Set::A, b'A'..=b'Z') => c - 64,
+            (Set::A, b' ') => 32,
+            (Set::A, b'"'..=b':') => c,
+            (Set::B, b'`') => 0,
+            (Set::B, b'a'..=b'z') => c - 96,
+            (Set::B, b'{') => 32,
+            (Set::B, b'}'..=0x7F) => c - 91,
+            (Set::B, b';'..=b'?') => c - 22,
+            (Set::B, b'['..=b'_') => c - 49,
+            (Set::B, b' ') => 47,
+            (Set::B, b',') => 48,
+            (Set::B, b'.'..=b'/') => c + 3,
+            (Set::B, b':') => 51,
+            (Set::B, b'@') => 52,
+            (Set::B, b'!') => 53,
+            (Set::B, b'|') => 54,
+            (Set::C, 0xC0..=0xDA) => c - 0xC0,
+            (Set::C, 0xDB..=0xDF) => c - 0xDB + 32,
+            (Set::C, 0xAA) => 37,
+            (Set::C, 0xAC) => 38,
+            (Set::C, 0xB1..=0xB3) => c - 0xB1 + 39,
+            (Set::C, 0xB5) => 42,
+            (Set::C, 0xB9..=0xBA) => c - 0xB9 + 43,
+            (Set::C, 0xBC..=0xBE) => c - 0xBC + 45,
+            (Set::C, 0x80..=0x89) => c - 0x80 + 48,
+            (Set::D, 0xE0..=0xFA) => c - 0xE0,
+            (Set::D, 0xFB..=0xFF) => c - 0xFB + 32,
+            (Set::D, 0xA1) => 37,
+            (Set::D, 0xA8) => 38,
+            (Set::D, 0xAB) => 39,
+            (Set::D, 0xAF..=0xB0) => c - 0xAF + 40,
+            (Set::D, 0xB4) => 42,
+            (Set::D, 0xB7..=0xB8) => c - 0xB7 + 43,
+            (Set::D, 0xBB) => 45,
+            (Set::D, 0xBF) => 46,
+            (Set::D, 0x8A..=0x94) => c - 0x8A + 47,
+            (Set::E, 0x00..=0x1A) => c,
+            (Set::E, 0x1B) => 30,
+            (Set::E, 0x1C..=0x1F) => c + 4,
+            (Set::E, 0x9F..=0xA0) => c - 0x9F + 36,
+            (Set::E, 0xA2..=0xA7) => c - 0xA2 + 38,
+            (Set::E, 0xA9) => 44,
+            (Set::E, 0xAD..=0xAE) => c - 0xAD + 45,
+            (Set::E, 0xB6) => 47,
+            (Set::E, 0x95..=0x9E) => c - 0x95 + 48,
+            (Set::C | Set::D | Set::E, b' ') => 59,
+            _ => return None,
+        };
+
+        Some(value)
+    }
+
+    // Returns the value that shifts to the given code set for one character.
+    fn shift(self, to: Set) -> Option<u8> {
+        match (self, to) {
+            (Set::B, Set::A) | (Set::A, Set::B) => Some(59),
+            (_, Set::A | Set::B) => None,
+            (from, to) if from == to => None,
+            (_, to) => Some(to as u8 + 58),
+        }
+    }
+
+    // Returns the values that latch or lock into the given code set.
+    fn latch(self, to: Set) -> &'static [u8] {
+        match (self, to) {
+            (from, to) if from == to => &[],
+            (Set::B, Set::A) => &[LATCH_B],
+            (_, Set::A) => &[LATCH_A],
+            (_, Set::B) => &[LATCH_B],
+            (_, Set::C) => &[60, 60],
+            (_, Set::D) => &[61, 61],
+            (_, Set::E) => &[62, 62],
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Edge {
+    Char,
+    Shift(Set),
+    ShiftA(usize),
+    Numeric,
+}
+
+fn relax(slot: &mut Option<(usize, Edge)>, cost: usize, edge: Edge) {
+    if slot.is_none_or(|(c, _)| cost < c) {
+        *slot = Some((cost, edge));
+    }
+}
+
+// Encodes the data as codewords, choosing the code sets that produce the fewest.
+// Returns the codewords and the code set they finish in.
+fn encode_data(data: &[u8]) -> (Vec<u8>, Set) {
+    let n = data.len();
+    // The cheapest way to reach each position in each code set, by the edge that ended there,
+    // and by the code set latched from before continuing.
+    let mut arrive: Vec<[Option<(usize, Edge)>; 5]> = vec![[None; 5]; n + 1];
+    let mut latched: Vec<[Option<(usize, Set)>; 5]> = vec![[None; 5]; n + 1];
+
+    arrive[0][Set::A as usize] = Some((0, Edge::Char));
+
+    for i in 0..=n {
+        for to in SETS {
+            for from in SETS {
+                if let Some((cost, _)) = arrive[i][from as usize] {
+                    let cost = cost + from.latch(to).len();
+
+                    if latched[i][to as usize].is_none_or(|(c, _)| cost < c) {
+                        latched[i][to as usize] = Some((cost, from));
+                    }
+                }
+            }
+        }
+
+        if i == n {
+            break;
+        }
+
+        let c = data[i];
+        let digits = data[i..]
+            .iter()
+            .take(9)
+            .take_while(|d| d.is_ascii_digit())
+            .count();
+
+        for set in SETS {
+            let Some((cost, _)) = latched[i][set as usize] else {
+                continue;
+            };
+            let next = &mut arrive[i + 1][set as usize];
+
+            if set.value(c).is_some() {
+                relax(next, cost + 1, Edge::Char);
+            }
+
+            for to in SETS {
+                if set.shift(to).is_some() && to.value(c).is_some() {
+                    relax(next, cost + 2, Edge::Shift(to));
+                }
+            }
+
+            if digits == 9 {
+                relax(&mut arrive[i + 9][set as usize], cost + 6, Edge::Numeric);
+            }
+
+            if set == Set::B {
+                for len in 2..=3 {
+                    if data[i..]
+                        .iter()
+                        .take(len)
+                        .filter(|&&c| Set::A.value(c).is_some())
+                        .count()
+                        == len
+                    {
+                        relax(
+                            &mut arrive[i + len][set as usize],
+                            cost + len + 1,
+                            Edge::ShiftA(len),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    // Walk back from the cheapest final code set, collecting the codewords of each step.
+    let last = SETS
+        .into_iter()
+        .min_by_key(|&s| arrive[n][s as usize].map_or(usize::MAX, |(c, _)| c))
+        .unwrap_or(Set::A);
+    let mut set = last;
+    let mut steps: Vec<Vec<u8>> = vec![];
+    let mut i = n;
+
+    while i > 0 {
+        let (_, edge) = arrive[i][set as usize].unwrap();
+        let mut step = vec![];
+
+        i -= match edge {
+            Edge::Char => 1,
+            Edge::Shift(_) => 1,
+            Edge::ShiftA(len) => len,
+            Edge::Numeric => 9,
+        };
+
+        match edge {
+            Edge::Char => step.extend(set.value(data[i])),
+            Edge::Shift(to) => step.extend([set.shift(to).unwrap(), to.value(data[i]).unwrap()]),
+            Edge::ShiftA(len) => {
+                step.push(54 + len as u8);
+                step.extend(data[i..i + len].iter().filter_map(|&c| Set::A.value(c)));
+            }
+            Edge::Numeric => {
+                let value = data[i..i + 9]
+                    .iter()
+                    .fold(0, |v, d| v * 10 + (d - b'0') as u32);
+
+                step.push(NUMERIC_SHIFT);
+                step.extend((0..5).rev().map(|k| (value >> (k * 6)) as u8 & 0x3F));
+            }
+        }
+
+        let (_, from) = latched[i][set as usize].unwrap();
+
+        step.splice(0..0, from.latch(set).iter().copied());
+        steps.push(step);
+        set = from;
+    }
+
+    (steps.into_iter().rev().flatten().collect(), last)
+}
+
+/// The MaxiCode barcode type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MaxiCode {
+    mode: u8,
+    codewords: Vec<u8>,
+}
+
+impl MaxiCode {
+    /// Creates a new barcode in the standard mode 4.
+    /// Returns Result<MaxiCode, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<MaxiCode> {
+        MaxiCode::with_mode(data, 4)
+    }
+
+    /// Creates a new barcode in mode 4 (standard), 5 (enhanced error correction) or 6 (reader
+    /// programming). Returns Result<MaxiCode, Error> indicating parse success.
+    pub fn with_mode<T: AsRef<str>>(data: T, mode: u8) -> Result<MaxiCode> {
+        let capacity = match mode {
+            4 | 6 => 93,
+            5 => 77,
+            _ => return Err(Error::option("mode", 4..=6, mode as usize)),
+        };
+        if data.as_ref().is_empty() {
+            return Err(Error::length(1..=usize::MAX, 0));
+        }
+
+        let mut codewords = vec![mode];
+
+        codewords.extend(MaxiCode::secondary(data.as_ref(), capacity)?);
+
+        Ok(MaxiCode { mode, codewords })
+    }
+
+    /// Creates a new barcode with a structured carrier message in mode 2 or 3. Postal codes of
+    /// up to nine digits use mode 2, and other postal codes of up to six characters use mode 3.
+    /// The country code and class of service range from 0 to 999.
+    /// Returns Result<MaxiCode, Error> indicating parse success.
+    pub fn carrier<T: AsRef<str>>(
+        data: T,
+        postcode: &str,
+        country: u16,
+        service: u16,
+    ) -> Result<MaxiCode> {
+        if country > 999 {
+            return Err(Error::option("country code", 0..=999, country as usize));
+        }
+        if service > 999 {
+            return Err(Error::option("class of service", 0..=999, service as usize));
+        }
+
+        let (mode, postcode) = match postcode.len() {
+            1..=9 if postcode.bytes().all(|c| c.is_ascii_digit()) => {
+                let value = postcode.bytes().fold(0, |v, d| v * 10 + (d - b'0') as u64);
+
+                (2, value | (postcode.len() as u64) << 30)
+            }
+            1..=6 => {
+                let value = postcode
+                    .bytes()
+                    .chain(core::iter::repeat(b' '))
+                    .take(6)
+                    .enumerate()
+                    .map(|(i, c)| match Set::A.value(c) {
+                        Some(v) if c.is_ascii_graphic() || c == b' ' => Ok(v as u64),
+                        _ => Err(Error::character(i, postcode[i..].chars().next().unwrap())),
+                    })
+                    .try_fold(0, |acc, v| v.map(|v| acc << 6 | v))?;
+
+                (3, value)
+            }
+            len => return Err(Error::length(1..=9, len)),
+        };
+
+        // The primary message packs the fields from the least significant bits of the first
+        // codeword: the mode, postal code, country code and class of service.
+        let primary = mode | postcode << 4 | (country as u64) << 40 | (service as u64) << 50;
+        let mut codewords: Vec<u8> = (0..10).map(|i| (primary >> (i * 6)) as u8 & 0x3F).collect();
+
+        codewords.extend(MaxiCode::secondary(data.as_ref(), 84)?);
+
+        Ok(MaxiCode {
+            mode: mode as u8,
+            codewords,
+        })
+    }
+
+    /// Returns the mode of the barcode (2-6).
+    pub fn mode(&self) -> u8 {
+        self.mode
+    }
+
+    // Encodes and pads the data to the given number of codewords.
+    fn secondary(data: &str, capacity: usize) -> Result<Vec<u8>> {
+        let data = data
+            .chars()
+            .enumerate()
+            .map(|(i, c)| match c as u32 {
+                0..=255 => Ok(c as u8),
+                _ => Err(Error::character(i, c)),
+            })
+            .collect::<Result<Vec<u8>>>()?;
+        let (mut codewords, set) = encode_data(&data);
+
+        if codewords.len() > capacity {
+            return Err(Error::length(1..=capacity, codewords.len()));
+        }
+
+        // Padding is only available in code sets A and B, so the others latch back to A.
+        if codewords.len() < capacity && !matches!(set, Set::A | Set::B) {
+            codewords.push(LATCH_A);
+        }
+
+        codewords.resize(capacity, PAD);
+
+        Ok(codewords)
+    }
+
+    // Returns all 144 codewords, with error correction for the primary message and for the
+    // odd and even codewords of the secondary message.
+    fn symbol_codewords(&self) -> Vec<u8> {
+        let to_u16 = |words: &[u8]| words.iter().map(|&w| w as u16).collect::<Vec<u16>>();
+        let (primary, secondary) = self.codewords.split_at(10);
+        let ecc_len = if self.mode == 5 { 28 } else { 20 };
+        let even: Vec<u8> = secondary.iter().step_by(2).copied().collect();
+        let odd: Vec<u8> = secondary.iter().skip(1).step_by(2).copied().collect();
+        let even_ecc = helpers::reed_solomon(&to_u16(&even), ecc_len, POLY, 1);
+        let odd_ecc = helpers::reed_solomon(&to_u16(&odd), ecc_len, POLY, 1);
+        let mut codewords = primary.to_vec();
+
+        codewords.extend(
+            helpers::reed_solomon(&to_u16(primary), 10, POLY, 1)
+                .iter()
+                .map(|&w| w as u8),
+        );
+        codewords.extend(secondary);
+
+        for (e, o) in even_ecc.iter().zip(odd_ecc.iter()) {
+            codewords.extend([*e as u8, *o as u8]);
+        }
+
+        codewords
+    }
+
+    /// Encodes the barcode.
+    /// Returns a hexagonal Matrix of modules.
+    pub fn encode(&self) -> Matrix {
+        let codewords = self.symbol_codewords();
+        let mut matrix = Matrix::new(30, 33).hexagonal();
+
+        for (y, row) in MODULES.iter().enumerate() {
+            for (x, &bit) in row.iter().enumerate() {
+                let module = match bit {
+                    -1 => 1,
+                    -2 => 0,
+                    b => codewords[b as usize / 6] >> (5 - b % 6) & 1,
+                };
+
+                matrix.set(x, y, module);
+            }
+        }
+
+        matrix
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::sym::maxicode::*;
+
+    #[test]
+    fn new_maxicode() {
+        let maxi1 = MaxiCode::new("Hello, World!");
+        let maxi2 = MaxiCode::with_mode("Grüße", 5);
+        let maxi3 = MaxiCode::carrier("[)>\x1E01\x1D96", "152382802", 840, 1);
+        let maxi4 = MaxiCode::carrier("", "B1050", 56, 999);
+
+        assert_eq!(maxi1.unwrap().mode(), 4);
+        assert_eq!(maxi2.unwrap().mode(), 5);
+        assert_eq!(maxi3.unwrap().mode(), 2);
+        assert_eq!(maxi4.unwrap().mode(), 3);
+    }
+
+    #[test]
+    fn invalid_data_maxicode() {
+        let maxi1 = MaxiCode::new("€100");
+        let maxi2 = MaxiCode::carrier("ABC", "b1050", 56, 999);
+        let maxi3 = MaxiCode::carrier("ABC", "152382802", 1000, 1);
+        let maxi4 = MaxiCode::carrier("ABC", "152382802", 840, 1000);
+
+        assert_eq!(maxi1.err().unwrap(), Error::character(0, '€'));
+        assert_eq!(maxi2.err().unwrap(), Error::character(0, 'b'));
+        assert!(matches!(
+            maxi3,
+            Err(Error::Option { name: "country code", allowed, actual })
+                if allowed == (0.0..=999.0) && actual == 1000.0
+        ));
+        assert!(matches!(
+            maxi4,
+            Err(Error::Option { name: "class of service", allowed, actual })
+                if allowed == (0.0..=999.0) && actual == 1000.0
+        ));
+    }
+
+    #[test]
+    fn invalid_len_maxicode() {
+        let maxi1 = MaxiCode::new("");
+        let maxi2 = MaxiCode::with_mode("ABC", 2);
+        let maxi3 = MaxiCode::new("A".repeat(94));
+        let maxi4 = MaxiCode::with_mode("A".repeat(78), 5);
+        let maxi5 = MaxiCode::carrier("ABC", "", 840, 1);
+        let maxi6 = MaxiCode::carrier("ABC", "AB12345", 840, 1);
+
+        assert_eq!(maxi1.err().unwrap(), Error::length(1..=usize::MAX, 0));
+        assert_eq!(maxi2.err().unwrap(), Error::option("mode", 4..=6, 2));
+        assert_eq!(maxi3.err().unwrap(), Error::length(1..=93, 94));
+        assert_eq!(maxi4.err().unwrap(), Error::length(1..=77, 78));
+        assert_eq!(maxi5.err().unwrap(), Error::length(1..=9, 0));
+        assert_eq!(maxi6.err().unwrap(), Error::length(1..=9, 7));
+    }
+
+    #[test]
+    fn maxicode_capacity() {
+        assert!(MaxiCode::new("A".repeat(93)).is_ok());
+        assert!(MaxiCode::new("1".repeat(138)).is_ok());
+        assert!(MaxiCode::with_mode("A".repeat(77), 5).is_ok());
+    }
+
+    #[test]
+    fn maxicode_codewords() {
+        let maxi1 = MaxiCode::new("ABC").unwrap();
+        let maxi2 = MaxiCode::new("abc").unwrap();
+        let maxi3 = MaxiCode::new("123456789").unwrap();
+        let maxi4 = MaxiCode::new("ÀÁÂÃ").unwrap();
+
+        assert_eq!(maxi1.codewords[..6], [4, 1, 2, 3, 33, 33]);
+        assert_eq!(maxi2.codewords[..6], [4, 63, 1, 2, 3, 33]);
+        assert_eq!(maxi3.codewords[..8], [4, 31, 7, 22, 60, 52, 21, 33]);
+        assert_eq!(maxi4.codewords[..9], [4, 60, 60, 0, 1, 2, 3, 58, 33]);
+        assert_eq!(maxi1.codewords.len(), 94);
+    }
+
+    #[test]
+    fn maxicode_carrier() {
+        let maxi1 = MaxiCode::carrier("", "152382802", 840, 1).unwrap();
+        let maxi2 = MaxiCode::carrier("", "B1050", 56, 999).unwrap();
+
+        assert_eq!(maxi1.codewords[..10], [34, 20, 45, 20, 17, 18, 2, 18, 7, 0]);
+        assert_eq!(maxi2.codewords[..10], [3, 8, 28, 13, 28, 44, 0, 14, 28, 62]);
+        assert_eq!(maxi1.codewords.len(), 94);
+    }
+
+    #[test]
+    fn maxicode_encode() {
+        let maxi = MaxiCode::new("Hello, World!").unwrap();
+        let matrix = maxi.encode();
+
+        assert_eq!(maxi.symbol_codewords().len(), 144);
+        assert_eq!((matrix.width(), matrix.height()), (30, 33));
+        assert!(matrix.is_hexagonal());
+        assert!(matrix.is_offset(1) && !matrix.is_offset(16));
+        assert_eq!(
+            (matrix.get(28, 0), matrix.get(10, 9), matrix.get(20, 17)),
+            (1, 1, 1)
+        );
+        assert_eq!(matrix.get(14, 16), 0);
+    }
+}
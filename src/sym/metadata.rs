@@ -0,0 +1,507 @@
+//! Descriptions of the supported symbologies.
+//!
+//! Each symbology can be described without creating a barcode, for example to validate input or
+//! show its limits before encoding:
+//!
+//! ```rust
+//! use barcoders::sym::metadata::*;
+//!
+//! let code39 = Symbology::Code39.metadata();
+//!
+//! assert_eq!(code39.length, 1..=256);
+//! assert_eq!(code39.checksum, Checksum::Optional);
+//! assert!(code39.characters.unwrap().contains(&'$'));
+//! ```
+//!
+//! The characters and lengths are those checked by the encoders themselves.
+
+use crate::sym::codabar::Codabar;
+use crate::sym::code11::Code11;
+use crate::sym::code32::Code32;
+use crate::sym::code39::Code39;
+use crate::sym::code93::Code93;
+use crate::sym::deutsche_post::{Identcode, Leitcode};
+use crate::sym::ean13::EAN13;
+use crate::sym::ean8::EAN8;
+use crate::sym::ean_supp::EANSUPP;
+use crate::sym::helpers::{self, Vec};
+use crate::sym::hibc::HIBC;
+use crate::sym::pharmacode::{Pharmacode, PharmacodeTwoTrack};
+use crate::sym::pzn::PZN;
+use crate::sym::tf::TF;
+use crate::sym::upca::UPCA;
+use crate::sym::{code128, Parse};
+use core::ops::RangeInclusive;
+
+/// The supported symbologies.
+///
+/// GS1 Composite symbols are not listed, as they combine a linear symbol with a separate
+/// two-dimensional component and so take two parts of data rather than one.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Symbology {
+    /// Aztec Code.
+    Aztec,
+    /// Codabar.
+    Codabar,
+    /// Codablock F.
+    CodablockF,
+    /// Code11 (USD-8).
+    Code11,
+    /// Code128.
+    Code128,
+    /// Code32 (Italian Pharmacode).
+    Code32,
+    /// Code39.
+    Code39,
+    /// Code93.
+    Code93,
+    /// GS1 DataBar Expanded, including its stacked form.
+    DataBarExpanded,
+    /// Data Matrix (ECC 200).
+    DataMatrix,
+    /// Deutsche Post Identcode.
+    Identcode,
+    /// Deutsche Post Leitcode.
+    Leitcode,
+    /// EAN-13.
+    EAN13,
+    /// EAN-8.
+    EAN8,
+    /// The 2 digit EAN supplemental barcode.
+    EAN2,
+    /// The 5 digit EAN supplemental barcode.
+    EAN5,
+    /// HIBC LIC primary data, carried in Code39.
+    HIBC,
+    /// MaxiCode.
+    MaxiCode,
+    /// MicroPDF417.
+    MicroPDF417,
+    /// PDF417.
+    PDF417,
+    /// Pharmacode (one-track).
+    Pharmacode,
+    /// Two-track Pharmacode.
+    PharmacodeTwoTrack,
+    /// Pharmazentralnummer.
+    PZN,
+    /// QR Code.
+    QRCode,
+    /// Standard 2-of-5, also known as Industrial 2-of-5.
+    #[cfg_attr(feature = "serde", serde(alias = "Industrial2of5"))]
+    Standard2of5,
+    /// Interleaved 2-of-5.
+    Interleaved2of5,
+    /// Matrix 2-of-5.
+    Matrix2of5,
+    /// IATA 2-of-5.
+    IATA2of5,
+    /// Datalogic 2-of-5.
+    Datalogic2of5,
+    /// COOP 2-of-5.
+    COOP2of5,
+    /// UPC-A.
+    UPCA,
+}
+
+/// How a symbology uses check characters.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Checksum {
+    /// The symbology has no check characters.
+    None,
+    /// A check character can be added on request, such as with `Code39::with_checksum`.
+    Optional,
+    /// Check characters (or error correction) are always computed from the data and added.
+    Computed,
+    /// A check digit is always encoded. It is computed when left out of the data and validated
+    /// when included.
+    Mandatory,
+}
+
+/// How the characters of a symbology are arranged.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Structure {
+    /// A linear symbology whose characters follow one another without gaps.
+    Continuous,
+    /// A linear symbology whose characters are separated by an inter-character gap.
+    Discrete,
+    /// A stacked or matrix symbology that encodes to a `Matrix`.
+    TwoDimensional,
+}
+
+/// A description of a symbology.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Metadata {
+    /// The name of the symbology.
+    pub name: &'static str,
+    /// The characters allowed in the data, or None if any character can be encoded.
+    pub characters: Option<Vec<char>>,
+    /// The allowed lengths of the data in characters. The upper bound is `usize::MAX` when the
+    /// length is only limited by the capacity of the symbol, which depends on the data.
+    pub length: RangeInclusive<usize>,
+    /// Whether the data is made of digits alone.
+    pub numeric: bool,
+    /// How the symbology uses check characters.
+    pub checksum: Checksum,
+    /// The minimum quiet zones before and after the symbol in modules. Two-dimensional
+    /// symbologies need the same quiet zones above and below.
+    pub quiet_zone: (usize, usize),
+    /// How the characters of the symbology are arranged.
+    pub structure: Structure,
+}
+
+impl Metadata {
+    fn new(
+        name: &'static str,
+        (characters, length): (Option<Vec<char>>, RangeInclusive<usize>),
+        checksum: Checksum,
+        quiet_zone: (usize, usize),
+        structure: Structure,
+    ) -> Metadata {
+        let numeric = characters
+            .as_ref()
+            .is_some_and(|chars| chars.iter().all(char::is_ascii_digit));
+
+        Metadata {
+            name,
+            characters,
+            length,
+            numeric,
+            checksum,
+            quiet_zone,
+            structure,
+        }
+    }
+}
+
+// The characters and lengths checked by a parsed symbology.
+fn parsed<T: Parse>() -> (Option<Vec<char>>, RangeInclusive<usize>) {
+    let len = T::valid_len();
+
+    (
+        Some(T::valid_chars()),
+        len.start as usize..=len.end as usize,
+    )
+}
+
+// The characters and lengths of the two-dimensional symbologies that encode ISO/IEC 8859-1.
+fn latin1() -> (Option<Vec<char>>, RangeInclusive<usize>) {
+    let chars = (0..=255u8).map(char::from).collect();
+
+    (Some(chars), 1..=usize::MAX)
+}
+
+impl Symbology {
+    /// Every supported symbology.
+    pub const ALL: [Symbology; 31] = [
+        Symbology::Aztec,
+        Symbology::Codabar,
+        Symbology::CodablockF,
+        Symbology::Code11,
+        Symbology::Code128,
+        Symbology::Code32,
+        Symbology::Code39,
+        Symbology::Code93,
+        Symbology::DataBarExpanded,
+        Symbology::DataMatrix,
+        Symbology::Identcode,
+        Symbology::Leitcode,
+        Symbology::EAN13,
+        Symbology::EAN8,
+        Symbology::EAN2,
+        Symbology::EAN5,
+        Symbology::HIBC,
+        Symbology::MaxiCode,
+        Symbology::MicroPDF417,
+        Symbology::PDF417,
+        Symbology::Pharmacode,
+        Symbology::PharmacodeTwoTrack,
+        Symbology::PZN,
+        Symbology::QRCode,
+        Symbology::Standard2of5,
+        Symbology::Interleaved2of5,
+        Symbology::Matrix2of5,
+        Symbology::IATA2of5,
+        Symbology::Datalogic2of5,
+        Symbology::COOP2of5,
+        Symbology::UPCA,
+    ];
+
+    /// Returns the description of the symbology.
+    pub fn metadata(self) -> Metadata {
+        match self {
+            Symbology::Aztec => Metadata::new(
+                "Aztec Code",
+                latin1(),
+                Checksum::Computed,
+                (0, 0),
+                Structure::TwoDimensional,
+            ),
+            Symbology::Codabar => Metadata::new(
+                "Codabar",
+                parsed::<Codabar>(),
+                Checksum::None,
+                (10, 10),
+                Structure::Discrete,
+            ),
+            Symbology::CodablockF => Metadata::new(
+                "Codablock F",
+                latin1(),
+                Checksum::Computed,
+                (10, 10),
+                Structure::TwoDimensional,
+            ),
+            Symbology::Code11 => Metadata::new(
+                "Code11",
+                parsed::<Code11>(),
+                Checksum::Computed,
+                (10, 10),
+                Structure::Discrete,
+            ),
+            Symbology::Code128 => Metadata::new(
+                "Code128",
+                (Some(code128::valid_chars()), 2..=usize::MAX),
+                Checksum::Computed,
+                (10, 10),
+                Structure::Continuous,
+            ),
+            Symbology::Code32 => Metadata::new(
+                "Code32",
+                parsed::<Code32>(),
+                Checksum::Mandatory,
+                (10, 10),
+                Structure::Discrete,
+            ),
+            Symbology::Code39 => Metadata::new(
+                "Code39",
+                parsed::<Code39>(),
+                Checksum::Optional,
+                (10, 10),
+                Structure::Discrete,
+            ),
+            Symbology::Code93 => Metadata::new(
+                "Code93",
+                parsed::<Code93>(),
+                Checksum::Computed,
+                (10, 10),
+                Structure::Continuous,
+            ),
+            Symbology::DataBarExpanded => Metadata::new(
+                "GS1 DataBar Expanded",
+                (Some(helpers::gs1_chars()), 1..=usize::MAX),
+                Checksum::Computed,
+                (0, 0),
+                Structure::Continuous,
+            ),
+            Symbology::DataMatrix => Metadata::new(
+                "Data Matrix",
+                latin1(),
+                Checksum::Computed,
+                (1, 1),
+                Structure::TwoDimensional,
+            ),
+            Symbology::Identcode => Metadata::new(
+                "Identcode",
+                parsed::<Identcode>(),
+                Checksum::Mandatory,
+                (10, 10),
+                Structure::Continuous,
+            ),
+            Symbology::Leitcode => Metadata::new(
+                "Leitcode",
+                parsed::<Leitcode>(),
+                Checksum::Mandatory,
+                (10, 10),
+                Structure::Continuous,
+            ),
+            Symbology::EAN13 => Metadata::new(
+                "EAN-13",
+                parsed::<EAN13>(),
+                Checksum::Mandatory,
+                (11, 7),
+                Structure::Continuous,
+            ),
+            Symbology::EAN8 => Metadata::new(
+                "EAN-8",
+                parsed::<EAN8>(),
+                Checksum::Mandatory,
+                (7, 7),
+                Structure::Continuous,
+            ),
+            // The supplements follow the main barcode after a gap of 7 to 12 modules.
+            Symbology::EAN2 => Metadata::new(
+                "EAN-2",
+                (parsed::<EANSUPP>().0, 2..=2),
+                Checksum::Computed,
+                (7, 5),
+                Structure::Continuous,
+            ),
+            Symbology::EAN5 => Metadata::new(
+                "EAN-5",
+                (parsed::<EANSUPP>().0, 5..=5),
+                Checksum::Computed,
+                (7, 5),
+                Structure::Continuous,
+            ),
+            Symbology::HIBC => Metadata::new(
+                "HIBC",
+                parsed::<HIBC>(),
+                Checksum::Computed,
+                (10, 10),
+                Structure::Discrete,
+            ),
+            Symbology::MaxiCode => Metadata::new(
+                "MaxiCode",
+                latin1(),
+                Checksum::Computed,
+                (1, 1),
+                Structure::TwoDimensional,
+            ),
+            Symbology::MicroPDF417 => Metadata::new(
+                "MicroPDF417",
+                latin1(),
+                Checksum::Computed,
+                (1, 1),
+                Structure::TwoDimensional,
+            ),
+            Symbology::PDF417 => Metadata::new(
+                "PDF417",
+                latin1(),
+                Checksum::Computed,
+                (2, 2),
+                Structure::TwoDimensional,
+            ),
+            Symbology::Pharmacode => Metadata::new(
+                "Pharmacode",
+                parsed::<Pharmacode>(),
+                Checksum::None,
+                (6, 6),
+                Structure::Continuous,
+            ),
+            Symbology::PharmacodeTwoTrack => Metadata::new(
+                "Pharmacode Two-Track",
+                parsed::<PharmacodeTwoTrack>(),
+                Checksum::None,
+                (6, 6),
+                Structure::TwoDimensional,
+            ),
+            Symbology::PZN => Metadata::new(
+                "PZN",
+                parsed::<PZN>(),
+                Checksum::Mandatory,
+                (10, 10),
+                Structure::Discrete,
+            ),
+            Symbology::QRCode => Metadata::new(
+                "QR Code",
+                (None, 1..=usize::MAX),
+                Checksum::Computed,
+                (4, 4),
+                Structure::TwoDimensional,
+            ),
+            Symbology::Standard2of5 => Metadata::new(
+                "Standard 2-of-5",
+                parsed::<TF>(),
+                Checksum::Optional,
+                (10, 10),
+                Structure::Discrete,
+            ),
+            Symbology::Interleaved2of5 => Metadata::new(
+                "Interleaved 2-of-5",
+                parsed::<TF>(),
+                Checksum::Optional,
+                (10, 10),
+                Structure::Continuous,
+            ),
+            Symbology::Matrix2of5 => Metadata::new(
+                "Matrix 2-of-5",
+                parsed::<TF>(),
+                Checksum::Optional,
+                (10, 10),
+                Structure::Discrete,
+            ),
+            Symbology::IATA2of5 => Metadata::new(
+                "IATA 2-of-5",
+                parsed::<TF>(),
+                Checksum::Optional,
+                (10, 10),
+                Structure::Discrete,
+            ),
+            Symbology::Datalogic2of5 => Metadata::new(
+                "Datalogic 2-of-5",
+                parsed::<TF>(),
+                Checksum::Optional,
+                (10, 10),
+                Structure::Discrete,
+            ),
+            Symbology::COOP2of5 => Metadata::new(
+                "COOP 2-of-5",
+                parsed::<TF>(),
+                Checksum::Optional,
+                (10, 10),
+                Structure::Discrete,
+            ),
+            Symbology::UPCA => Metadata::new(
+                "UPC-A",
+                parsed::<UPCA>(),
+                Checksum::Mandatory,
+                (9, 9),
+                Structure::Continuous,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sym::code39::Code39;
+    use crate::sym::metadata::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::string::ToString;
+
+    #[test]
+    fn symbology_metadata() {
+        let code39 = Symbology::Code39.metadata();
+        let ean13 = Symbology::EAN13.metadata();
+        let qr = Symbology::QRCode.metadata();
+        let hibc = Symbology::HIBC.metadata();
+
+        assert_eq!(code39.name, "Code39");
+        assert_eq!(code39.length, 1..=256);
+        assert!(!code39.numeric);
+        assert_eq!(code39.checksum, Checksum::Optional);
+        assert_eq!(code39.structure, Structure::Discrete);
+        assert_eq!(ean13.length, 12..=13);
+        assert!(ean13.numeric);
+        assert_eq!(ean13.checksum, Checksum::Mandatory);
+        assert_eq!(ean13.quiet_zone, (11, 7));
+        assert!(qr.characters.is_none());
+        assert_eq!(qr.structure, Structure::TwoDimensional);
+        assert_eq!(hibc.checksum, Checksum::Computed);
+        assert_eq!(hibc.structure, Structure::Discrete);
+    }
+
+    #[test]
+    fn symbology_characters() {
+        let code39 = Symbology::Code39.metadata().characters.unwrap();
+        let code128 = Symbology::Code128.metadata().characters.unwrap();
+        let databar = Symbology::DataBarExpanded.metadata().characters.unwrap();
+
+        assert!(code39.iter().all(|&c| Code39::new(c.to_string()).is_ok()));
+        assert!(code128.contains(&'À') && code128.contains(&'a') && code128.contains(&'\u{0}'));
+        assert!(!code128.contains(&'€'));
+        assert!(databar.contains(&'(') && !databar.contains(&'#'));
+    }
+
+    #[test]
+    fn all_symbologies() {
+        let mut names: Vec<&str> = Symbology::ALL.iter().map(|s| s.metadata().name).collect();
+
+        names.sort_unstable();
+        names.dedup();
+
+        assert_eq!(names.len(), Symbology::ALL.len());
+    }
+}
@@ -0,0 +1,234 @@
+//! Encoders for Pharmacode (Laetus) barcodes.
+//!
+//! Pharmacode is used in the pharmaceutical industry to check that packaging, such as folding
+//! boxes and leaflets, matches the product on the line. It encodes a single integer and has no
+//! human readable text or check digit.
+//!
+//! One-track Pharmacode encodes 3 to 131070 as a row of narrow and wide bars, read from right to
+//! left. Two-track Pharmacode encodes 3 to 64570080 as a row of bars that fill the upper track,
+//! the lower track or both.
+
+use super::helpers::{vec, Vec};
+use crate::error::{Error, Result};
+use crate::sym::{Elements, Matrix, Parse};
+use core::ops::{Range, RangeInclusive};
+
+const NARROW: [u8; 1] = [1];
+const WIDE: [u8; 3] = [1, 1, 1];
+const SPACE: [u8; 2] = [0, 0];
+
+// The height of each track of a two-track barcode, in multiples of the bar width.
+const TRACK_HEIGHT: usize = 4;
+
+// Parses the digits as a number in the given range.
+fn parse_value(data: &str, range: RangeInclusive<u32>) -> Result<u32> {
+    let value = data.chars().fold(0, |value, c| {
+        value * 10 + c.to_digit(10).expect("Unknown character")
+    });
+
+    if !range.contains(&value) {
+        return Err(Error::option(
+            "value",
+            *range.start() as usize..=*range.end() as usize,
+            value as usize,
+        ));
+    }
+
+    Ok(value)
+}
+
+/// The one-track Pharmacode barcode type.
+#[derive(Clone, Copy, Debug)]
+pub struct Pharmacode(u32);
+
+impl Pharmacode {
+    /// Creates a new barcode.
+    /// Returns Result<Pharmacode, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<Pharmacode> {
+        let data = Pharmacode::parse(data.as_ref())?;
+
+        parse_value(data, 3..=131_070).map(Pharmacode)
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Vec<u8> of binary digits.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bars = vec![];
+        let mut value = self.0;
+
+        // Bars are found from the right, with even values taking a wide bar.
+        while value > 0 {
+            if value.is_multiple_of(2) {
+                bars.push(&WIDE[..]);
+                value = (value - 2) / 2;
+            } else {
+                bars.push(&NARROW[..]);
+                value = (value - 1) / 2;
+            }
+        }
+
+        bars.iter()
+            .rev()
+            .enumerate()
+            .fold(vec![], |mut enc, (i, bar)| {
+                if i > 0 {
+                    enc.extend(SPACE);
+                }
+
+                enc.extend(bar.iter());
+                enc
+            })
+    }
+
+    /// Encodes the barcode as a sequence of bar and space widths.
+    pub fn elements(&self) -> Elements {
+        Elements::from(self.encode())
+    }
+}
+
+impl Parse for Pharmacode {
+    /// Returns the valid length of data acceptable in this type of barcode.
+    fn valid_len() -> Range<u32> {
+        1..6
+    }
+
+    /// Returns the set of valid characters allowed in this type of barcode.
+    fn valid_chars() -> Vec<char> {
+        (0..10).map(|i| char::from_digit(i, 10).unwrap()).collect()
+    }
+}
+
+/// The two-track Pharmacode barcode type.
+#[derive(Clone, Copy, Debug)]
+pub struct PharmacodeTwoTrack(u32);
+
+impl PharmacodeTwoTrack {
+    /// Creates a new barcode.
+    /// Returns Result<PharmacodeTwoTrack, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<PharmacodeTwoTrack> {
+        let data = PharmacodeTwoTrack::parse(data.as_ref())?;
+
+        parse_value(data, 3..=64_570_080).map(PharmacodeTwoTrack)
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Matrix of modules, with the upper track in the first row and the lower track in
+    /// the second.
+    pub fn encode(&self) -> Matrix {
+        let mut bars = vec![];
+        let mut value = self.0;
+
+        // Each bar is a base 3 digit, with 1 for the lower track, 2 for the upper track and 3
+        // (in place of 0) for both.
+        while value > 0 {
+            let bar = match value % 3 {
+                0 => 3,
+                bar => bar,
+            };
+
+            bars.push(bar);
+            value = (value - bar) / 3;
+        }
+
+        let mut matrix = Matrix::new(bars.len() * 2 - 1, 2).with_row_height(TRACK_HEIGHT);
+
+        for (i, &bar) in bars.iter().rev().enumerate() {
+            matrix.set(i * 2, 0, (bar >> 1) as u8);
+            matrix.set(i * 2, 1, (bar & 1) as u8);
+        }
+
+        matrix
+    }
+}
+
+impl Parse for PharmacodeTwoTrack {
+    /// Returns the valid length of data acceptable in this type of barcode.
+    fn valid_len() -> Range<u32> {
+        1..8
+    }
+
+    /// Returns the set of valid characters allowed in this type of barcode.
+    fn valid_chars() -> Vec<char> {
+        (0..10).map(|i| char::from_digit(i, 10).unwrap()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sym::pharmacode::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::string::String;
+    use core::char;
+
+    fn collapse_vec(v: Vec<u8>) -> String {
+        let chars = v.iter().map(|d| char::from_digit(*d as u32, 10).unwrap());
+        chars.collect()
+    }
+
+    #[test]
+    fn invalid_length_pharmacode() {
+        let pharma1 = Pharmacode::new("");
+        let pharma2 = Pharmacode::new("1234567");
+        let pharma3 = PharmacodeTwoTrack::new("123456789");
+
+        assert_eq!(pharma1.err().unwrap(), Error::length(1..=6, 0));
+        assert_eq!(pharma2.err().unwrap(), Error::length(1..=6, 7));
+        assert_eq!(pharma3.err().unwrap(), Error::length(1..=8, 9));
+    }
+
+    #[test]
+    fn invalid_data_pharmacode() {
+        let pharma1 = Pharmacode::new("12A");
+        let pharma2 = Pharmacode::new("2");
+        let pharma3 = Pharmacode::new("131071");
+        let pharma4 = PharmacodeTwoTrack::new("64570081");
+
+        assert_eq!(pharma1.err().unwrap(), Error::character(2, 'A'));
+        assert!(matches!(
+            pharma2,
+            Err(Error::Option { allowed, actual, .. })
+                if allowed == (3.0..=131_070.0) && actual == 2.0
+        ));
+        assert!(matches!(
+            pharma3,
+            Err(Error::Option { allowed, actual, .. })
+                if allowed == (3.0..=131_070.0) && actual == 131_071.0
+        ));
+        assert!(matches!(
+            pharma4,
+            Err(Error::Option { allowed, actual, .. })
+                if allowed == (3.0..=64_570_080.0) && actual == 64_570_081.0
+        ));
+    }
+
+    #[test]
+    fn pharmacode_encode() {
+        let pharma1 = Pharmacode::new("3").unwrap();
+        let pharma2 = Pharmacode::new("1234").unwrap();
+        let pharma3 = Pharmacode::new("131070").unwrap();
+
+        assert_eq!(collapse_vec(pharma1.encode()), "1001");
+        assert_eq!(
+            collapse_vec(pharma2.encode()),
+            "10010011100111001001110010010011100111"
+        );
+        assert_eq!(collapse_vec(pharma3.encode()), "11100".repeat(15) + "111");
+    }
+
+    #[test]
+    fn pharmacode_two_track_encode() {
+        let pharma1 = PharmacodeTwoTrack::new("4").unwrap().encode();
+        let pharma2 = PharmacodeTwoTrack::new("1234").unwrap().encode();
+
+        assert_eq!((pharma1.width(), pharma1.height()), (3, 2));
+        assert_eq!(pharma1.row_height(), 4);
+        assert_eq!(
+            collapse_vec(pharma1.rows().flatten().cloned().collect()),
+            "000101"
+        );
+        assert_eq!(
+            collapse_vec(pharma2.rows().flatten().cloned().collect()),
+            "00001010001001010001010101"
+        );
+    }
+}
@@ -0,0 +1,183 @@
+//! Encoder for PZN barcodes.
+//!
+//! The PZN (Pharmazentralnummer) identifies pharmaceutical products in Germany. It is encoded in
+//! a Code39 barcode as a "-" followed by the number and a modulo-11 check digit.
+//!
+//! PZN-8 is the current eight digit number. The older seven digit PZN-7 is also supported. Numbers
+//! with a check digit of 10 are not issued and are rejected.
+
+use crate::checksum;
+use crate::error::{Error, Result};
+use crate::sym::code39::Code39;
+use crate::sym::{helpers, Elements, Parse};
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+use core::ops::Range;
+use helpers::Vec;
+
+/// The PZN barcode type.
+#[derive(Debug)]
+pub struct PZN(Vec<u8>);
+
+impl PZN {
+    fn init(data: &str, len: usize) -> Result<PZN> {
+        let d = PZN::parse(data)?;
+        let digits: Vec<u8> = d
+            .chars()
+            .map(|c| c.to_digit(10).expect("Unknown character") as u8)
+            .collect();
+
+        if digits.len() != len && digits.len() != len + 1 {
+            return Err(Error::length(len..=len + 1, digits.len()));
+        }
+
+        let pzn = PZN(digits[0..len].to_vec());
+        let checksum = pzn.check_digit();
+
+        // Numbers with a check digit of 10 are not issued, so the last digit is invalid.
+        if checksum == 10 {
+            return Err(Error::character(len - 1, (b'0' + digits[len - 1]) as char));
+        }
+
+        // If checksum digit is provided, check the checksum.
+        if digits.len() == len + 1 && checksum != digits[len] {
+            return Err(Error::checksum(checksum, digits[len]));
+        }
+
+        Ok(pzn)
+    }
+
+    /// Creates a new PZN-8 barcode from seven digits, with or without the check digit.
+    /// Returns Result<PZN, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<PZN> {
+        PZN::init(data.as_ref(), 7)
+    }
+
+    /// Creates a new PZN-7 barcode from six digits, with or without the check digit.
+    /// Returns Result<PZN, Error> indicating parse success.
+    pub fn pzn7<T: AsRef<str>>(data: T) -> Result<PZN> {
+        PZN::init(data.as_ref(), 6)
+    }
+
+    /// Returns the check digit, calculated using a modulo-11 weighting algorithm, with weights
+    /// ascending from 1 for PZN-8 and from 2 for PZN-7.
+    pub fn check_digit(&self) -> u8 {
+        checksum::pzn_digit(&self.0)
+    }
+
+    /// Returns the encoded data, a "-" followed by the number and its check digit.
+    pub fn data(&self) -> String {
+        let mut data = String::from("-");
+
+        data.push_str(&helpers::digit_string(&self.0));
+        data.push((b'0' + self.check_digit()) as char);
+        data
+    }
+
+    /// Returns the human readable text, the number and its check digit prefixed by "PZN - ".
+    pub fn text(&self) -> String {
+        let mut text = String::from("PZN - ");
+
+        text.push_str(&self.data()[1..]);
+        text
+    }
+
+    /// Encodes the barcode as a sequence of bar and space widths.
+    pub fn elements(&self) -> Elements {
+        Code39::new(self.data())
+            .expect("PZN digits are valid Code39")
+            .elements()
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Vec<u8> of binary digits.
+    pub fn encode(&self) -> Vec<u8> {
+        self.elements().modules()
+    }
+}
+
+impl Parse for PZN {
+    /// Returns the valid length of data acceptable in this type of barcode.
+    fn valid_len() -> Range<u32> {
+        6..8
+    }
+
+    /// Returns the set of valid characters allowed in this type of barcode.
+    fn valid_chars() -> Vec<char> {
+        (0..10).map(|i| char::from_digit(i, 10).unwrap()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sym::pzn::*;
+
+    #[test]
+    fn new_pzn() {
+        let pzn1 = PZN::new("0123456");
+        let pzn2 = PZN::new("01234562");
+        let pzn3 = PZN::pzn7("123456");
+        let pzn4 = PZN::pzn7("1234562");
+
+        assert!(pzn1.is_ok());
+        assert!(pzn2.is_ok());
+        assert!(pzn3.is_ok());
+        assert!(pzn4.is_ok());
+    }
+
+    #[test]
+    fn invalid_data_pzn() {
+        let pzn = PZN::new("012345A");
+
+        assert_eq!(pzn.err().unwrap(), Error::character(6, 'A'));
+    }
+
+    #[test]
+    fn invalid_len_pzn() {
+        let pzn1 = PZN::new("012345");
+        let pzn2 = PZN::pzn7("01234567");
+        let pzn3 = PZN::new("012345678");
+
+        assert_eq!(pzn1.err().unwrap(), Error::length(7..=8, 6));
+        assert_eq!(pzn2.err().unwrap(), Error::length(6..=7, 8));
+        assert_eq!(pzn3.err().unwrap(), Error::length(6..=8, 9));
+    }
+
+    #[test]
+    fn invalid_checksum_pzn() {
+        let pzn = PZN::new("01234563");
+
+        assert_eq!(pzn.err().unwrap(), Error::checksum(2, 3));
+    }
+
+    #[test]
+    fn unissued_pzn() {
+        let pzn1 = PZN::new("1234561");
+        let pzn2 = PZN::new("12345610");
+        let pzn3 = PZN::new("0000003");
+        let pzn4 = PZN::pzn7("000003");
+
+        assert_eq!(pzn1.err().unwrap(), Error::character(6, '1'));
+        assert_eq!(pzn2.err().unwrap(), Error::character(6, '1'));
+        assert_eq!(pzn3.err().unwrap(), Error::character(6, '3'));
+        assert_eq!(pzn4.err().unwrap(), Error::character(5, '3'));
+    }
+
+    #[test]
+    fn pzn_encode() {
+        let pzn1 = PZN::new("0123456").unwrap();
+        let pzn2 = PZN::pzn7("123456").unwrap();
+
+        assert_eq!(pzn1.encode(), Code39::new("-01234562").unwrap().encode());
+        assert_eq!(pzn2.encode(), Code39::new("-1234562").unwrap().encode());
+    }
+
+    #[test]
+    fn pzn_text() {
+        let pzn = PZN::new("1234562").unwrap();
+
+        assert_eq!(pzn.check_digit(), 6);
+        assert_eq!(pzn.data(), "-12345626");
+        assert_eq!(pzn.text(), "PZN - 12345626");
+    }
+}
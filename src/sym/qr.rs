@@ -0,0 +1,954 @@
+//! Encoder for QR Code barcodes.
+//!
+//! QR Code is a two-dimensional matrix symbology defined by ISO/IEC 18004. All 40 versions
+//! (21x21 to 177x177 modules) and the four error correction levels are supported.
+//!
+//! The input is split into numeric, alphanumeric, byte and Kanji segments automatically so that
+//! the smallest possible symbol is produced. Text is encoded as UTF-8 bytes; an Extended Channel
+//! Interpretation (ECI) can be given to tell readers which character set was used, e.g. 26 for
+//! UTF-8. Kanji mode is only available to Shift JIS encoded input (see `QRCode::shift_jis`).
+//!
+//! The eight mask patterns are scored as per the specification and the best one is applied.
+//! The encoded `Matrix` does not include the four module quiet zone.
+
+use crate::error::{Error, Result};
+use crate::sym::helpers::{self, vec, Vec};
+use crate::sym::Matrix;
+
+// The 45 characters of the alphanumeric mode, in value order.
+const ALPHANUMERIC: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+// Error correction codewords per block, indexed by ECC level then version.
+const ECC_CODEWORDS: [[u8; 41]; 4] = [
+    [
+        0, 7, 10, 15, 20, 26, 18, 20, 24, 30, 18, 20, 24, 26, 30, 22, 24, 28, 30, 28, 28, 28, 28,
+        30, 30, 26, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+    ],
+    [
+        0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28, 26, 26, 26, 26, 28,
+        28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28,
+    ],
+    [
+        0, 13, 22, 18, 26, 18, 24, 18, 22, 20, 24, 28, 26, 24, 20, 30, 24, 28, 28, 26, 30, 28, 30,
+        30, 30, 30, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+    ],
+    [
+        0, 17, 28, 22, 16, 22, 28, 26, 26, 24, 28, 24, 28, 22, 24, 24, 30, 28, 28, 26, 28, 30, 24,
+        30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+    ],
+];
+
+// Number of error correction blocks, indexed by ECC level then version.
+const ECC_BLOCKS: [[u8; 41]; 4] = [
+    [
+        0, 1, 1, 1, 1, 1, 2, 2, 2, 2, 4, 4, 4, 4, 4, 6, 6, 6, 6, 7, 8, 8, 9, 9, 10, 12, 12, 12, 13,
+        14, 15, 16, 17, 18, 19, 19, 20, 21, 22, 24, 25,
+    ],
+    [
+        0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5, 5, 8, 9, 9, 10, 10, 11, 13, 14, 16, 17, 17, 18, 20, 21,
+        23, 25, 26, 28, 29, 31, 33, 35, 37, 38, 40, 43, 45, 47, 49,
+    ],
+    [
+        0, 1, 1, 2, 2, 4, 4, 6, 6, 8, 8, 8, 10, 12, 16, 12, 17, 16, 18, 21, 20, 23, 23, 25, 27, 29,
+        34, 34, 35, 38, 40, 43, 45, 48, 51, 53, 56, 59, 62, 65, 68,
+    ],
+    [
+        0, 1, 1, 2, 4, 4, 4, 5, 6, 8, 8, 11, 11, 16, 16, 18, 16, 19, 21, 25, 25, 25, 34, 30, 32,
+        35, 37, 40, 42, 45, 48, 51, 54, 57, 60, 63, 66, 70, 74, 77, 81,
+    ],
+];
+
+// Mask pattern penalty weights (N1 to N4).
+const PENALTY_RUN: u32 = 3;
+const PENALTY_BLOCK: u32 = 3;
+const PENALTY_FINDER: u32 = 40;
+const PENALTY_BALANCE: u32 = 10;
+
+// Finder-like patterns searched for by the N3 penalty rule: 1:1:3:1:1 with four light modules
+// on one side and at least one on the other.
+const FINDER_LIKE: [[u8; 12]; 2] = [
+    [0, 0, 0, 0, 1, 0, 1, 1, 1, 0, 1, 0],
+    [0, 1, 0, 1, 1, 1, 0, 1, 0, 0, 0, 0],
+];
+
+/// The error correction level of a QR Code.
+///
+/// Higher levels allow more of the symbol to be damaged while remaining readable, at the cost
+/// of capacity.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ErrorCorrection {
+    /// Recovers roughly 7% of the symbol.
+    L,
+    /// Recovers roughly 15% of the symbol. This is the default.
+    M,
+    /// Recovers roughly 25% of the symbol.
+    Q,
+    /// Recovers roughly 30% of the symbol.
+    H,
+}
+
+impl ErrorCorrection {
+    fn index(self) -> usize {
+        match self {
+            ErrorCorrection::L => 0,
+            ErrorCorrection::M => 1,
+            ErrorCorrection::Q => 2,
+            ErrorCorrection::H => 3,
+        }
+    }
+
+    fn format_bits(self) -> u32 {
+        match self {
+            ErrorCorrection::L => 1,
+            ErrorCorrection::M => 0,
+            ErrorCorrection::Q => 3,
+            ErrorCorrection::H => 2,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Mode {
+    Numeric,
+    Alphanumeric,
+    Byte,
+    Kanji,
+}
+
+const MODES: [Mode; 4] = [Mode::Numeric, Mode::Alphanumeric, Mode::Byte, Mode::Kanji];
+
+impl Mode {
+    fn indicator(self) -> u32 {
+        match self {
+            Mode::Numeric => 0b0001,
+            Mode::Alphanumeric => 0b0010,
+            Mode::Byte => 0b0100,
+            Mode::Kanji => 0b1000,
+        }
+    }
+
+    fn count_bits(self, version: u8) -> usize {
+        let class = match version {
+            1..=9 => 0,
+            10..=26 => 1,
+            _ => 2,
+        };
+
+        match self {
+            Mode::Numeric => [10, 12, 14][class],
+            Mode::Alphanumeric => [9, 11, 13][class],
+            Mode::Byte => [8, 16, 16][class],
+            Mode::Kanji => [8, 10, 12][class],
+        }
+    }
+
+    // The cost of a unit in this mode, in sixths of a bit, or None if it can't be encoded.
+    fn cost(self, unit: Unit) -> Option<u32> {
+        match (self, unit) {
+            (Mode::Numeric, Unit::Byte(b)) if b.is_ascii_digit() => Some(20),
+            (Mode::Alphanumeric, Unit::Byte(b)) if ALPHANUMERIC.contains(&b) => Some(33),
+            (Mode::Byte, Unit::Byte(_)) => Some(48),
+            (Mode::Byte, Unit::Kanji(_)) => Some(96),
+            (Mode::Kanji, Unit::Kanji(_)) => Some(78),
+            _ => None,
+        }
+    }
+}
+
+// A single input character: a byte, or a double-byte Shift JIS character in the Kanji range.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Unit {
+    Byte(u8),
+    Kanji(u16),
+}
+
+/// The QR Code barcode type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QRCode {
+    units: Vec<Unit>,
+    eci: Option<u32>,
+    ecc: ErrorCorrection,
+    version: u8,
+    bits: Vec<u8>,
+}
+
+impl QRCode {
+    /// Creates a new barcode with error correction level M.
+    /// Returns Result<QRCode, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<QRCode> {
+        QRCode::with_ecc(data, ErrorCorrection::M)
+    }
+
+    /// Creates a new barcode with the given error correction level.
+    /// Returns Result<QRCode, Error> indicating parse success.
+    pub fn with_ecc<T: AsRef<str>>(data: T, ecc: ErrorCorrection) -> Result<QRCode> {
+        let units = data.as_ref().bytes().map(Unit::Byte).collect();
+
+        QRCode::build(units, None, ecc, 1)
+    }
+
+    /// Creates a new barcode that starts with the given ECI assignment number (0 to 999999).
+    /// Returns Result<QRCode, Error> indicating parse success.
+    pub fn with_eci<T: AsRef<str>>(data: T, ecc: ErrorCorrection, eci: u32) -> Result<QRCode> {
+        if eci > 999_999 {
+            return Err(Error::option("ECI", 0..=999_999, eci as usize));
+        }
+
+        let units = data.as_ref().bytes().map(Unit::Byte).collect();
+
+        QRCode::build(units, Some(eci), ecc, 1)
+    }
+
+    /// Creates a new barcode from Shift JIS encoded data, using Kanji mode where possible.
+    /// Returns Result<QRCode, Error> indicating parse success.
+    pub fn shift_jis<T: AsRef<[u8]>>(data: T, ecc: ErrorCorrection) -> Result<QRCode> {
+        let data = data.as_ref();
+        let mut units = vec![];
+        let mut i = 0;
+
+        while i < data.len() {
+            let lead = data[i];
+            let trail = data.get(i + 1).copied().unwrap_or(0);
+            let double = matches!(lead, 0x81..=0x9F | 0xE0..=0xFC)
+                && matches!(trail, 0x40..=0x7E | 0x80..=0xFC);
+
+            if double {
+                let value = ((lead as u16) << 8) | trail as u16;
+
+                if matches!(value, 0x8140..=0x9FFC | 0xE040..=0xEBBF) {
+                    units.push(Unit::Kanji(value));
+                } else {
+                    units.push(Unit::Byte(lead));
+                    units.push(Unit::Byte(trail));
+                }
+
+                i += 2;
+            } else {
+                units.push(Unit::Byte(lead));
+                i += 1;
+            }
+        }
+
+        QRCode::build(units, None, ecc, 1)
+    }
+
+    /// Sets the smallest version (1 to 40) the barcode may use. A larger version is chosen when
+    /// the data does not fit.
+    /// Returns Result<QRCode, Error> indicating success.
+    pub fn min_version(self, version: u8) -> Result<QRCode> {
+        if !(1..=40).contains(&version) {
+            return Err(Error::option("version", 1..=40, version as usize));
+        }
+
+        QRCode::build(self.units, self.eci, self.ecc, version)
+    }
+
+    /// Returns the version (1 to 40) of the barcode.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Returns the error correction level of the barcode.
+    pub fn ecc(&self) -> ErrorCorrection {
+        self.ecc
+    }
+
+    fn build(
+        units: Vec<Unit>,
+        eci: Option<u32>,
+        ecc: ErrorCorrection,
+        min_version: u8,
+    ) -> Result<QRCode> {
+        if units.is_empty() {
+            return Err(Error::length(1..=usize::MAX, 0));
+        }
+
+        for version in min_version..=40 {
+            let bits = match QRCode::data_bits(&units, eci, version) {
+                Some(bits) => bits,
+                None => continue,
+            };
+
+            if bits.len() <= data_codewords(version, ecc) * 8 {
+                return Ok(QRCode {
+                    units,
+                    eci,
+                    ecc,
+                    version,
+                    bits,
+                });
+            }
+        }
+
+        // The length is counted in bits, against the capacity of the largest version.
+        let bits = QRCode::data_bits(&units, eci, 40).map_or(units.len() * 8, |bits| bits.len());
+
+        Err(Error::length(1..=data_codewords(40, ecc) * 8, bits))
+    }
+
+    // Finds the cheapest sequence of modes for the units, using the character count indicator
+    // lengths of the given version.
+    fn segment_modes(units: &[Unit], version: u8) -> Vec<Mode> {
+        let header = |m: Mode| (4 + m.count_bits(version) as u32) * 6;
+        let round = |c: u32| c.div_ceil(6) * 6;
+        let mut costs: [Option<u32>; 4] = [None; 4];
+        let mut previous: Vec<[usize; 4]> = Vec::with_capacity(units.len());
+
+        for (i, &unit) in units.iter().enumerate() {
+            let mut next: [Option<u32>; 4] = [None; 4];
+            let mut from = [0; 4];
+
+            for (m, mode) in MODES.iter().enumerate() {
+                let cost = match mode.cost(unit) {
+                    Some(c) => c,
+                    None => continue,
+                };
+
+                if i == 0 {
+                    next[m] = Some(header(*mode) + cost);
+                    continue;
+                }
+
+                for (p, prev) in costs.iter().enumerate() {
+                    let total = match prev {
+                        Some(c) if p == m => c + cost,
+                        Some(c) => round(*c) + header(*mode) + cost,
+                        None => continue,
+                    };
+
+                    if next[m].is_none_or(|n| total < n) {
+                        next[m] = Some(total);
+                        from[m] = p;
+                    }
+                }
+            }
+
+            costs = next;
+            previous.push(from);
+        }
+
+        let mut best = (0..4)
+            .filter(|&m| costs[m].is_some())
+            .min_by_key(|&m| costs[m].map(round))
+            .unwrap_or(2);
+        let mut modes = vec![Mode::Byte; units.len()];
+
+        for i in (0..units.len()).rev() {
+            modes[i] = MODES[best];
+            best = previous[i][best];
+        }
+
+        modes
+    }
+
+    // Builds the data bit stream (without terminator and padding) for the given version, or
+    // None if a segment is too long for its character count indicator.
+    fn data_bits(units: &[Unit], eci: Option<u32>, version: u8) -> Option<Vec<u8>> {
+        let modes = QRCode::segment_modes(units, version);
+        let mut bits = vec![];
+
+        if let Some(eci) = eci {
+            push_bits(&mut bits, 0b0111, 4);
+
+            match eci {
+                0..=127 => push_bits(&mut bits, eci, 8),
+                128..=16383 => push_bits(&mut bits, 0b10 << 14 | eci, 16),
+                _ => push_bits(&mut bits, 0b110 << 21 | eci, 24),
+            }
+        }
+
+        let mut start = 0;
+
+        while start < units.len() {
+            let mode = modes[start];
+            let end = (start..units.len())
+                .find(|&i| modes[i] != mode)
+                .unwrap_or(units.len());
+            let segment = &units[start..end];
+            let count = match mode {
+                Mode::Byte => segment
+                    .iter()
+                    .map(|u| match u {
+                        Unit::Byte(_) => 1,
+                        Unit::Kanji(_) => 2,
+                    })
+                    .sum(),
+                _ => segment.len(),
+            };
+
+            if count >= 1 << mode.count_bits(version) {
+                return None;
+            }
+
+            push_bits(&mut bits, mode.indicator(), 4);
+            push_bits(&mut bits, count as u32, mode.count_bits(version));
+            encode_segment(&mut bits, mode, segment);
+            start = end;
+        }
+
+        Some(bits)
+    }
+
+    // Terminates and pads the data bits, then interleaves the data and error correction
+    // codewords.
+    fn codewords(&self) -> Vec<u8> {
+        let capacity = data_codewords(self.version, self.ecc) * 8;
+        let mut bits = self.bits.clone();
+        let terminator = (capacity - bits.len()).min(4);
+
+        bits.extend(vec![0; terminator]);
+        bits.extend(vec![0; (8 - bits.len() % 8) % 8]);
+
+        let mut data: Vec<u8> = bits
+            .chunks(8)
+            .map(|byte| byte.iter().fold(0, |acc, &b| (acc << 1) | b))
+            .collect();
+
+        for pad in [0xEC, 0x11].iter().cycle() {
+            if data.len() * 8 >= capacity {
+                break;
+            }
+
+            data.push(*pad);
+        }
+
+        let ecc = self.ecc.index();
+        let version = self.version as usize;
+        let blocks = ECC_BLOCKS[ecc][version] as usize;
+        let ecc_len = ECC_CODEWORDS[ecc][version] as usize;
+        let total = raw_modules(self.version) / 8;
+        let short_blocks = blocks - total % blocks;
+        let short_len = total / blocks - ecc_len;
+        let mut data_blocks = vec![];
+        let mut ecc_blocks = vec![];
+        let mut offset = 0;
+
+        for i in 0..blocks {
+            let len = short_len + usize::from(i >= short_blocks);
+            let block = &data[offset..offset + len];
+
+            let words: Vec<u16> = block.iter().map(|&c| c as u16).collect();
+
+            ecc_blocks.push(helpers::reed_solomon(&words, ecc_len, 0x11D, 0));
+            data_blocks.push(block);
+            offset += len;
+        }
+
+        let mut codewords = Vec::with_capacity(total);
+
+        for i in 0..=short_len {
+            for block in data_blocks.iter() {
+                if let Some(&c) = block.get(i) {
+                    codewords.push(c);
+                }
+            }
+        }
+
+        for i in 0..ecc_len {
+            for block in ecc_blocks.iter() {
+                codewords.push(block[i] as u8);
+            }
+        }
+
+        codewords
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Matrix of binary digits.
+    pub fn encode(&self) -> Matrix {
+        let size = self.version as usize * 4 + 17;
+        let mut symbol = Symbol {
+            matrix: Matrix::new(size, size),
+            function: vec![false; size * size],
+            size,
+        };
+
+        symbol.draw_function_patterns(self.version);
+        symbol.draw_codewords(&self.codewords());
+
+        let mut best: Option<(u32, Matrix)> = None;
+
+        for mask in 0..8 {
+            let mut candidate = symbol.clone();
+
+            candidate.apply_mask(mask);
+            candidate.draw_format(self.ecc, mask);
+
+            let penalty = candidate.penalty();
+
+            if best.as_ref().is_none_or(|(p, _)| penalty < *p) {
+                best = Some((penalty, candidate.matrix));
+            }
+        }
+
+        match best {
+            Some((_, matrix)) => matrix,
+            None => symbol.matrix,
+        }
+    }
+}
+
+// The symbol under construction, tracking which modules belong to function patterns.
+#[derive(Clone, Debug)]
+struct Symbol {
+    matrix: Matrix,
+    function: Vec<bool>,
+    size: usize,
+}
+
+impl Symbol {
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        self.matrix.set(x, y, u8::from(dark));
+        self.function[y * self.size + x] = true;
+    }
+
+    fn draw_function_patterns(&mut self, version: u8) {
+        let size = self.size;
+
+        for i in 0..size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+
+        for &(cx, cy) in [(3, 3), (size - 4, 3), (3, size - 4)].iter() {
+            for dy in -4i32..=4 {
+                for dx in -4i32..=4 {
+                    let (x, y) = (cx as i32 + dx, cy as i32 + dy);
+
+                    if x < 0 || y < 0 || x >= size as i32 || y >= size as i32 {
+                        continue;
+                    }
+
+                    let ring = dx.abs().max(dy.abs());
+                    self.set_function(x as usize, y as usize, ring != 2 && ring != 4);
+                }
+            }
+        }
+
+        let centres = alignment_positions(version);
+        let last = centres.len().saturating_sub(1);
+
+        for (i, &cy) in centres.iter().enumerate() {
+            for (j, &cx) in centres.iter().enumerate() {
+                if (i == 0 && (j == 0 || j == last)) || (i == last && j == 0) {
+                    continue;
+                }
+
+                for dy in -2i32..=2 {
+                    for dx in -2i32..=2 {
+                        let ring = dx.abs().max(dy.abs());
+                        let (x, y) = ((cx as i32 + dx) as usize, (cy as i32 + dy) as usize);
+
+                        self.set_function(x, y, ring != 1);
+                    }
+                }
+            }
+        }
+
+        // Reserve the format areas; they are drawn once the mask is known.
+        self.draw_format(ErrorCorrection::M, 0);
+
+        if version >= 7 {
+            let bits = (version as u32) << 12 | bch_remainder(version as u32, 0x1F25, 12);
+
+            for i in 0..18 {
+                let dark = (bits >> i) & 1 == 1;
+                let (a, b) = (size - 11 + i % 3, i / 3);
+
+                self.set_function(a, b, dark);
+                self.set_function(b, a, dark);
+            }
+        }
+    }
+
+    fn draw_format(&mut self, ecc: ErrorCorrection, mask: u8) {
+        let size = self.size;
+        let data = ecc.format_bits() << 3 | mask as u32;
+        let bits = (data << 10 | bch_remainder(data, 0x537, 10)) ^ 0x5412;
+        let bit = |i: usize| (bits >> i) & 1 == 1;
+
+        for i in 0..6 {
+            self.set_function(8, i, bit(i));
+        }
+
+        self.set_function(8, 7, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(7, 8, bit(8));
+
+        for i in 9..15 {
+            self.set_function(14 - i, 8, bit(i));
+        }
+
+        for i in 0..8 {
+            self.set_function(size - 1 - i, 8, bit(i));
+        }
+
+        for i in 8..15 {
+            self.set_function(8, size - 15 + i, bit(i));
+        }
+
+        self.set_function(8, size - 8, true);
+    }
+
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let size = self.size;
+        let mut i = 0;
+        let mut right = size - 1;
+
+        // Column pairs are filled in a zigzag from the bottom right, skipping the vertical
+        // timing pattern.
+        while right >= 1 {
+            if right == 6 {
+                right = 5;
+            }
+
+            let upward = ((right + 1) & 2) == 0;
+
+            for step in 0..size {
+                let y = if upward { size - 1 - step } else { step };
+
+                for x in [right, right - 1] {
+                    if self.function[y * size + x] {
+                        continue;
+                    }
+
+                    let dark = codewords
+                        .get(i / 8)
+                        .is_some_and(|c| (c >> (7 - i % 8)) & 1 == 1);
+
+                    self.matrix.set(x, y, u8::from(dark));
+                    i += 1;
+                }
+            }
+
+            if right < 2 {
+                break;
+            }
+
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: u8) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                if self.function[y * self.size + x] {
+                    continue;
+                }
+
+                let invert = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (y / 2 + x / 3) % 2 == 0,
+                    5 => (x * y) % 2 + (x * y) % 3 == 0,
+                    6 => ((x * y) % 2 + (x * y) % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + (x * y) % 3) % 2 == 0,
+                };
+
+                if invert {
+                    self.matrix.set(x, y, self.matrix.get(x, y) ^ 1);
+                }
+            }
+        }
+    }
+
+    fn penalty(&self) -> u32 {
+        let size = self.size;
+        let m = &self.matrix;
+        let mut penalty = 0;
+
+        for horizontal in [true, false] {
+            for a in 0..size {
+                let line: Vec<u8> = (0..size)
+                    .map(|b| if horizontal { m.get(b, a) } else { m.get(a, b) })
+                    .collect();
+                let mut run = 1;
+
+                for b in 1..=size {
+                    if b < size && line[b] == line[b - 1] {
+                        run += 1;
+                        continue;
+                    }
+
+                    if run >= 5 {
+                        penalty += PENALTY_RUN + run - 5;
+                    }
+
+                    run = 1;
+                }
+
+                let mut padded = vec![0; 4];
+                padded.extend(line);
+                padded.extend([0; 4]);
+
+                penalty += padded
+                    .windows(12)
+                    .filter(|w| FINDER_LIKE.iter().any(|p| p == w))
+                    .count() as u32
+                    * PENALTY_FINDER;
+            }
+        }
+
+        for y in 0..size - 1 {
+            for x in 0..size - 1 {
+                let c = m.get(x, y);
+
+                if c == m.get(x + 1, y) && c == m.get(x, y + 1) && c == m.get(x + 1, y + 1) {
+                    penalty += PENALTY_BLOCK;
+                }
+            }
+        }
+
+        let total = (size * size) as i64;
+        let dark = m.rows().flatten().filter(|&&c| c == 1).count() as i64;
+        let k = ((dark * 20 - total * 10).abs() + total - 1) / total - 1;
+
+        penalty + k as u32 * PENALTY_BALANCE
+    }
+}
+
+fn push_bits(bits: &mut Vec<u8>, value: u32, len: usize) {
+    bits.extend((0..len).rev().map(|i| ((value >> i) & 1) as u8));
+}
+
+fn encode_segment(bits: &mut Vec<u8>, mode: Mode, segment: &[Unit]) {
+    let bytes: Vec<u8> = segment
+        .iter()
+        .flat_map(|u| match *u {
+            Unit::Byte(b) => vec![b],
+            Unit::Kanji(k) => vec![(k >> 8) as u8, k as u8],
+        })
+        .collect();
+
+    match mode {
+        Mode::Numeric => {
+            for group in bytes.chunks(3) {
+                let value = group.iter().fold(0, |acc, &d| acc * 10 + (d - b'0') as u32);
+                push_bits(bits, value, group.len() * 3 + 1);
+            }
+        }
+        Mode::Alphanumeric => {
+            let value = |c: &u8| ALPHANUMERIC.iter().position(|a| a == c).unwrap_or(0) as u32;
+
+            for pair in bytes.chunks(2) {
+                match pair {
+                    [a, b] => push_bits(bits, value(a) * 45 + value(b), 11),
+                    [a] => push_bits(bits, value(a), 6),
+                    _ => (),
+                }
+            }
+        }
+        Mode::Byte => {
+            for b in bytes {
+                push_bits(bits, b as u32, 8);
+            }
+        }
+        Mode::Kanji => {
+            for unit in segment {
+                if let Unit::Kanji(k) = *unit {
+                    let k = if k >= 0xE040 { k - 0xC140 } else { k - 0x8140 } as u32;
+                    push_bits(bits, (k >> 8) * 0xC0 + (k & 0xFF), 13);
+                }
+            }
+        }
+    }
+}
+
+// The number of modules available for data and error correction codewords.
+fn raw_modules(version: u8) -> usize {
+    let v = version as usize;
+    let mut modules = (16 * v + 128) * v + 64;
+
+    if v >= 2 {
+        let alignments = v / 7 + 2;
+        modules -= (25 * alignments - 10) * alignments - 55;
+
+        if v >= 7 {
+            modules -= 36;
+        }
+    }
+
+    modules
+}
+
+fn data_codewords(version: u8, ecc: ErrorCorrection) -> usize {
+    let (e, v) = (ecc.index(), version as usize);
+
+    raw_modules(version) / 8 - ECC_CODEWORDS[e][v] as usize * ECC_BLOCKS[e][v] as usize
+}
+
+// The row/column coordinates of the alignment pattern centres.
+fn alignment_positions(version: u8) -> Vec<usize> {
+    if version == 1 {
+        return vec![];
+    }
+
+    let v = version as usize;
+    let count = v / 7 + 2;
+    let step = if v == 32 {
+        26
+    } else {
+        (v * 4 + count * 2 + 1) / (count * 2 - 2) * 2
+    };
+    let mut positions: Vec<usize> = (0..count - 1).map(|i| v * 4 + 10 - i * step).collect();
+
+    positions.push(6);
+    positions.reverse();
+    positions
+}
+
+fn bch_remainder(data: u32, generator: u32, degree: u32) -> u32 {
+    let mut rem = data << degree;
+
+    for i in (degree..=degree + 5).rev() {
+        if (rem >> i) & 1 == 1 {
+            rem ^= generator << (i - degree);
+        }
+    }
+
+    rem
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::sym::qr::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::string::String;
+
+    fn collapse_vec(v: &[u8]) -> String {
+        let chars = v.iter().map(|d| char::from_digit(*d as u32, 10).unwrap());
+        chars.collect()
+    }
+
+    #[test]
+    fn new_qr() {
+        let qr1 = QRCode::new("HELLO WORLD");
+        let qr2 = QRCode::with_ecc("https://example.com/?q=1", ErrorCorrection::H);
+        let qr3 = QRCode::with_eci("Grüße", ErrorCorrection::L, 26);
+
+        assert!(qr1.is_ok());
+        assert!(qr2.is_ok());
+        assert!(qr3.is_ok());
+    }
+
+    #[test]
+    fn invalid_len_qr() {
+        let qr1 = QRCode::new("");
+        let qr2 = QRCode::with_ecc("1".repeat(7090), ErrorCorrection::L);
+        let qr3 = QRCode::new("ABC").unwrap().min_version(41);
+
+        assert_eq!(qr1.err().unwrap(), Error::length(1..=usize::MAX, 0));
+        assert_eq!(qr2.err().unwrap(), Error::length(1..=23648, 23652));
+        assert_eq!(qr3.err().unwrap(), Error::option("version", 1..=40, 41));
+    }
+
+    #[test]
+    fn invalid_eci_qr() {
+        let qr = QRCode::with_eci("ABC", ErrorCorrection::M, 1_000_000);
+
+        assert!(matches!(
+            qr,
+            Err(Error::Option { name: "ECI", allowed, actual })
+                if allowed == (0.0..=999_999.0) && actual == 1_000_000.0
+        ));
+    }
+
+    #[test]
+    fn qr_capacity() {
+        let numeric = QRCode::with_ecc("1".repeat(7089), ErrorCorrection::L).unwrap();
+        let alphanumeric = QRCode::with_ecc("A".repeat(4296), ErrorCorrection::L).unwrap();
+        let bytes = QRCode::with_ecc("a".repeat(2953), ErrorCorrection::L).unwrap();
+        let small = QRCode::with_ecc("1".repeat(41), ErrorCorrection::L).unwrap();
+        let h = QRCode::with_ecc("1".repeat(18), ErrorCorrection::H).unwrap();
+
+        assert_eq!(numeric.version(), 40);
+        assert_eq!(alphanumeric.version(), 40);
+        assert_eq!(bytes.version(), 40);
+        assert!(QRCode::with_ecc("a".repeat(2954), ErrorCorrection::L).is_err());
+        assert_eq!(small.version(), 1);
+        assert_eq!(h.version(), 2);
+    }
+
+    #[test]
+    fn qr_segmentation() {
+        let qr = QRCode::new("HELLO 0123456789012345").unwrap();
+        let modes = QRCode::segment_modes(&qr.units, qr.version);
+
+        assert_eq!(modes[0], Mode::Alphanumeric);
+        assert_eq!(modes[5], Mode::Alphanumeric);
+        assert_eq!(modes[6], Mode::Numeric);
+        assert_eq!(modes[21], Mode::Numeric);
+    }
+
+    #[test]
+    fn qr_kanji() {
+        // "点茗" in Shift JIS, followed by ASCII digits.
+        let qr =
+            QRCode::shift_jis([0x93, 0x5F, 0xE4, 0xAA, 0x31, 0x32], ErrorCorrection::M).unwrap();
+
+        assert_eq!(qr.units[0], Unit::Kanji(0x935F));
+        assert_eq!(qr.units[1], Unit::Kanji(0xE4AA));
+        assert_eq!(collapse_vec(&qr.bits[..27]), "100000000010011011001111111");
+    }
+
+    #[test]
+    fn qr_data_bits() {
+        let qr = QRCode::with_ecc("01234567", ErrorCorrection::M).unwrap();
+
+        assert_eq!(
+            collapse_vec(&qr.bits),
+            "00010000001000000000110001010110011000011"
+        );
+    }
+
+    #[test]
+    fn qr_eci_bits() {
+        let qr = QRCode::with_eci("A", ErrorCorrection::M, 26).unwrap();
+
+        assert_eq!(collapse_vec(&qr.bits[..12]), "011100011010");
+    }
+
+    #[test]
+    fn qr_codewords() {
+        // The worked example of ISO/IEC 18004 Annex I: "01234567" at version 1-M.
+        let qr = QRCode::with_ecc("01234567", ErrorCorrection::M).unwrap();
+
+        assert_eq!(
+            qr.codewords(),
+            vec![
+                16, 32, 12, 86, 97, 128, 236, 17, 236, 17, 236, 17, 236, 17, 236, 17, 165, 36, 212,
+                193, 237, 54, 199, 135, 44, 85,
+            ]
+        );
+    }
+
+    #[test]
+    fn qr_encode() {
+        let qr = QRCode::new("HELLO WORLD").unwrap();
+        let matrix = qr.encode();
+
+        assert_eq!(matrix.width(), 21);
+        assert_eq!(matrix.height(), 21);
+        assert_eq!(
+            collapse_vec(matrix.rows().next().unwrap()),
+            "111111100010101111111"
+        );
+    }
+
+    #[test]
+    fn qr_version_info() {
+        let qr = QRCode::new("1").unwrap().min_version(7).unwrap();
+        let matrix = qr.encode();
+        let block: String = (0..6)
+            .map(|y| collapse_vec(&matrix.rows().nth(y).unwrap()[34..37]))
+            .collect();
+
+        assert_eq!(qr.version(), 7);
+        assert_eq!(matrix.width(), 45);
+        assert_eq!(block, "001010010011111000");
+    }
+}
@@ -0,0 +1,355 @@
+//! Best effort conversion of arbitrary text into data a symbology can encode.
+//!
+//! Data copied from spreadsheets and documents often carries stray whitespace, typographic quotes
+//! and accented letters that the encoders reject. [`sanitize`] replaces what it can and reports
+//! every change it made, so that the changes can be shown to the user before encoding:
+//!
+//! ```rust
+//! use barcoders::sym::code39::Code39;
+//! use barcoders::sym::metadata::Symbology;
+//! use barcoders::sym::sanitize::sanitize;
+//!
+//! let sanitized = sanitize(Symbology::Code39, " Café-1234 ");
+//!
+//! assert_eq!(sanitized.data(), "CAFE-1234");
+//! assert_eq!(sanitized.substitutions().len(), 5);
+//! assert!(Code39::new(sanitized.data()).is_ok());
+//! ```
+//!
+//! The conversions are tried in turn for each character the symbology cannot encode:
+//!   * Leading and trailing whitespace is removed.
+//!   * Hyphens and whitespace are removed from numeric data, such as GTINs.
+//!   * Typographic quotes, dashes and spaces become their ASCII equivalents.
+//!   * Accented letters become unaccented ASCII letters, such as 'é' to 'e' and 'ß' to "ss".
+//!   * Lowercase letters become uppercase, for symbologies without lowercase (such as Code39).
+//!
+//! Characters that cannot be converted are left as they are, so the encoder still reports them.
+//! Characters the symbology can encode are never changed, except that Code128 data is given a
+//! start character for set B unless it is already written with character sets (see [`sanitize`]).
+
+use crate::sym::code128::Code128;
+use crate::sym::helpers::{vec, Vec};
+use crate::sym::metadata::Symbology;
+#[cfg(not(feature = "std"))]
+use alloc::string::{String, ToString};
+
+// The characters selecting the character sets and function characters of Code128.
+const CODE128_SETS: [char; 3] = ['\u{00C0}', '\u{0181}', '\u{0106}'];
+const CODE128_FUNCTIONS: [char; 5] = ['\u{0179}', '\u{017A}', '\u{017B}', '\u{017C}', '\u{017D}'];
+const CODE128_SET_B: char = '\u{0181}';
+
+// Typographic characters and their ASCII equivalents.
+const TYPOGRAPHIC: [(&str, &str); 5] = [
+    ("\u{2018}\u{2019}\u{201A}\u{201B}\u{2032}", "'"),
+    ("\u{201C}\u{201D}\u{201E}\u{201F}\u{2033}", "\""),
+    ("\u{2010}\u{2011}\u{2012}\u{2013}\u{2014}\u{2212}", "-"),
+    ("\u{00A0}\u{2007}\u{202F}", " "),
+    ("\u{2026}", "..."),
+];
+
+// Accented letters of Latin-1 and Latin Extended-A (and the 'Ɓ' of Code128) and their unaccented
+// ASCII equivalents.
+const TRANSLITERATIONS: [(&str, &str); 61] = [
+    ("ÀÁÂÃÄÅĀĂĄ", "A"),
+    ("àáâãäåāăą", "a"),
+    ("Æ", "AE"),
+    ("æ", "ae"),
+    ("Ɓ", "B"),
+    ("ÇĆĈĊČ", "C"),
+    ("çćĉċč", "c"),
+    ("ÐĎĐ", "D"),
+    ("ðďđ", "d"),
+    ("ÈÉÊËĒĔĖĘĚ", "E"),
+    ("èéêëēĕėęě", "e"),
+    ("ĜĞĠĢ", "G"),
+    ("ĝğġģ", "g"),
+    ("ĤĦ", "H"),
+    ("ĥħ", "h"),
+    ("ÌÍÎÏĨĪĬĮİ", "I"),
+    ("ìíîïĩīĭįı", "i"),
+    ("Ĳ", "IJ"),
+    ("ĳ", "ij"),
+    ("Ĵ", "J"),
+    ("ĵ", "j"),
+    ("Ķ", "K"),
+    ("ķĸ", "k"),
+    ("ĹĻĽĿŁ", "L"),
+    ("ĺļľŀł", "l"),
+    ("ÑŃŅŇŊ", "N"),
+    ("ñńņňŉŋ", "n"),
+    ("ÒÓÔÕÖØŌŎŐ", "O"),
+    ("òóôõöøōŏő", "o"),
+    ("Œ", "OE"),
+    ("œ", "oe"),
+    ("ŔŖŘ", "R"),
+    ("ŕŗř", "r"),
+    ("ŚŜŞŠ", "S"),
+    ("śŝşšſ", "s"),
+    ("ß", "ss"),
+    ("ŢŤŦ", "T"),
+    ("ţťŧ", "t"),
+    ("Þ", "TH"),
+    ("þ", "th"),
+    ("ÙÚÛÜŨŪŬŮŰŲ", "U"),
+    ("ùúûüũūŭůűų", "u"),
+    ("Ŵ", "W"),
+    ("ŵ", "w"),
+    ("ÝŶŸ", "Y"),
+    ("ýÿŷ", "y"),
+    ("ŹŻŽ", "Z"),
+    ("źżž", "z"),
+    ("×", "x"),
+    ("÷", "/"),
+    ("«", "\""),
+    ("»", "\""),
+    ("´", "'"),
+    ("¸", ","),
+    ("·", "."),
+    ("¡", "!"),
+    ("¿", "?"),
+    ("¢", "c"),
+    ("©", "(C)"),
+    ("®", "(R)"),
+    ("°", "o"),
+];
+
+/// A change made to the data by [`sanitize`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Substitution {
+    /// The index of the replaced character, counted in characters of the original data.
+    pub index: usize,
+    /// The replaced character.
+    pub original: char,
+    /// The characters that replaced it, empty if the character was removed.
+    pub replacement: String,
+}
+
+/// Data converted for a symbology, along with every change made to it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sanitized {
+    data: String,
+    substitutions: Vec<Substitution>,
+}
+
+impl Sanitized {
+    /// Returns the converted data.
+    pub fn data(&self) -> &str {
+        &self.data
+    }
+
+    /// Returns the changes made to the data, in the order of the original characters.
+    pub fn substitutions(&self) -> &[Substitution] {
+        &self.substitutions
+    }
+
+    /// Returns true if the data was not changed.
+    pub fn is_unchanged(&self) -> bool {
+        self.substitutions.is_empty()
+    }
+}
+
+/// Converts the data into characters the symbology can encode, as far as possible.
+/// Returns the converted data and the substitutions made. The data is not otherwise validated,
+/// so it may still be rejected by the encoder, for example for its length.
+///
+/// Code128 data that starts with a character set (see [`code128`](crate::sym::code128)) and can
+/// be encoded is kept in that syntax. Other data is taken as plain text: the character set and
+/// function characters in it are converted like any other accented letter, and character set B
+/// is selected by inserting 'Ɓ' before the first character, reported as a substitution of it.
+pub fn sanitize<T: AsRef<str>>(symbology: Symbology, data: T) -> Sanitized {
+    let data = data.as_ref();
+    let metadata = symbology.metadata();
+    let valid = |s: &str| match &metadata.characters {
+        Some(characters) => s.chars().all(|c| characters.contains(&c)),
+        None => true,
+    };
+    let sanitized = convert(data, metadata.numeric, valid);
+
+    if symbology != Symbology::Code128
+        || sanitized.data.starts_with(CODE128_SETS) && Code128::new(&sanitized.data).is_ok()
+    {
+        return sanitized;
+    }
+
+    let text = |s: &str| valid(s) && !s.contains(CODE128_SETS) && !s.contains(CODE128_FUNCTIONS);
+    let mut sanitized = convert(data, false, text);
+
+    if let Some(first) = data.chars().position(|c| !c.is_whitespace()) {
+        sanitized.data.insert(0, CODE128_SET_B);
+
+        match sanitized
+            .substitutions
+            .iter_mut()
+            .find(|s| s.index == first)
+        {
+            Some(substitution) => substitution.replacement.insert(0, CODE128_SET_B),
+            None => {
+                let original = data.chars().nth(first).expect("Index is within the data");
+                let at = sanitized.substitutions.partition_point(|s| s.index < first);
+
+                sanitized.substitutions.insert(
+                    at,
+                    Substitution {
+                        index: first,
+                        original,
+                        replacement: [CODE128_SET_B, original].iter().collect(),
+                    },
+                );
+            }
+        }
+    }
+
+    sanitized
+}
+
+// Converts the characters that fail the predicate, trimming the data and removing separators
+// from numeric data.
+fn convert<F: Fn(&str) -> bool>(data: &str, numeric: bool, valid: F) -> Sanitized {
+    let first = data.chars().position(|c| !c.is_whitespace());
+    let last = data.chars().rev().position(|c| !c.is_whitespace());
+    let len = data.chars().count();
+    let mut sanitized = String::with_capacity(data.len());
+    let mut substitutions = vec![];
+
+    for (i, c) in data.chars().enumerate() {
+        let outside = first.is_none_or(|first| i < first || i >= len - last.unwrap_or(0));
+
+        let replacement = if outside || (numeric && is_separator(c)) {
+            Some(String::new())
+        } else if valid(c.encode_utf8(&mut [0; 4])) {
+            None
+        } else {
+            let ascii = lookup(&TYPOGRAPHIC, c)
+                .or_else(|| lookup(&TRANSLITERATIONS, c))
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| c.to_string());
+
+            [ascii.clone(), ascii.to_uppercase()]
+                .into_iter()
+                .find(|s| valid(s))
+        };
+
+        match replacement {
+            Some(replacement) => {
+                sanitized.push_str(&replacement);
+                substitutions.push(Substitution {
+                    index: i,
+                    original: c,
+                    replacement,
+                });
+            }
+            None => sanitized.push(c),
+        }
+    }
+
+    Sanitized {
+        data: sanitized,
+        substitutions,
+    }
+}
+
+// Returns true if the character separates groups of digits, such as in a GTIN.
+fn is_separator(c: char) -> bool {
+    c.is_whitespace() || c == '-' || lookup(&TYPOGRAPHIC[2..4], c).is_some()
+}
+
+// Returns the replacement for the character in the given table.
+fn lookup(table: &[(&str, &'static str)], c: char) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|(chars, _)| chars.contains(c))
+        .map(|&(_, replacement)| replacement)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sym::code128::Code128;
+    use crate::sym::code39::Code39;
+    use crate::sym::ean13::EAN13;
+    use crate::sym::metadata::Symbology;
+    use crate::sym::sanitize::*;
+
+    fn substitution(index: usize, original: char, replacement: &str) -> Substitution {
+        Substitution {
+            index,
+            original,
+            replacement: replacement.to_string(),
+        }
+    }
+
+    #[test]
+    fn sanitize_code39() {
+        let sanitized = sanitize(Symbology::Code39, "\tab-12 ");
+
+        assert_eq!(sanitized.data(), "AB-12");
+        assert_eq!(
+            sanitized.substitutions(),
+            [
+                substitution(0, '\t', ""),
+                substitution(1, 'a', "A"),
+                substitution(2, 'b', "B"),
+                substitution(6, ' ', ""),
+            ]
+        );
+        assert!(Code39::new(sanitized.data()).is_ok());
+    }
+
+    #[test]
+    fn sanitize_gtin() {
+        let sanitized1 = sanitize(Symbology::EAN13, " 750-1031 311309\u{a0}");
+        let sanitized2 = sanitize(Symbology::EAN13, "7501031311309");
+
+        assert_eq!(sanitized1.data(), "7501031311309");
+        assert_eq!(sanitized1.substitutions().len(), 4);
+        assert_eq!(sanitized1.substitutions()[1], substitution(4, '-', ""));
+        assert!(EAN13::new(sanitized1.data()).is_ok());
+        assert!(sanitized2.is_unchanged());
+    }
+
+    #[test]
+    fn sanitize_code128() {
+        let sanitized1 = sanitize(Symbology::Code128, " naïve “quote” ×2…");
+        let sanitized2 = sanitize(Symbology::Code128, "Àvila");
+        let sanitized3 = sanitize(Symbology::Code128, "Ćrème brûlée");
+
+        assert_eq!(sanitized1.data(), "Ɓnaive \"quote\" x2...");
+        assert_eq!(
+            sanitized1.substitutions()[..3],
+            [
+                substitution(0, ' ', ""),
+                substitution(1, 'n', "Ɓn"),
+                substitution(3, 'ï', "i"),
+            ]
+        );
+        assert_eq!(sanitized2.data(), "ƁAvila");
+        assert_eq!(sanitized2.substitutions(), [substitution(0, 'À', "ƁA")]);
+        assert_eq!(sanitized3.data(), "ƁCreme brulee");
+
+        for sanitized in [sanitized1, sanitized2, sanitized3] {
+            assert!(Code128::new(sanitized.data()).is_ok());
+        }
+    }
+
+    #[test]
+    fn sanitize_code128_syntax() {
+        let sanitized1 = sanitize(Symbology::Code128, "ÀHELLOĆ1234");
+        let sanitized2 = sanitize(Symbology::Code128, "Ɓ“Crème”");
+
+        assert!(sanitized1.is_unchanged());
+        assert_eq!(sanitized2.data(), "Ɓ\"Creme\"");
+        assert_eq!(sanitized2.substitutions().len(), 3);
+    }
+
+    #[test]
+    fn sanitize_unconvertible() {
+        let sanitized1 = sanitize(Symbology::Code39, "AB☺");
+        let sanitized2 = sanitize(Symbology::QRCode, " ☺ ");
+        let sanitized3 = sanitize(Symbology::Code39, "  ");
+
+        assert_eq!(sanitized1.data(), "AB☺");
+        assert!(sanitized1.is_unchanged());
+        assert_eq!(sanitized2.data(), "☺");
+        assert_eq!(sanitized3.data(), "");
+        assert_eq!(sanitized3.substitutions().len(), 2);
+    }
+}
@@ -0,0 +1,553 @@
+//! Barcode specifications, made of a symbology, the data to encode and the encoding options.
+//!
+//! A specification describes a barcode without creating it, so that barcodes can be kept in
+//! configuration such as label templates. With the `serde` feature enabled, specifications (along
+//! with the generators) can be serialized and deserialized:
+//!
+//! ```rust
+//! use barcoders::sym::metadata::Symbology;
+//! use barcoders::sym::spec::*;
+//!
+//! let spec = Spec::new(Symbology::Code39, "CODE39").options(Options {
+//!     checksum: true,
+//!     ratio: Some(3.0),
+//!     ..Options::default()
+//! });
+//!
+//! match spec.encode().unwrap() {
+//!     Encoded::Linear(elements) => assert_eq!(elements.width(), 143.0),
+//!     _ => unreachable!(),
+//! }
+//! ```
+//!
+//! Serialized as JSON, the specification above is:
+//! ```javascript
+//! {
+//!   "symbology": "Code39",
+//!   "data": "CODE39",
+//!   "options": {"checksum": true, "ratio": 3.0}
+//! }
+//! ```
+//!
+//! The options can be left out, as can any of their fields, to use the defaults. Each option is
+//! validated by the encoder it applies to, and ignored by the other symbologies.
+
+use crate::error::{Error, Result};
+use crate::sym::aztec::Aztec;
+use crate::sym::codabar::Codabar;
+use crate::sym::codablock::CodablockF;
+use crate::sym::code11::Code11;
+use crate::sym::code128::Code128;
+use crate::sym::code32::Code32;
+use crate::sym::code39::Code39;
+use crate::sym::code93::Code93;
+use crate::sym::databar::DataBarExpanded;
+use crate::sym::datamatrix::DataMatrix;
+use crate::sym::deutsche_post::{Identcode, Leitcode};
+use crate::sym::ean13::EAN13;
+use crate::sym::ean8::EAN8;
+use crate::sym::ean_supp::EANSUPP;
+use crate::sym::helpers::Vec;
+use crate::sym::hibc::HIBC;
+use crate::sym::maxicode::MaxiCode;
+use crate::sym::metadata::Symbology;
+use crate::sym::pdf417::{MicroPDF417, PDF417};
+use crate::sym::pharmacode::{Pharmacode, PharmacodeTwoTrack};
+use crate::sym::pzn::PZN;
+use crate::sym::qr::{ErrorCorrection, QRCode};
+use crate::sym::tf::TF;
+use crate::sym::upca::UPCA;
+use crate::sym::{Elements, Matrix};
+#[cfg(not(feature = "std"))]
+use alloc::string::{String, ToString};
+
+/// The options used to encode a barcode.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Options {
+    /// Adds the check character to symbologies where it is optional, such as Code39 and the
+    /// 2-of-5 symbologies.
+    pub checksum: bool,
+    /// The wide-to-narrow ratio (2-3) of the linear symbologies with wide elements, such as
+    /// Code39 and the 2-of-5 symbologies. See `Elements::with_ratio`.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub ratio: Option<f64>,
+    /// The width in modules (1-5) of the inter-character gap of Code39, Codabar and Code11. See
+    /// `Elements::with_gap`.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub gap: Option<f64>,
+    /// The error correction level of QR Code. The default is M.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub ecc: Option<ErrorCorrection>,
+    /// The smallest version (1-40) of QR Code.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub version: Option<u8>,
+    /// The ECI assignment number (0-999999) that QR Code data starts with.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub eci: Option<u32>,
+    /// The mode (4-6) of MaxiCode. The default is 4.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub mode: Option<u8>,
+    /// The number of segments per row (an even number from 2 to 22) of DataBar Expanded, which
+    /// is then encoded in its stacked form.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub segments: Option<usize>,
+    /// The number of data columns of PDF417 (1-30), MicroPDF417 (1-4) and Codablock F (4-62).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub columns: Option<usize>,
+    /// The security level (0-8) of PDF417, which sets the number of error correction codewords.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub security_level: Option<u8>,
+}
+
+/// A barcode specification.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Spec {
+    /// The symbology of the barcode.
+    pub symbology: Symbology,
+    /// The data to encode.
+    pub data: String,
+    /// The options used to encode the barcode.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub options: Options,
+}
+
+/// An encoded barcode.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Encoded {
+    /// The bars and spaces of a linear symbology, with the ratio and gap options applied.
+    Linear(Elements),
+    /// The rows of binary digits of DataBar Expanded Stacked, as returned by
+    /// `DataBarExpanded::encode_stacked`.
+    Stacked(Vec<Vec<u8>>),
+    /// The modules of a stacked or two-dimensional symbology.
+    Matrix(Matrix),
+}
+
+impl Spec {
+    /// Returns a new specification with the default options.
+    pub fn new<T: AsRef<str>>(symbology: Symbology, data: T) -> Spec {
+        Spec {
+            symbology,
+            data: data.as_ref().to_string(),
+            options: Options::default(),
+        }
+    }
+
+    /// Sets whether an optional check character is added.
+    pub fn checksum(mut self, checksum: bool) -> Spec {
+        self.options.checksum = checksum;
+        self
+    }
+
+    /// Sets the options used to encode the barcode.
+    pub fn options(mut self, options: Options) -> Spec {
+        self.options = options;
+        self
+    }
+
+    /// Creates and encodes the barcode.
+    /// Returns Result<Encoded, Error> indicating parse success.
+    pub fn encode(&self) -> Result<Encoded> {
+        let data = self.data.as_str();
+        let options = &self.options;
+        let tf = |tf: TF| match options.checksum {
+            true => tf.with_checksum(),
+            false => tf,
+        };
+
+        let elements = match self.symbology {
+            Symbology::Codabar => Codabar::new(data)?.elements(),
+            Symbology::Code11 => Code11::new(data)?.elements(),
+            Symbology::Code128 => Code128::new(data)?.elements(),
+            Symbology::Code32 => Code32::new(data)?.elements(),
+            Symbology::Code39 if options.checksum => Code39::with_checksum(data)?.elements(),
+            Symbology::Code39 => Code39::new(data)?.elements(),
+            Symbology::Code93 => Code93::new(data)?.elements(),
+            Symbology::DataBarExpanded => match options.segments {
+                Some(segments) => {
+                    let databar = DataBarExpanded::stacked(data, segments)?;
+                    return Ok(Encoded::Stacked(databar.encode_stacked()));
+                }
+                None => DataBarExpanded::new(data)?.elements(),
+            },
+            Symbology::Identcode => Identcode::new(data)?.elements(),
+            Symbology::Leitcode => Leitcode::new(data)?.elements(),
+            Symbology::EAN13 => EAN13::new(data)?.elements(),
+            Symbology::EAN8 => EAN8::new(data)?.elements(),
+            Symbology::EAN2 | Symbology::EAN5 => {
+                let length = self.symbology.metadata().length;
+                let len = data.chars().count();
+
+                if !length.contains(&len) {
+                    return Err(Error::length(length, len));
+                }
+
+                EANSUPP::new(data)?.elements()
+            }
+            Symbology::HIBC => HIBC::new(data)?.code39().elements(),
+            Symbology::Pharmacode => Pharmacode::new(data)?.elements(),
+            Symbology::PZN => PZN::new(data)?.elements(),
+            Symbology::Standard2of5 => tf(TF::standard(data)?).elements(),
+            Symbology::Interleaved2of5 => tf(TF::interleaved(data)?).elements(),
+            Symbology::Matrix2of5 => tf(TF::matrix(data)?).elements(),
+            Symbology::IATA2of5 => tf(TF::iata(data)?).elements(),
+            Symbology::Datalogic2of5 => tf(TF::datalogic(data)?).elements(),
+            Symbology::COOP2of5 => tf(TF::coop(data)?).elements(),
+            Symbology::UPCA => UPCA::new(data)?.elements(),
+            Symbology::Aztec
+            | Symbology::CodablockF
+            | Symbology::DataMatrix
+            | Symbology::MaxiCode
+            | Symbology::MicroPDF417
+            | Symbology::PDF417
+            | Symbology::PharmacodeTwoTrack
+            | Symbology::QRCode => return self.encode_matrix().map(Encoded::Matrix),
+        };
+        let elements = match options.ratio {
+            Some(ratio) => elements.with_ratio(ratio)?,
+            None => elements,
+        };
+        let elements = match options.gap {
+            Some(gap) => elements.with_gap(gap)?,
+            None => elements,
+        };
+
+        Ok(Encoded::Linear(elements))
+    }
+
+    // Creates and encodes the barcode of a stacked or two-dimensional symbology.
+    fn encode_matrix(&self) -> Result<Matrix> {
+        let data = self.data.as_str();
+        let options = &self.options;
+
+        let matrix = match self.symbology {
+            Symbology::Aztec => Aztec::new(data)?.encode(),
+            Symbology::CodablockF => match options.columns {
+                Some(columns) => CodablockF::new(data)?.columns(columns)?.encode(),
+                None => CodablockF::new(data)?.encode(),
+            },
+            Symbology::DataMatrix => DataMatrix::new(data)?.encode(),
+            Symbology::MaxiCode => MaxiCode::with_mode(data, options.mode.unwrap_or(4))?.encode(),
+            Symbology::MicroPDF417 => match options.columns {
+                Some(columns) => MicroPDF417::new(data)?.columns(columns)?.encode(),
+                None => MicroPDF417::new(data)?.encode(),
+            },
+            Symbology::PDF417 => {
+                let mut pdf417 = PDF417::new(data)?;
+
+                if let Some(columns) = options.columns {
+                    pdf417 = pdf417.columns(columns)?;
+                }
+
+                if let Some(level) = options.security_level {
+                    pdf417 = pdf417.security_level(level)?;
+                }
+
+                pdf417.encode()
+            }
+            Symbology::PharmacodeTwoTrack => PharmacodeTwoTrack::new(data)?.encode(),
+            Symbology::QRCode => {
+                let ecc = options.ecc.unwrap_or(ErrorCorrection::M);
+                let qr = match options.eci {
+                    Some(eci) => QRCode::with_eci(data, ecc, eci)?,
+                    None => QRCode::with_ecc(data, ecc)?,
+                };
+
+                match options.version {
+                    Some(version) => qr.min_version(version)?.encode(),
+                    None => qr.encode(),
+                }
+            }
+            symbology => unreachable!("{:?} is a linear symbology", symbology),
+        };
+
+        Ok(matrix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::ErrorKind;
+    use crate::sym::code39::Code39;
+    use crate::sym::databar::DataBarExpanded;
+    use crate::sym::maxicode::MaxiCode;
+    use crate::sym::metadata::Symbology;
+    use crate::sym::pdf417::PDF417;
+    use crate::sym::qr::{ErrorCorrection, QRCode};
+    use crate::sym::spec::*;
+    use crate::sym::tf::TF;
+
+    #[test]
+    fn spec_encode() {
+        let code39 = Spec::new(Symbology::Code39, "CODE39").checksum(true);
+        let itf = Spec::new(Symbology::Interleaved2of5, "1234567");
+        let qr = Spec::new(Symbology::QRCode, "HELLO");
+
+        assert_eq!(
+            code39.encode().unwrap(),
+            Encoded::Linear(Code39::with_checksum("CODE39").unwrap().elements())
+        );
+        assert_eq!(
+            itf.encode().unwrap(),
+            Encoded::Linear(TF::interleaved("1234567").unwrap().elements())
+        );
+        assert_eq!(
+            qr.encode().unwrap(),
+            Encoded::Matrix(QRCode::new("HELLO").unwrap().encode())
+        );
+    }
+
+    #[test]
+    fn spec_options() {
+        let itf = Spec::new(Symbology::Interleaved2of5, "1234").options(Options {
+            checksum: true,
+            ratio: Some(2.5),
+            ..Options::default()
+        });
+        let code39 = Spec::new(Symbology::Code39, "CODE39").options(Options {
+            gap: Some(2.0),
+            ..Options::default()
+        });
+        let qr = Spec::new(Symbology::QRCode, "HELLO").options(Options {
+            ecc: Some(ErrorCorrection::H),
+            version: Some(5),
+            ..Options::default()
+        });
+        let maxicode = Spec::new(Symbology::MaxiCode, "HELLO").options(Options {
+            mode: Some(5),
+            ..Options::default()
+        });
+        let databar =
+            Spec::new(Symbology::DataBarExpanded, "(01)98898765432106").options(Options {
+                segments: Some(4),
+                ..Options::default()
+            });
+        let pdf417 = Spec::new(Symbology::PDF417, "HELLO").options(Options {
+            columns: Some(4),
+            security_level: Some(3),
+            ..Options::default()
+        });
+
+        assert_eq!(
+            itf.encode().unwrap(),
+            Encoded::Linear(
+                TF::interleaved("1234")
+                    .unwrap()
+                    .with_checksum()
+                    .elements()
+                    .with_ratio(2.5)
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            code39.encode().unwrap(),
+            Encoded::Linear(
+                Code39::new("CODE39")
+                    .unwrap()
+                    .elements()
+                    .with_gap(2.0)
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            qr.encode().unwrap(),
+            Encoded::Matrix(
+                QRCode::with_ecc("HELLO", ErrorCorrection::H)
+                    .unwrap()
+                    .min_version(5)
+                    .unwrap()
+                    .encode()
+            )
+        );
+        assert_eq!(
+            maxicode.encode().unwrap(),
+            Encoded::Matrix(MaxiCode::with_mode("HELLO", 5).unwrap().encode())
+        );
+        assert_eq!(
+            databar.encode().unwrap(),
+            Encoded::Stacked(
+                DataBarExpanded::stacked("(01)98898765432106", 4)
+                    .unwrap()
+                    .encode_stacked()
+            )
+        );
+        assert_eq!(
+            pdf417.encode().unwrap(),
+            Encoded::Matrix(
+                PDF417::new("HELLO")
+                    .unwrap()
+                    .columns(4)
+                    .unwrap()
+                    .security_level(3)
+                    .unwrap()
+                    .encode()
+            )
+        );
+    }
+
+    #[test]
+    fn spec_invalid_options() {
+        let options = [
+            (
+                Symbology::Code39,
+                "ratio",
+                Options {
+                    ratio: Some(5.0),
+                    ..Options::default()
+                },
+            ),
+            (
+                Symbology::Code39,
+                "gap",
+                Options {
+                    gap: Some(f64::INFINITY),
+                    ..Options::default()
+                },
+            ),
+            (
+                Symbology::QRCode,
+                "version",
+                Options {
+                    version: Some(41),
+                    ..Options::default()
+                },
+            ),
+            (
+                Symbology::QRCode,
+                "ECI",
+                Options {
+                    eci: Some(1_000_000),
+                    ..Options::default()
+                },
+            ),
+            (
+                Symbology::MaxiCode,
+                "mode",
+                Options {
+                    mode: Some(7),
+                    ..Options::default()
+                },
+            ),
+            (
+                Symbology::DataBarExpanded,
+                "segments",
+                Options {
+                    segments: Some(3),
+                    ..Options::default()
+                },
+            ),
+            (
+                Symbology::PDF417,
+                "columns",
+                Options {
+                    columns: Some(31),
+                    ..Options::default()
+                },
+            ),
+            (
+                Symbology::PDF417,
+                "security level",
+                Options {
+                    security_level: Some(9),
+                    ..Options::default()
+                },
+            ),
+            (
+                Symbology::MicroPDF417,
+                "columns",
+                Options {
+                    columns: Some(5),
+                    ..Options::default()
+                },
+            ),
+            (
+                Symbology::CodablockF,
+                "columns",
+                Options {
+                    columns: Some(3),
+                    ..Options::default()
+                },
+            ),
+        ];
+
+        for (symbology, option, options) in options {
+            let data = match symbology {
+                Symbology::DataBarExpanded => "(01)98898765432106",
+                _ => "HELLO",
+            };
+
+            assert!(
+                matches!(
+                    Spec::new(symbology, data).options(options).encode(),
+                    Err(Error::Option { name, .. }) if name == option
+                ),
+                "{:?}",
+                symbology
+            );
+        }
+    }
+
+    #[test]
+    fn spec_invalid() {
+        let ean2 = Spec::new(Symbology::EAN2, "12345");
+        let ean13 = Spec::new(Symbology::EAN13, "75010313113A");
+
+        assert_eq!(ean2.encode().unwrap_err().kind(), ErrorKind::Length);
+        assert_eq!(ean13.encode().unwrap_err().kind(), ErrorKind::Character);
+    }
+
+    #[test]
+    fn spec_encode_all() {
+        for symbology in Symbology::ALL {
+            let data = match symbology {
+                Symbology::Identcode => "56310243031",
+                Symbology::Leitcode => "2134807501640",
+                Symbology::EAN2 => "12",
+                Symbology::EAN5 => "51299",
+                Symbology::Pharmacode | Symbology::PharmacodeTwoTrack => "1234",
+                Symbology::DataBarExpanded => "(01)98898765432106",
+                Symbology::Code32 => "01234567",
+                Symbology::PZN => "1234562",
+                Symbology::Codabar => "A1234B",
+                Symbology::EAN8 => "5512345",
+                Symbology::UPCA => "03600029145",
+                Symbology::Code128 => "ÀHELLO",
+                Symbology::HIBC => "A123BJC5D6E71",
+                _ => "750103131130",
+            };
+
+            assert!(
+                Spec::new(symbology, data).encode().is_ok(),
+                "{:?}",
+                symbology
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn spec_serde() {
+        let spec = Spec::new(Symbology::QRCode, "HELLO").options(Options {
+            checksum: true,
+            ecc: Some(ErrorCorrection::Q),
+            version: Some(2),
+            ..Options::default()
+        });
+        let json = r#"{"symbology":"QRCode","data":"HELLO","options":{"checksum":true,"ecc":"Q","version":2}}"#;
+
+        assert_eq!(serde_json::to_string(&spec).unwrap(), json);
+        assert_eq!(serde_json::from_str::<Spec>(json).unwrap(), spec);
+        assert_eq!(
+            serde_json::from_str::<Spec>(r#"{"symbology":"EAN13","data":"750103131130"}"#).unwrap(),
+            Spec::new(Symbology::EAN13, "750103131130")
+        );
+        assert_eq!(
+            serde_json::from_str::<Spec>(r#"{"symbology":"Industrial2of5","data":"1234"}"#)
+                .unwrap(),
+            Spec::new(Symbology::Standard2of5, "1234")
+        );
+    }
+}
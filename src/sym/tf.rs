@@ -6,13 +6,27 @@
 //! groups of products (cartons of Cola, etc).
 //!
 //! Most of the time you will want to use the interleaved barcode over the standard option.
+//!
+//! The other members of the family share the same digit patterns:
+//!   * Matrix 2-of-5, with three bars and two spaces per digit.
+//!   * IATA 2-of-5, used on airline baggage tags.
+//!   * Datalogic 2-of-5, a variant of Matrix 2-of-5 with shorter start and stop characters.
+//!   * COOP 2-of-5, a variant of Matrix 2-of-5 with the digit patterns in a different order.
+//!
+//! Any of them can carry an optional modulo-10 check digit.
+//!
+//! Standard 2-of-5 is also known as Industrial 2-of-5, so `TF::industrial` creates a standard
+//! barcode.
 
+use crate::checksum;
 use crate::error::Result;
 use crate::sym::helpers;
-use crate::sym::Parse;
+use crate::sym::{Element, Elements, Parse};
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
 use core::char;
 use core::ops::Range;
-use helpers::{vec, Vec};
+use helpers::Vec;
 
 #[rustfmt::skip]
 const WIDTHS: [&str; 10] = [
@@ -22,18 +36,70 @@ const WIDTHS: [&str; 10] = [
     "NWNWN",
 ];
 
-const ITF_START: [u8; 4] = [1, 0, 1, 0];
-const ITF_STOP: [u8; 4] = [1, 1, 0, 1];
-const STF_START: [u8; 8] = [1, 1, 0, 1, 1, 0, 1, 0];
-const STF_STOP: [u8; 8] = [1, 1, 0, 1, 0, 1, 1, 0];
+// The start and stop characters as alternating bars and spaces, starting with a bar.
+const ITF_START: &str = "NNNN";
+const ITF_STOP: &str = "wNN";
+const STF_START: &str = "wNwNNN";
+const STF_STOP: &str = "wNNNwN";
+const MATRIX_START: &str = "4NNNNN";
+const MATRIX_STOP: &str = "4NNNN";
+const COOP_START: &str = "WNWN";
+const COOP_STOP: &str = "NWN";
+
+// IATA and Datalogic 2-of-5 share their start and stop characters.
+const SHORT_START: &str = "NNNN";
+const SHORT_STOP: &str = "WNN";
+
+// Returns a narrow (N) or wide (W) bar or space. Wide elements are three modules wide unless
+// drawn at another ratio, except those of the ITF and STF start and stop characters (w), which
+// are two modules wide. A digit gives an element of that many modules whatever the ratio, such
+// as the four module bar of the Matrix 2-of-5 start and stop characters.
+fn element(bar: bool, c: char) -> Element {
+    let element = if bar { Element::bar } else { Element::space };
+
+    match c {
+        'W' => element(3.0).wide(),
+        'w' => element(2.0).wide(),
+        'N' => element(1.0),
+        c => element(c.to_digit(10).expect("Unknown width") as f64),
+    }
+}
 
-/// The 2-of-5 barcode type.
-#[derive(Debug)]
-pub enum TF {
+// Returns the elements of a start or stop character.
+fn guard(widths: &str) -> Elements {
+    widths
+        .chars()
+        .enumerate()
+        .map(|(i, c)| element(i % 2 == 0, c))
+        .collect()
+}
+
+// The WIDTHS index of each digit in COOP 2-of-5.
+const COOP_DIGITS: [u8; 10] = [3, 7, 4, 0, 2, 9, 6, 1, 8, 5];
+
+/// The members of the 2-of-5 family.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Variant {
     /// The standard 2-of-5 barcode type.
-    Standard(Vec<u8>),
+    Standard,
     /// The interleaved 2-of-5 barcode type.
-    Interleaved(Vec<u8>),
+    Interleaved,
+    /// The matrix 2-of-5 barcode type.
+    Matrix,
+    /// The IATA 2-of-5 barcode type.
+    IATA,
+    /// The Datalogic 2-of-5 barcode type.
+    Datalogic,
+    /// The COOP 2-of-5 barcode type.
+    COOP,
+}
+
+/// The 2-of-5 barcode type.
+#[derive(Debug)]
+pub struct TF {
+    variant: Variant,
+    digits: Vec<u8>,
+    check_digit: bool,
 }
 
 impl TF {
@@ -41,108 +107,219 @@ impl TF {
     /// If the length of the given data is odd, a checksum value will be computed and appended to
     /// the data for encoding.
     ///
-    /// Returns Result<TF::Interleaved, Error> indicating parse success.
+    /// Returns Result<TF, Error> indicating parse success.
     pub fn interleaved<T: AsRef<str>>(data: T) -> Result<TF> {
-        TF::parse(data.as_ref()).map(|d| {
-            let mut digits: Vec<u8> = d
+        let mut tf = TF::digits(Variant::Interleaved, data.as_ref())?;
+
+        if tf.digits.len() % 2 == 1 {
+            tf.push_check_digit();
+        }
+
+        Ok(tf)
+    }
+
+    fn digits(variant: Variant, data: &str) -> Result<TF> {
+        TF::parse(data).map(|d| {
+            let digits = d
                 .chars()
                 .map(|c| c.to_digit(10).expect("Unknown character") as u8)
                 .collect();
-            let checksum_required = digits.len() % 2 == 1;
 
-            if checksum_required {
-                let check_digit = helpers::modulo_10_checksum(&digits[..], false);
-                digits.push(check_digit);
-            }
-
-            TF::Interleaved(digits)
+            TF::from_digits(variant, digits)
         })
     }
 
+    // Creates a barcode of the given digits, which must already be valid for the variant.
+    pub(crate) fn from_digits(variant: Variant, digits: Vec<u8>) -> TF {
+        TF {
+            variant,
+            digits,
+            check_digit: false,
+        }
+    }
+
     /// Creates a new STF barcode.
     ///
-    /// Returns Result<TF::Standard, Error> indicating parse success.
+    /// Returns Result<TF, Error> indicating parse success.
     pub fn standard<T: AsRef<str>>(data: T) -> Result<TF> {
-        TF::parse(data.as_ref()).map(|d| {
-            let digits: Vec<u8> = d
-                .chars()
-                .map(|c| c.to_digit(10).expect("Unknown character") as u8)
-                .collect();
-            TF::Standard(digits)
-        })
+        TF::digits(Variant::Standard, data.as_ref())
     }
 
-    fn raw_data(&self) -> &[u8] {
-        match *self {
-            TF::Standard(ref d) | TF::Interleaved(ref d) => &d[..],
+    /// Creates a new matrix 2-of-5 barcode.
+    ///
+    /// Returns Result<TF, Error> indicating parse success.
+    pub fn matrix<T: AsRef<str>>(data: T) -> Result<TF> {
+        TF::digits(Variant::Matrix, data.as_ref())
+    }
+
+    /// Creates a new IATA 2-of-5 barcode.
+    ///
+    /// Returns Result<TF, Error> indicating parse success.
+    pub fn iata<T: AsRef<str>>(data: T) -> Result<TF> {
+        TF::digits(Variant::IATA, data.as_ref())
+    }
+
+    /// Creates a new industrial 2-of-5 barcode, which is another name for standard 2-of-5.
+    ///
+    /// Returns Result<TF, Error> indicating parse success.
+    pub fn industrial<T: AsRef<str>>(data: T) -> Result<TF> {
+        TF::standard(data)
+    }
+
+    /// Creates a new Datalogic 2-of-5 barcode.
+    ///
+    /// Returns Result<TF, Error> indicating parse success.
+    pub fn datalogic<T: AsRef<str>>(data: T) -> Result<TF> {
+        TF::digits(Variant::Datalogic, data.as_ref())
+    }
+
+    /// Creates a new COOP 2-of-5 barcode.
+    ///
+    /// Returns Result<TF, Error> indicating parse success.
+    pub fn coop<T: AsRef<str>>(data: T) -> Result<TF> {
+        TF::digits(Variant::COOP, data.as_ref())
+    }
+
+    /// Appends a modulo-10 check digit to the data.
+    /// Interleaved barcodes need an even number of digits, so they are returned unchanged. They
+    /// are given a check digit on creation when the data is of odd length.
+    pub fn with_checksum(mut self) -> TF {
+        if self.variant != Variant::Interleaved {
+            self.push_check_digit();
+        }
+
+        self
+    }
+
+    fn push_check_digit(&mut self) {
+        let check_digit = checksum::modulo_10_digit(&self.digits[..], false);
+
+        self.digits.push(check_digit);
+        self.check_digit = true;
+    }
+
+    /// Returns the member of the 2-of-5 family.
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    /// Returns the check digit added by `interleaved` or `with_checksum`, if any.
+    pub fn check_digit(&self) -> Option<u8> {
+        match self.check_digit {
+            true => self.digits.last().copied(),
+            false => None,
         }
     }
 
-    fn interleave(&self, bars: u8, spaces: u8) -> Vec<u8> {
+    /// Returns the encoded data, including any check digit added by `interleaved` or
+    /// `with_checksum`.
+    pub fn data(&self) -> String {
+        helpers::digit_string(self.raw_data())
+    }
+
+    /// Returns the human readable text, which is the same as the data.
+    pub fn text(&self) -> String {
+        self.data()
+    }
+
+    fn raw_data(&self) -> &[u8] {
+        &self.digits[..]
+    }
+
+    // Encodes a pair of digits, the first in the bars and the second in the spaces.
+    fn interleave(&self, bars: u8, spaces: u8) -> Elements {
         let bwidths = WIDTHS[bars as usize].chars();
         let swidths = WIDTHS[spaces as usize].chars();
-        let mut encoding: Vec<u8> = vec![];
-
-        for (b, s) in bwidths.zip(swidths) {
-            for &(c, i) in &[(b, 1), (s, 0)] {
-                match c {
-                    'W' => encoding.extend([i; 3].iter().cloned()),
-                    _ => encoding.push(i),
-                }
-            }
-        }
 
-        encoding
+        bwidths
+            .zip(swidths)
+            .flat_map(|(b, s)| [element(true, b), element(false, s)])
+            .collect()
     }
 
-    fn char_encoding(&self, d: u8) -> Vec<u8> {
-        let bars: Vec<Vec<u8>> = self
+    // Encodes the digit in the bars, each followed by a narrow space.
+    fn char_encoding(&self, d: u8) -> Elements {
+        self.char_widths(d)
+            .chars()
+            .flat_map(|c| [element(true, c), Element::space(1.0)])
+            .collect()
+    }
+
+    // Encodes the digit as alternating bars and spaces, followed by a narrow space.
+    fn matrix_encoding(&self, d: u8) -> Elements {
+        let mut encoding: Elements = self
             .char_widths(d)
             .chars()
-            .map(|c| match c {
-                'W' => vec![1, 1, 1, 0],
-                _ => vec![1, 0],
-            })
+            .enumerate()
+            .map(|(i, c)| element(i % 2 == 0, c))
             .collect();
 
-        helpers::join_iters(bars.iter())
+        encoding.push(Element::space(1.0));
+        encoding
     }
 
     fn char_widths(&self, d: u8) -> &'static str {
-        WIDTHS[d as usize]
+        match self.variant {
+            Variant::COOP => WIDTHS[COOP_DIGITS[d as usize] as usize],
+            _ => WIDTHS[d as usize],
+        }
     }
 
-    fn stf_payload(&self) -> Vec<u8> {
-        let mut encodings = vec![];
+    fn stf_payload(&self) -> Elements {
+        let mut encodings = Elements::new();
 
         for d in self.raw_data() {
-            encodings.extend(self.char_encoding(*d).iter().cloned());
+            encodings.extend(self.char_encoding(*d));
         }
 
         encodings
     }
 
-    fn itf_payload(&self) -> Vec<u8> {
-        let weaves: Vec<Vec<u8>> = self
-            .raw_data()
-            .chunks(2)
-            .map(|c| self.interleave(c[0], c[1]))
-            .collect();
+    fn matrix_payload(&self) -> Elements {
+        let mut encodings = Elements::new();
+
+        for d in self.raw_data() {
+            encodings.extend(self.matrix_encoding(*d));
+        }
 
-        helpers::join_iters(weaves.iter())
+        encodings
+    }
+
+    fn itf_payload(&self) -> Elements {
+        let mut encodings = Elements::new();
+
+        for c in self.raw_data().chunks(2) {
+            encodings.extend(self.interleave(c[0], c[1]));
+        }
+
+        encodings
+    }
+
+    /// Encodes the barcode as a sequence of bar and space widths.
+    pub fn elements(&self) -> Elements {
+        let (start, stop) = match self.variant {
+            Variant::Standard => (STF_START, STF_STOP),
+            Variant::Interleaved => (ITF_START, ITF_STOP),
+            Variant::Matrix => (MATRIX_START, MATRIX_STOP),
+            Variant::IATA | Variant::Datalogic => (SHORT_START, SHORT_STOP),
+            Variant::COOP => (COOP_START, COOP_STOP),
+        };
+        let payload = match self.variant {
+            Variant::Interleaved => self.itf_payload(),
+            Variant::Standard | Variant::IATA => self.stf_payload(),
+            Variant::Matrix | Variant::Datalogic | Variant::COOP => self.matrix_payload(),
+        };
+        let mut elements = guard(start);
+
+        elements.extend(payload);
+        elements.extend(guard(stop));
+        elements
     }
 
     /// Encodes the barcode.
     /// Returns a Vec<u8> of binary digits.
     pub fn encode(&self) -> Vec<u8> {
-        match *self {
-            TF::Standard(_) => {
-                helpers::join_slices(&[&STF_START[..], &self.stf_payload()[..], &STF_STOP[..]][..])
-            }
-            TF::Interleaved(_) => {
-                helpers::join_slices(&[&ITF_START[..], &self.itf_payload()[..], &ITF_STOP[..]][..])
-            }
-        }
+        self.elements().modules()
     }
 }
 
@@ -174,42 +351,42 @@ mod tests {
 
     #[test]
     fn new_itf() {
-        let itf = TF::interleaved("12345679".to_string());
+        let itf = TF::interleaved("12345679");
 
         assert!(itf.is_ok());
     }
 
     #[test]
     fn new_stf() {
-        let stf = TF::standard("12345".to_string());
+        let stf = TF::standard("12345");
 
         assert!(stf.is_ok());
     }
 
     #[test]
     fn invalid_data_itf() {
-        let itf = TF::interleaved("1234er123412".to_string());
+        let itf = TF::interleaved("1234er123412");
 
-        assert_eq!(itf.err().unwrap(), Error::Character);
+        assert_eq!(itf.err().unwrap(), Error::character(4, 'e'));
     }
 
     #[test]
     fn invalid_data_stf() {
-        let stf = TF::standard("WORDUP".to_string());
+        let stf = TF::standard("WORDUP");
 
-        assert_eq!(stf.err().unwrap(), Error::Character);
+        assert_eq!(stf.err().unwrap(), Error::character(0, 'W'));
     }
 
     #[test]
     fn itf_raw_data() {
-        let itf = TF::interleaved("12345679".to_string()).unwrap();
+        let itf = TF::interleaved("12345679").unwrap();
 
         assert_eq!(itf.raw_data(), &[1, 2, 3, 4, 5, 6, 7, 9]);
     }
 
     #[test]
     fn itf_encode() {
-        let itf = TF::interleaved("1234567".to_string()).unwrap(); // Check digit: 0
+        let itf = TF::interleaved("1234567").unwrap(); // Check digit: 0
 
         assert_eq!(
             collapse_vec(itf.encode()),
@@ -220,8 +397,142 @@ mod tests {
 
     #[test]
     fn stf_encode() {
-        let stf = TF::standard("1234567".to_string()).unwrap();
+        let stf = TF::standard("1234567").unwrap();
 
         assert_eq!(collapse_vec(stf.encode()), "110110101110101010111010111010101110111011101010101010111010111011101011101010101110111010101010101110111011010110".to_string());
     }
+
+    #[test]
+    fn new_tf_variants() {
+        assert!(TF::matrix("12345").is_ok());
+        assert!(TF::iata("12345").is_ok());
+        assert!(TF::industrial("12345").is_ok());
+        assert!(TF::datalogic("12345").is_ok());
+        assert!(TF::coop("12345").is_ok());
+        assert_eq!(TF::coop("1234A").err().unwrap(), Error::character(4, 'A'));
+        assert_eq!(TF::matrix("").err().unwrap(), Error::length(1..=256, 0));
+    }
+
+    #[test]
+    fn tf_with_checksum() {
+        let matrix = TF::matrix("1234567").unwrap().with_checksum();
+        let itf = TF::interleaved("12345670").unwrap().with_checksum();
+
+        assert_eq!(matrix.raw_data(), &[1, 2, 3, 4, 5, 6, 7, 0]);
+        assert_eq!(itf.raw_data(), &[1, 2, 3, 4, 5, 6, 7, 0]);
+        assert_eq!(itf.check_digit(), None);
+        assert_eq!(itf.variant(), Variant::Interleaved);
+    }
+
+    #[test]
+    fn matrix_encode() {
+        let matrix = TF::matrix("1234567").unwrap();
+
+        assert_eq!(collapse_vec(matrix.encode()), "111101010111010111010001011101110001010101110111011101110101000111010101000111011110101".to_string());
+    }
+
+    #[test]
+    fn iata_encode() {
+        let iata = TF::iata("1234567").unwrap();
+
+        assert_eq!(collapse_vec(iata.encode()), "10101110101010111010111010101110111011101010101010111010111011101011101010101110111010101010101110111011101".to_string());
+    }
+
+    #[test]
+    fn industrial_encode() {
+        let industrial = TF::industrial("1234567").unwrap();
+        let stf = TF::standard("1234567").unwrap();
+
+        assert_eq!(industrial.encode(), stf.encode());
+    }
+
+    #[test]
+    fn datalogic_encode() {
+        let datalogic = TF::datalogic("1234567").unwrap();
+
+        assert_eq!(
+            collapse_vec(datalogic.encode()),
+            "1010111010111010001011101110001010101110111011101110101000111010101000111011101"
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn coop_encode() {
+        let coop = TF::coop("1234567").unwrap();
+
+        assert_eq!(
+            collapse_vec(coop.encode()),
+            "11101110101000111010111011101011100010100010111010001000101000111010111010111010001"
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn itf_elements() {
+        let itf = TF::interleaved("1234567").unwrap();
+        let widths: Vec<f64> = itf.elements().iter().map(|e| e.width()).collect();
+
+        assert_eq!(
+            &widths[..12],
+            &[1.0, 1.0, 1.0, 1.0, 3.0, 1.0, 1.0, 3.0, 1.0, 1.0, 1.0, 1.0][..]
+        );
+        assert_eq!(itf.elements().modules(), itf.encode());
+        assert!(itf.elements().iter().next().unwrap().is_bar());
+    }
+
+    #[test]
+    fn tf_ratio() {
+        let itf = TF::interleaved("1234567").unwrap();
+        let stf = TF::standard("1234567").unwrap();
+
+        // Each digit has two wide elements, as do the start and stop characters of STF, and the
+        // ITF stop character has one. Those of the start and stop characters are two modules
+        // wide unless drawn at another ratio.
+        assert_eq!(itf.elements().width(), 80.0);
+        assert_eq!(itf.elements().with_ratio(2.0).unwrap().width(), 64.0);
+        assert_eq!(
+            stf.elements().with_ratio(2.5).unwrap().width(),
+            114.0 - 7.0 + 2.0
+        );
+        assert_eq!(stf.elements().with_ratio(3.0).unwrap().width(), 114.0 + 4.0);
+        assert_eq!(itf.elements().iter().filter(|e| e.is_wide()).count(), 17);
+    }
+
+    #[test]
+    fn tf_ratio_guards() {
+        let tfs = [
+            TF::standard("1234567").unwrap(),
+            TF::interleaved("1234567").unwrap(),
+            TF::matrix("1234567").unwrap(),
+            TF::iata("1234567").unwrap(),
+            TF::datalogic("1234567").unwrap(),
+            TF::coop("1234567").unwrap(),
+        ];
+
+        for tf in tfs {
+            let elements = tf.elements().with_ratio(2.0).unwrap();
+
+            assert!(elements
+                .iter()
+                .filter(|e| e.is_wide())
+                .all(|e| e.width() == 2.0));
+            assert!(elements.iter().all(|e| e.is_wide() || e.width() != 3.0));
+        }
+    }
+
+    #[test]
+    fn tf_text() {
+        let itf = TF::interleaved("1234567").unwrap();
+        let stf = TF::standard("123").unwrap().with_checksum();
+        let matrix = TF::matrix("123").unwrap();
+
+        assert_eq!(itf.check_digit(), Some(0));
+        assert_eq!(itf.data(), "12345670");
+        assert_eq!(itf.text(), "12345670");
+        assert_eq!(stf.check_digit(), Some(6));
+        assert_eq!(stf.text(), "1236");
+        assert_eq!(matrix.check_digit(), None);
+        assert_eq!(matrix.data(), "123");
+    }
 }
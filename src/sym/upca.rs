@@ -5,8 +5,11 @@
 //! This module defines types for:
 //!   * UPC-A
 
+use crate::checksum;
 use crate::error::{Error, Result};
-use crate::sym::{helpers, Parse};
+use crate::sym::{helpers, Elements, Parse};
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
 use core::char;
 use core::ops::Range;
 use helpers::Vec;
@@ -44,21 +47,6 @@ pub const ENCODINGS: [[[u8; 7]; 10]; 2] = [
     ],
 ];
 
-/// Maps parity (odd/even) for the left-side digits based on the first digit in
-/// the number system portion of the barcode data.
-const PARITY: [[usize; 5]; 10] = [
-    [0, 0, 0, 0, 0],
-    [0, 1, 0, 1, 1],
-    [0, 1, 1, 0, 1],
-    [0, 1, 1, 1, 0],
-    [1, 0, 0, 1, 1],
-    [1, 1, 0, 0, 1],
-    [1, 1, 1, 0, 0],
-    [1, 0, 1, 0, 1],
-    [1, 0, 1, 1, 0],
-    [1, 1, 0, 1, 0],
-];
-
 /// The left-hand guard pattern.
 pub const LEFT_GUARD: [u8; 3] = [1, 0, 1];
 /// The middle guard pattern.
@@ -83,20 +71,33 @@ impl UPCA {
         let upca = UPCA(digits[0..11].to_vec());
 
         // If checksum digit is provided, check the checksum.
-        if digits.len() == 12 && upca.checksum_digit() != digits[11] {
-            return Err(Error::Checksum);
+        if digits.len() == 12 && upca.check_digit() != digits[11] {
+            return Err(Error::checksum(upca.check_digit(), digits[11]));
         }
 
         Ok(upca)
     }
 
-    /// Calculates the checksum digit using a modulo-10 weighting algorithm.
-    fn checksum_digit(&self) -> u8 {
-        helpers::modulo_10_checksum(&self.0[..], false)
+    /// Returns the check digit, calculated using a modulo-10 weighting algorithm.
+    pub fn check_digit(&self) -> u8 {
+        checksum::modulo_10_digit(&self.0[..], false)
+    }
+
+    /// Returns the encoded data, the twelve digits including the check digit.
+    pub fn data(&self) -> String {
+        let mut data = helpers::digit_string(&self.0);
+
+        data.push((b'0' + self.check_digit()) as char);
+        data
+    }
+
+    /// Returns the human readable text, which is the same as the data.
+    pub fn text(&self) -> String {
+        self.data()
     }
 
     fn checksum_encoding(&self) -> [u8; 7] {
-        self.char_encoding(1, self.checksum_digit())
+        self.char_encoding(1, self.check_digit())
     }
 
     fn char_encoding(&self, side: usize, d: u8) -> [u8; 7] {
@@ -145,6 +146,11 @@ impl UPCA {
             ][..],
         )
     }
+
+    /// Encodes the barcode as a sequence of bar and space widths.
+    pub fn elements(&self) -> Elements {
+        Elements::from(self.encode())
+    }
 }
 
 impl Parse for UPCA {
@@ -161,7 +167,6 @@ impl Parse for UPCA {
 
 #[cfg(test)]
 mod tests {
-    use crate::error::Error;
     use crate::sym::upca::*;
     #[cfg(not(feature = "std"))]
     use alloc::string::String;
@@ -183,21 +188,21 @@ mod tests {
     fn invalid_data_upca() {
         let upca = UPCA::new("012345612a45");
 
-        assert_eq!(upca.err().unwrap(), Error::Character)
+        assert_eq!(upca.err().unwrap(), Error::character(9, 'a'))
     }
 
     #[test]
     fn invalid_len_upca() {
         let upca = UPCA::new("1234561234589");
 
-        assert_eq!(upca.err().unwrap(), Error::Length)
+        assert_eq!(upca.err().unwrap(), Error::length(11..=12, 13))
     }
 
     #[test]
     fn invalid_checksum_upca() {
         let upca = UPCA::new("725272730705");
 
-        assert_eq!(upca.err().unwrap(), Error::Checksum)
+        assert_eq!(upca.err().unwrap(), Error::checksum(6, 5))
     }
     
     #[test]
@@ -218,4 +223,12 @@ mod tests {
         assert_eq!(collapse_vec(upca3.encode()), "10100011010001011011000101000110010011001100101010111001010001001010000101000011001101100110101");
     }
 
+    #[test]
+    fn upca_text() {
+        let upca = UPCA::new("03600029145").unwrap();
+
+        assert_eq!(upca.check_digit(), 2);
+        assert_eq!(upca.data(), "036000291452");
+        assert_eq!(upca.text(), "036000291452");
+    }
 }